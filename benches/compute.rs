@@ -0,0 +1,104 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use cutlist::{model, solver};
+
+const SMALL: &str = "
+spacing: 0.25
+boards:
+  - 96x8:A
+  - 96x6:B
+cutlist:
+  - 4@25x2:Leg
+  - 2@12x4:Apron
+  - 4@20x3:Top Table Edges
+";
+
+// few distinct parts, many copies of each
+const DUPLICATES: &str = "
+spacing: 0.25
+boards:
+  - 96x8:A
+  - 96x6:B
+cutlist:
+  - 24@25x2:Slat
+  - 16@14x3:Rail
+  - 12@30x4:Stile
+";
+
+const MIXED: &str = "
+spacing: 0.25
+boards:
+  - 96x8:A
+  - 96x6:B
+  - 72x10:C
+  - 120x4:D
+cutlist:
+  - 4@25x2:Leg
+  - 3@14x5:Top Table Slat
+  - 4@4x.75:Apron Mount
+  - 4@20x3:Top Table Edges
+  - 4@16x3:Lower Table Edge
+  - 2@10x5:Lower Table Slat
+  - 2@12x4:Apron
+  - 6@48x3.5:Shelf
+  - 8@30x1.5:Drawer Side
+  - 4@18x1.5:Drawer Front
+  - 2@60x9:Top
+  - 10@6x2:Block
+";
+
+fn input(yaml: &str) -> model::Input {
+    model::Input::parse(yaml, model::InputFormat::Yaml).unwrap()
+}
+
+fn options() -> solver::Options {
+    // the search is seeded, so a fixed attempt count does a fixed amount of work
+    solver::Options {
+        attempts: 64,
+        ..Default::default()
+    }
+}
+
+fn compute(c: &mut Criterion) {
+    let mut group = c.benchmark_group("compute");
+    for (name, yaml) in [
+        ("small", SMALL),
+        ("duplicates", DUPLICATES),
+        ("mixed", MIXED),
+    ] {
+        let model = input(yaml);
+        let options = options();
+        group.bench_function(name, |b| {
+            b.iter(|| solver::compute(black_box(&model), &options).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn micro(c: &mut Criterion) {
+    let model = input(MIXED);
+    let options = options();
+    let solution = solver::compute(&model, &options).unwrap().remove(0);
+    let cuts: Vec<solver::Cut> = solution
+        .iter()
+        .flat_map(|board| board.cuts())
+        .cloned()
+        .collect();
+
+    c.bench_function("score", |b| b.iter(|| solver::score(black_box(&solution))));
+
+    c.bench_function("accept", |b| {
+        b.iter_batched(
+            || solver::Board::from(&model.boards[0]),
+            |mut board| {
+                for cut in &cuts {
+                    black_box(board.accept(cut, &options));
+                }
+                board
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+criterion_group!(benches, compute, micro);
+criterion_main!(benches);
@@ -0,0 +1,121 @@
+use super::solver;
+
+/// The default width, in characters, of a rendered solution
+pub const COLUMNS: usize = 72;
+
+// the label given to the nth cut of a board; labels repeat after 62 cuts
+fn label(index: usize) -> char {
+    const LABELS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+    LABELS[index % LABELS.len()] as char
+}
+
+// renders a board at `scale` characters per unit of length, with its header and legend
+fn render_board(index: usize, board: &solver::Board, scale: f32) -> String {
+    // terminal cells are about twice as tall as they are wide
+    let columns = ((board.length * scale).round() as usize).max(1);
+    let rows = ((board.width * scale / 2f32).round() as usize).max(1);
+    let mut grid = vec![vec!['.'; columns]; rows];
+    let mut legend = Vec::new();
+
+    let span = |start: f32, size: f32, scale: f32, limit: usize| {
+        let first = ((start * scale).round() as usize).min(limit - 1);
+        let last = (((start + size) * scale).round() as usize).clamp(first + 1, limit);
+        first..last
+    };
+
+    for solver::Placement { cut, x, y, .. } in board.placements() {
+        let label = label(legend.len());
+        // cuts smaller than a cell still get one, truncating whatever they overlap
+        for row in span(y, cut.width, scale / 2f32, rows) {
+            for column in span(x, cut.length, scale, columns) {
+                grid[row][column] = label;
+            }
+        }
+        legend.push(format!(
+            "    {}: {} ({} by {}){}",
+            label,
+            cut.id,
+            cut.length,
+            cut.width,
+            if cut.double_sided { ", face up" } else { "" }
+        ));
+    }
+
+    let score = board
+        .score_breakdown()
+        .map(|breakdown| format!(", score {:.2}", breakdown.score))
+        .unwrap_or_default();
+    let mut lines = vec![format!(
+        "Board {}: {} ({} by {}){}",
+        index + 1,
+        board.id,
+        board.nominal_length,
+        board.nominal_width,
+        score
+    )];
+    let border = format!("+{}+", "-".repeat(columns));
+    lines.push(border.clone());
+    lines.extend(
+        grid.into_iter()
+            .map(|row| format!("|{}|", row.into_iter().collect::<String>())),
+    );
+    lines.push(border);
+    lines.append(&mut legend);
+    lines.join("\n")
+}
+
+/// Renders each board of `solution` as an ascii block headed by its id, dimensions and score,
+/// followed by a legend of its cuts. Boards share a scale which fits the longest in `columns`
+/// characters, so their lengths compare at a glance.
+pub fn render(solution: &[solver::Board], columns: usize) -> String {
+    let longest = solution
+        .iter()
+        .map(|board| board.length)
+        .fold(0f32, f32::max);
+    // leave room for the border
+    let scale = if longest > 0f32 {
+        columns.saturating_sub(2).max(1) as f32 / longest
+    } else {
+        1f32
+    };
+    solution
+        .iter()
+        .enumerate()
+        .map(|(index, board)| render_board(index, board, scale))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+#[cfg(test)]
+mod ascii_tests {
+    use super::*;
+
+    #[test]
+    fn renders_a_block_per_board() {
+        let solution = solver::test_support::solve(
+            "
+            boards:
+              - 96x8:A
+              - 48x6:B
+            cutlist:
+              - 4@25x6:Leg
+              - 2@12x4:Apron
+              - 2@40x6:Top
+            ",
+        );
+        let ascii = render(&solution, COLUMNS);
+
+        let headers: Vec<&str> = ascii
+            .lines()
+            .filter(|line| line.starts_with("Board "))
+            .collect();
+        assert_eq!(headers.len(), solution.len());
+        for (header, board) in headers.iter().zip(&solution) {
+            assert!(header.contains(&board.id));
+        }
+        assert!(ascii
+            .lines()
+            .all(|line| line.chars().count() <= COLUMNS || !line.starts_with(['+', '|'].as_ref())));
+        assert!(ascii.contains("Leg"));
+    }
+}
@@ -0,0 +1,105 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    fs,
+    hash::{Hash, Hasher},
+    path::Path,
+};
+
+use anyhow::Result;
+
+use super::{model, solver};
+
+/// Returns a key identifying the solutions of `model` under `options`; any change to either yields
+/// a different key. Keys are stable for a given build of the crate, not across toolchains.
+pub fn cache_key(model: &model::Input, options: &solver::Options) -> String {
+    // the debug representation covers every field, and the input's maps are ordered
+    let mut hasher = DefaultHasher::new();
+    format!("{:?}", model).hash(&mut hasher);
+    format!("{:?}", options).hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// Returns the solutions cached in `cache_dir` for this model and options if there are any,
+/// otherwise calls `solve` and caches what it returns. A cache entry which can't be read is
+/// treated as a miss.
+pub fn cached<F>(
+    model: &model::Input,
+    options: &solver::Options,
+    cache_dir: &Path,
+    solve: F,
+) -> Result<Vec<Vec<solver::Board>>>
+where
+    F: FnOnce() -> Result<Vec<Vec<solver::Board>>>,
+{
+    let path = cache_dir.join(format!("{}.bin", cache_key(model, options)));
+    if let Ok(bytes) = fs::read(&path) {
+        if let Ok(solutions) = bincode::deserialize(&bytes) {
+            return Ok(solutions);
+        }
+    }
+
+    let solutions = solve()?;
+    fs::create_dir_all(cache_dir)?;
+    fs::write(&path, bincode::serialize(&solutions)?)?;
+    Ok(solutions)
+}
+
+#[cfg(test)]
+mod cache_tests {
+    use super::*;
+
+    fn model() -> model::Input {
+        model::Input::parse(
+            "
+            boards:
+              - 96x8:A
+            cutlist:
+              - 4@25x2:Leg
+              - 2@12x4:Apron
+            ",
+            model::InputFormat::Yaml,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn second_solve_hits_the_cache() {
+        let cache_dir = std::env::temp_dir().join(format!("cutlist-cache-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&cache_dir);
+        let model = model();
+        let options = solver::Options::default();
+
+        let first = cached(&model, &options, &cache_dir, || {
+            solver::compute(&model, &options)
+        })
+        .unwrap();
+        let second = cached(&model, &options, &cache_dir, || {
+            panic!("Expected the cache to be hit")
+        })
+        .unwrap();
+        assert_eq!(
+            serde_json::to_string(&first).unwrap(),
+            serde_json::to_string(&second).unwrap()
+        );
+
+        fs::remove_dir_all(&cache_dir).unwrap();
+    }
+
+    #[test]
+    fn key_changes_with_input_or_options() {
+        let model = model();
+        let options = solver::Options::default();
+        let key = cache_key(&model, &options);
+        assert_eq!(key, cache_key(&model.clone(), &options.clone()));
+
+        let mut changed_model = model.clone();
+        changed_model.spacing = 0.125;
+        assert_ne!(key, cache_key(&changed_model, &options));
+
+        let changed_options = solver::Options {
+            attempts: 12,
+            ..Default::default()
+        };
+        assert_ne!(key, cache_key(&model, &changed_options));
+    }
+}
@@ -0,0 +1,180 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Result;
+use rusqlite::{params, Connection};
+
+use super::solver;
+
+// a row per solve, a row per board of its solution, and a row per cut of each board
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS runs (
+        id INTEGER PRIMARY KEY,
+        timestamp INTEGER NOT NULL,
+        input_hash TEXT NOT NULL,
+        board_count INTEGER NOT NULL,
+        score REAL NOT NULL,
+        waste REAL NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS boards (
+        id INTEGER PRIMARY KEY,
+        run_id INTEGER NOT NULL REFERENCES runs (id),
+        board_index INTEGER NOT NULL,
+        board_id TEXT NOT NULL,
+        length REAL NOT NULL,
+        width REAL NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS cuts (
+        id INTEGER PRIMARY KEY,
+        board_row_id INTEGER NOT NULL REFERENCES boards (id),
+        cut_id TEXT NOT NULL,
+        name TEXT NOT NULL,
+        x REAL NOT NULL,
+        y REAL NOT NULL,
+        length REAL NOT NULL,
+        width REAL NOT NULL
+    );
+";
+
+/// Opens the sqlite database at `path`, creating it and its tables if need be
+pub fn open(path: &Path) -> Result<Connection> {
+    let conn = Connection::open(path)?;
+    create_schema(&conn)?;
+    Ok(conn)
+}
+
+fn create_schema(conn: &Connection) -> Result<()> {
+    conn.execute_batch(SCHEMA)?;
+    Ok(())
+}
+
+/// Returns a key identifying the input document's text, so runs of the same input can be found.
+/// Like `cache::cache_key`, keys are stable for a given build of the crate, not across toolchains.
+pub fn input_hash(input: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    input.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// Records a solve of the input identified by `input_hash`: a run, with the solution's board
+/// count, score and waste (the area of its boards not covered by cuts), and under it a row for
+/// each board and each cut, positioned as in `solver::flat_cut_table`. Returns the run's id.
+pub fn record_solve(
+    conn: &mut Connection,
+    input_hash: &str,
+    solution: &[solver::Board],
+) -> Result<i64> {
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+    let waste: f32 = solution
+        .iter()
+        .filter_map(|board| board.score_breakdown())
+        .map(|breakdown| breakdown.scrap_area)
+        .sum();
+
+    let transaction = conn.transaction()?;
+    transaction.execute(
+        "INSERT INTO runs (timestamp, input_hash, board_count, score, waste)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            timestamp,
+            input_hash,
+            solution.len() as i64,
+            solver::score(solution) as f64,
+            waste as f64
+        ],
+    )?;
+    let run_id = transaction.last_insert_rowid();
+
+    let mut board_row_ids = Vec::new();
+    for (board_index, board) in solution.iter().enumerate() {
+        transaction.execute(
+            "INSERT INTO boards (run_id, board_index, board_id, length, width)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                run_id,
+                board_index as i64,
+                board.id,
+                board.length as f64,
+                board.width as f64
+            ],
+        )?;
+        board_row_ids.push(transaction.last_insert_rowid());
+    }
+    for row in solver::flat_cut_table(solution) {
+        transaction.execute(
+            "INSERT INTO cuts (board_row_id, cut_id, name, x, y, length, width)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                board_row_ids[row.board],
+                row.cut,
+                row.name,
+                row.x as f64,
+                row.y as f64,
+                row.length as f64,
+                row.width as f64
+            ],
+        )?;
+    }
+    transaction.commit()?;
+    Ok(run_id)
+}
+
+#[cfg(test)]
+mod db_tests {
+    use super::*;
+
+    #[test]
+    fn recorded_solves_read_back() {
+        let solution = solver::test_support::solve(
+            "
+            boards:
+              - 96x8:A
+              - 48x4:B
+            cutlist:
+              - 6@25x6:Leg
+              - 2@40x4:Apron
+            ",
+        );
+        let cut_count: usize = solution.iter().map(|board| board.cuts().len()).sum();
+
+        let mut conn = Connection::open_in_memory().unwrap();
+        create_schema(&conn).unwrap();
+        // creating the tables again leaves them be
+        create_schema(&conn).unwrap();
+        let run_id = record_solve(&mut conn, &input_hash("input"), &solution).unwrap();
+        record_solve(&mut conn, &input_hash("other input"), &solution).unwrap();
+
+        let count = |sql: &str| -> i64 {
+            conn.query_row(sql, params![run_id], |row| row.get(0))
+                .unwrap()
+        };
+        assert_eq!(
+            count("SELECT board_count FROM runs WHERE id = ?1"),
+            solution.len() as i64
+        );
+        assert_eq!(
+            count("SELECT COUNT(*) FROM boards WHERE run_id = ?1"),
+            solution.len() as i64
+        );
+        assert_eq!(
+            count(
+                "SELECT COUNT(*) FROM cuts JOIN boards ON cuts.board_row_id = boards.id
+                 WHERE boards.run_id = ?1"
+            ),
+            cut_count as i64
+        );
+        let (hash, score): (String, f64) = conn
+            .query_row(
+                "SELECT input_hash, score FROM runs WHERE id = ?1",
+                params![run_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(hash, input_hash("input"));
+        assert_eq!(score as f32, solver::score(&solution));
+    }
+}
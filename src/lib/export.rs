@@ -0,0 +1,359 @@
+use std::{collections::HashMap, fs::File, io::BufWriter, path::Path, str::FromStr};
+
+use anyhow::{bail, Result};
+use printpdf::{
+    path::PaintMode, BuiltinFont, IndirectFontRef, Line, Mm, PdfDocument, PdfDocumentReference,
+    PdfLayerReference, Point, Rect,
+};
+
+use super::solver;
+
+// US letter, landscape
+const PAGE_WIDTH: f32 = 279.4;
+const PAGE_HEIGHT: f32 = 215.9;
+const MARGIN: f32 = 15f32;
+
+const TITLE_SIZE: f32 = 18f32;
+const TEXT_SIZE: f32 = 11f32;
+const LABEL_SIZE: f32 = 7f32;
+
+/// Space reserved below a board for its dimension ruler, in mm
+const RULER_HEIGHT: f32 = 12f32;
+
+/// Model units between the ruler's ticks, e.g., a foot
+const RULER_INTERVAL: f32 = 12f32;
+
+/// Writes a printable cut sheet for the solution to `path`: a shopping list of the boards it
+/// uses, followed by one page per board drawn to scale with its cuts labeled.
+pub fn pdf<P: AsRef<Path>>(solution: &[solver::Board], path: P) -> Result<()> {
+    let doc = document(solution)?;
+    doc.save(&mut BufWriter::new(File::create(path)?))?;
+    Ok(())
+}
+
+fn document(solution: &[solver::Board]) -> Result<PdfDocumentReference> {
+    let (doc, page, layer) =
+        PdfDocument::new("Cutlist", Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Shopping list");
+    let font = doc.add_builtin_font(BuiltinFont::Helvetica)?;
+
+    let layer = doc.get_page(page).get_layer(layer);
+    render_shopping_list(solution, &layer, &font);
+
+    for (i, board) in solution.iter().enumerate() {
+        let (page, layer) = doc.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Board");
+        let layer = doc.get_page(page).get_layer(layer);
+        layer.use_text(
+            format!(
+                "Board {} of {}: {} ({} by {})",
+                i + 1,
+                solution.len(),
+                board.id,
+                board.nominal_length,
+                board.nominal_width
+            ),
+            TITLE_SIZE,
+            Mm(MARGIN),
+            Mm(PAGE_HEIGHT - MARGIN),
+            &font,
+        );
+        render_board(board, &layer, &font);
+    }
+
+    Ok(doc)
+}
+
+/// Returns a line per distinct board in the solution, e.g., "2 x A (96 by 8)", in order of first use
+fn shopping_list(solution: &[solver::Board]) -> Vec<String> {
+    solver::stock_list(solution)
+        .iter()
+        .map(|line| line.to_string())
+        .collect()
+}
+
+fn render_shopping_list(
+    solution: &[solver::Board],
+    layer: &PdfLayerReference,
+    font: &IndirectFontRef,
+) {
+    let mut y = PAGE_HEIGHT - MARGIN;
+    layer.use_text("Shopping list", TITLE_SIZE, Mm(MARGIN), Mm(y), font);
+    y -= TITLE_SIZE * 0.6;
+    for entry in shopping_list(solution) {
+        y -= TEXT_SIZE * 0.5;
+        layer.use_text(entry, TEXT_SIZE, Mm(MARGIN), Mm(y), font);
+    }
+}
+
+fn line(layer: &PdfLayerReference, start: (f32, f32), end: (f32, f32)) {
+    layer.add_line(Line {
+        points: vec![
+            (Point::new(Mm(start.0), Mm(start.1)), false),
+            (Point::new(Mm(end.0), Mm(end.1)), false),
+        ],
+        is_closed: false,
+    });
+}
+
+/// Draws the board scaled to fit the page below its title, with a ruler along its length
+fn render_board(board: &solver::Board, layer: &PdfLayerReference, font: &IndirectFontRef) {
+    let available_width = PAGE_WIDTH - 2f32 * MARGIN;
+    let available_height = PAGE_HEIGHT - 2f32 * MARGIN - TITLE_SIZE - RULER_HEIGHT;
+    let scale = (available_width / board.length).min(available_height / board.width);
+
+    // pdf coordinates run up from the bottom left; the board hangs from beneath the title
+    let left = MARGIN;
+    let top = PAGE_HEIGHT - MARGIN - TITLE_SIZE;
+    let to_page = |x: f32, y: f32| (left + x * scale, top - y * scale);
+
+    let rect = |x: f32, y: f32, length: f32, width: f32| {
+        let (llx, lly) = to_page(x, y + width);
+        let (urx, ury) = to_page(x + length, y);
+        Rect::new(Mm(llx), Mm(lly), Mm(urx), Mm(ury)).with_mode(PaintMode::Stroke)
+    };
+
+    layer.set_outline_thickness(1f32);
+    layer.add_rect(rect(0f32, 0f32, board.length, board.width));
+
+    layer.set_outline_thickness(0.5);
+    for solver::Placement { cut, x, y, .. } in board.placements() {
+        layer.add_rect(rect(x, y, cut.length, cut.width));
+        let (label_x, label_y) = to_page(x, y + cut.width / 2f32);
+        layer.use_text(
+            format!("{} ({} by {})", cut.id, cut.length, cut.width),
+            LABEL_SIZE,
+            Mm(label_x + 1f32),
+            Mm(label_y),
+            font,
+        );
+    }
+    for x in board.crosscuts() {
+        line(layer, to_page(x, -1f32), to_page(x, board.width + 1f32));
+    }
+
+    // the ruler, ticked every interval along the board's length
+    let (_, ruler_y) = to_page(0f32, board.width);
+    let ruler_y = ruler_y - RULER_HEIGHT / 2f32;
+    line(
+        layer,
+        (left, ruler_y),
+        (left + board.length * scale, ruler_y),
+    );
+    let mut tick = 0f32;
+    while tick <= board.length {
+        let tick_x = left + tick * scale;
+        line(layer, (tick_x, ruler_y - 1.5), (tick_x, ruler_y + 1.5));
+        layer.use_text(
+            format!("{}", tick),
+            LABEL_SIZE,
+            Mm(tick_x - 1f32),
+            Mm(ruler_y - 5f32),
+            font,
+        );
+        tick += RULER_INTERVAL;
+    }
+}
+
+/// The layout of a sheet of sticker labels, in mm
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LabelTemplate {
+    pub page_width: f32,
+    pub page_height: f32,
+    pub columns: usize,
+    pub rows: usize,
+    pub label_width: f32,
+    pub label_height: f32,
+    /// Distance from the page's left edge to the first column of labels
+    pub left: f32,
+    /// Distance from the page's top edge to the first row of labels
+    pub top: f32,
+    /// Distance from one column of labels to the next, including the gap between
+    pub column_pitch: f32,
+    /// Distance from one row of labels to the next, including the gap between
+    pub row_pitch: f32,
+}
+
+impl LabelTemplate {
+    /// Avery 5160 address labels: 30 per US letter page, 2 5/8 by 1 inches
+    pub const AVERY_5160: LabelTemplate = LabelTemplate {
+        page_width: 215.9,
+        page_height: 279.4,
+        columns: 3,
+        rows: 10,
+        label_width: 66.675,
+        label_height: 25.4,
+        left: 4.7625,
+        top: 12.7,
+        column_pitch: 69.85,
+        row_pitch: 25.4,
+    };
+
+    /// Avery 5163 shipping labels: 10 per US letter page, 4 by 2 inches
+    pub const AVERY_5163: LabelTemplate = LabelTemplate {
+        page_width: 215.9,
+        page_height: 279.4,
+        columns: 2,
+        rows: 5,
+        label_width: 101.6,
+        label_height: 50.8,
+        left: 3.96875,
+        top: 12.7,
+        column_pitch: 106.3625,
+        row_pitch: 50.8,
+    };
+
+    fn per_page(&self) -> usize {
+        self.columns * self.rows
+    }
+}
+
+impl FromStr for LabelTemplate {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim_start_matches("avery-") {
+            "5160" => Ok(LabelTemplate::AVERY_5160),
+            "5163" => Ok(LabelTemplate::AVERY_5163),
+            _ => bail!(
+                "Unrecognized label template \"{}\", expected 5160 or 5163",
+                s
+            ),
+        }
+    }
+}
+
+/// Returns the lines of a label per piece placed by the solution, in the order of the flat cut
+/// table: the part's name and which of its pieces this is, its dimensions, and the board and
+/// position on it the piece comes from.
+fn label_lines(solution: &[solver::Board]) -> Vec<[String; 3]> {
+    let rows = solver::flat_cut_table(solution);
+    let mut totals: HashMap<&str, usize> = HashMap::new();
+    for row in &rows {
+        *totals.entry(&row.name).or_default() += 1;
+    }
+    let mut indices: HashMap<&str, usize> = HashMap::new();
+    rows.iter()
+        .map(|row| {
+            let index = indices.entry(&row.name).or_default();
+            *index += 1;
+            [
+                format!("{} ({} of {})", row.cut, index, totals[row.name.as_str()]),
+                format!("{} by {}", row.length, row.width),
+                format!(
+                    "Board {} ({}), at {}, {}",
+                    row.board + 1,
+                    row.board_id,
+                    row.x,
+                    row.y
+                ),
+            ]
+        })
+        .collect()
+}
+
+/// Writes a pdf of sticker labels, one per piece the solution places, laid out on the label
+/// sheet `template` describes, to `path`. Each label names the part and which of its pieces it
+/// is, with the piece's dimensions and the board and position it's cut from.
+pub fn labels<P: AsRef<Path>>(
+    solution: &[solver::Board],
+    template: &LabelTemplate,
+    path: P,
+) -> Result<()> {
+    let doc = label_document(solution, template)?;
+    doc.save(&mut BufWriter::new(File::create(path)?))?;
+    Ok(())
+}
+
+fn label_document(
+    solution: &[solver::Board],
+    template: &LabelTemplate,
+) -> Result<PdfDocumentReference> {
+    let (width, height) = (Mm(template.page_width), Mm(template.page_height));
+    let (doc, page, layer) = PdfDocument::new("Cutlist labels", width, height, "Labels");
+    let font = doc.add_builtin_font(BuiltinFont::Helvetica)?;
+
+    let mut layer = doc.get_page(page).get_layer(layer);
+    for (i, lines) in label_lines(solution).iter().enumerate() {
+        let slot = i % template.per_page();
+        if i > 0 && slot == 0 {
+            let (page, new_layer) = doc.add_page(width, height, "Labels");
+            layer = doc.get_page(page).get_layer(new_layer);
+        }
+        let (column, row) = (slot % template.columns, slot / template.columns);
+        let left = template.left + column as f32 * template.column_pitch + 3f32;
+        let top = template.page_height - template.top - row as f32 * template.row_pitch;
+
+        // the lines are centered down the label, the first set larger; text sits on its baseline,
+        // about 0.8 of a line below the line's top
+        let line_height = TEXT_SIZE * 0.45;
+        let block_height = line_height * lines.len() as f32;
+        let mut y = top - (template.label_height - block_height) / 2f32 - line_height * 0.8;
+        for (j, line) in lines.iter().enumerate() {
+            let size = if j == 0 { TEXT_SIZE } else { LABEL_SIZE };
+            layer.use_text(line.as_str(), size, Mm(left), Mm(y), &font);
+            y -= line_height;
+        }
+    }
+    Ok(doc)
+}
+
+#[cfg(test)]
+mod export_tests {
+    use super::*;
+
+    fn solution() -> Vec<solver::Board> {
+        solver::test_support::solve(
+            "
+            boards:
+              - 96x8:A
+            cutlist:
+              - 2@40x4:Rail
+              - 1@20x3:Stile
+            ",
+        )
+    }
+
+    #[test]
+    fn shopping_list_counts_boards_by_id() {
+        let solution = solution();
+        assert_eq!(
+            shopping_list(&solution),
+            vec![format!("{} x A (96 by 8)", solution.len())]
+        );
+    }
+
+    #[test]
+    fn a_label_is_made_per_placed_piece() {
+        let solution = solution();
+        let lines = label_lines(&solution);
+        let pieces: usize = solution.iter().map(|board| board.cuts().len()).sum();
+        assert_eq!(lines.len(), pieces);
+        assert_eq!(
+            lines
+                .iter()
+                .filter(|lines| lines[0].starts_with("Rail ("))
+                .count(),
+            2
+        );
+        assert!(lines.iter().any(|lines| lines[0] == "Rail (2 of 2)"));
+        assert!(lines.iter().any(|lines| lines[1] == "20 by 3"));
+        assert!(lines[0][2].starts_with("Board 1 (A), at "));
+
+        let bytes = label_document(&solution, &LabelTemplate::AVERY_5160)
+            .unwrap()
+            .save_to_bytes()
+            .unwrap();
+        assert!(bytes.starts_with(b"%PDF"));
+        assert_eq!(
+            "avery-5163".parse::<LabelTemplate>().unwrap(),
+            LabelTemplate::AVERY_5163
+        );
+        assert!("5161".parse::<LabelTemplate>().is_err());
+    }
+
+    #[test]
+    fn pdf_is_produced_for_a_simple_solution() {
+        let bytes = document(&solution()).unwrap().save_to_bytes().unwrap();
+        assert!(bytes.starts_with(b"%PDF"));
+        assert!(bytes.len() > 100);
+    }
+}
@@ -0,0 +1,89 @@
+use rand::prelude::*;
+use rand_pcg::Pcg64;
+
+use super::model;
+
+const BOARD_LENGTHS: &[f32] = &[72f32, 96f32, 120f32];
+const BOARD_WIDTHS: &[f32] = &[4f32, 6f32, 8f32, 10f32];
+const KERF: f32 = 0.125;
+
+// rounds to the nearest quarter inch, as a cutlist would be written
+fn quarters(value: f32) -> f32 {
+    (value * 4f32).round() / 4f32
+}
+
+/// Generates a random yaml input document with two board sizes and `cut_types` kinds of cut,
+/// for demos and stress tests. Every cut fits within both board sizes, so the document is always
+/// solvable. The same seed generates the same document.
+pub fn random_input(cut_types: usize, seed: u64) -> String {
+    let mut rng = Pcg64::seed_from_u64(seed);
+
+    let mut widths = BOARD_WIDTHS.to_vec();
+    widths.shuffle(&mut rng);
+    let boards: Vec<model::Board> = ["A", "B"]
+        .iter()
+        .zip(widths)
+        .map(|(id, width)| {
+            let length = *BOARD_LENGTHS.choose(&mut rng).unwrap();
+            model::Board::new(length, width, id, None, 0f32).unwrap()
+        })
+        .collect();
+
+    // leave room for the kerf around the largest cut on the smallest board
+    let max_length = boards
+        .iter()
+        .map(|board| board.length)
+        .fold(f32::MAX, f32::min)
+        * 0.75;
+    let max_width = boards
+        .iter()
+        .map(|board| board.width)
+        .fold(f32::MAX, f32::min)
+        - KERF;
+    let cutlist: Vec<model::Cut> = (1..=cut_types)
+        .map(|i| {
+            let length = quarters(rng.gen_range(4f32..max_length));
+            let width = quarters(rng.gen_range(1f32..max_width)).min(max_width);
+            let count = model::CountSpec::Fixed(rng.gen_range(1..=4));
+            model::Cut::new(length, width, count, &format!("Part {}", i), None).unwrap()
+        })
+        .collect();
+
+    let mut lines = vec![
+        format!("version: {}", model::INPUT_VERSION),
+        format!("kerf: {}", KERF),
+        "boards:".to_owned(),
+    ];
+    lines.extend(boards.iter().map(|board| format!("  - {}", board)));
+    lines.push("cutlist:".to_owned());
+    lines.extend(cutlist.iter().map(|cut| format!("  - {}", cut)));
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod generate_tests {
+    use super::*;
+    use crate::solver;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn generated_inputs_are_solvable() {
+        for seed in 0..16 {
+            let yaml = random_input(8, seed);
+            let docs = YamlLoader::load_from_str(&yaml).unwrap();
+            let input = model::Input::from(&docs[0]).unwrap();
+            assert_eq!(input.cutlist.len(), 8);
+            assert_eq!(input.boards.len(), 2);
+            assert_eq!(input.spacing, KERF);
+            assert!(solver::is_a_solution_possible(&input), "{}", yaml);
+        }
+        assert_eq!(random_input(4, 7), random_input(4, 7));
+
+        let options = solver::Options {
+            attempts: 8,
+            ..Default::default()
+        };
+        let input = model::Input::parse(&random_input(8, 0), model::InputFormat::Yaml).unwrap();
+        assert!(solver::compute(&input, &options).is_ok());
+    }
+}
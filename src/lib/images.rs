@@ -0,0 +1,267 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+use image::{Rgb, RgbImage};
+
+use super::solver;
+
+/// Pixels along the length of each board, whatever its length; its width follows its aspect ratio
+pub const BOARD_PIXELS: u32 = 1600;
+
+/// Pixels around the drawing
+const MARGIN: u32 = 16;
+
+/// Pixels per pixel of the bitmap font, for headers and ruler numbers, and for cut labels
+const HEADER_SCALE: u32 = 3;
+const LABEL_SCALE: u32 = 2;
+
+const BACKGROUND: Rgb<u8> = Rgb([255, 255, 255]);
+const TEXT: Rgb<u8> = Rgb([0, 0, 0]);
+const BOARD_FILL: Rgb<u8> = Rgb([0xe6, 0xe6, 0xe6]);
+const CUT_FILL: Rgb<u8> = Rgb([0xb0, 0xb0, 0xb0]);
+const CUT_STROKE: Rgb<u8> = Rgb([0x40, 0x40, 0x40]);
+const CROSSCUT_STROKE: Rgb<u8> = Rgb([0xff, 0, 0]);
+
+// rows of a 3 by 5 pixel glyph, top first, the high bit of each row leftmost; letters are drawn
+// as capitals, and characters without a glyph as blanks
+fn glyph(c: char) -> [u8; 5] {
+    match c.to_ascii_uppercase() {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b001, 0b001, 0b001],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        'A' => [0b010, 0b101, 0b111, 0b101, 0b101],
+        'B' => [0b110, 0b101, 0b110, 0b101, 0b110],
+        'C' => [0b011, 0b100, 0b100, 0b100, 0b011],
+        'D' => [0b110, 0b101, 0b101, 0b101, 0b110],
+        'E' => [0b111, 0b100, 0b110, 0b100, 0b111],
+        'F' => [0b111, 0b100, 0b110, 0b100, 0b100],
+        'G' => [0b011, 0b100, 0b101, 0b101, 0b011],
+        'H' => [0b101, 0b101, 0b111, 0b101, 0b101],
+        'I' => [0b111, 0b010, 0b010, 0b010, 0b111],
+        'J' => [0b001, 0b001, 0b001, 0b101, 0b010],
+        'K' => [0b101, 0b101, 0b110, 0b101, 0b101],
+        'L' => [0b100, 0b100, 0b100, 0b100, 0b111],
+        'M' => [0b101, 0b111, 0b111, 0b101, 0b101],
+        'N' => [0b110, 0b101, 0b101, 0b101, 0b101],
+        'O' => [0b010, 0b101, 0b101, 0b101, 0b010],
+        'P' => [0b110, 0b101, 0b110, 0b100, 0b100],
+        'Q' => [0b010, 0b101, 0b101, 0b110, 0b011],
+        'R' => [0b110, 0b101, 0b110, 0b101, 0b101],
+        'S' => [0b011, 0b100, 0b010, 0b001, 0b110],
+        'T' => [0b111, 0b010, 0b010, 0b010, 0b010],
+        'U' => [0b101, 0b101, 0b101, 0b101, 0b111],
+        'V' => [0b101, 0b101, 0b101, 0b101, 0b010],
+        'W' => [0b101, 0b101, 0b111, 0b111, 0b101],
+        'X' => [0b101, 0b101, 0b010, 0b101, 0b101],
+        'Y' => [0b101, 0b101, 0b010, 0b010, 0b010],
+        'Z' => [0b111, 0b001, 0b010, 0b100, 0b111],
+        '.' => [0b000, 0b000, 0b000, 0b000, 0b010],
+        ',' => [0b000, 0b000, 0b000, 0b010, 0b100],
+        ':' => [0b000, 0b010, 0b000, 0b010, 0b000],
+        '-' => [0b000, 0b000, 0b111, 0b000, 0b000],
+        '#' => [0b101, 0b111, 0b101, 0b111, 0b101],
+        '/' => [0b001, 0b001, 0b010, 0b100, 0b100],
+        '(' => [0b010, 0b100, 0b100, 0b100, 0b010],
+        ')' => [0b010, 0b001, 0b001, 0b001, 0b010],
+        _ => [0; 5],
+    }
+}
+
+fn text_width(text: &str, scale: u32) -> u32 {
+    // each glyph is 3 wide, with a pixel between
+    (text.chars().count() as u32 * 4).saturating_sub(1) * scale
+}
+
+fn text_height(scale: u32) -> u32 {
+    5 * scale
+}
+
+fn fill_rect(image: &mut RgbImage, x: u32, y: u32, width: u32, height: u32, color: Rgb<u8>) {
+    for py in y..(y + height).min(image.height()) {
+        for px in x..(x + width).min(image.width()) {
+            image.put_pixel(px, py, color);
+        }
+    }
+}
+
+fn stroke_rect(image: &mut RgbImage, x: u32, y: u32, width: u32, height: u32, color: Rgb<u8>) {
+    fill_rect(image, x, y, width, 1, color);
+    fill_rect(image, x, y + height.saturating_sub(1), width, 1, color);
+    fill_rect(image, x, y, 1, height, color);
+    fill_rect(image, x + width.saturating_sub(1), y, 1, height, color);
+}
+
+fn draw_text(image: &mut RgbImage, x: u32, y: u32, text: &str, scale: u32, color: Rgb<u8>) {
+    for (i, c) in text.chars().enumerate() {
+        let left = x + i as u32 * 4 * scale;
+        for (row, bits) in glyph(c).iter().enumerate() {
+            for column in 0..3 {
+                if bits & (0b100 >> column) != 0 {
+                    let (px, py) = (left + column * scale, y + row as u32 * scale);
+                    fill_rect(image, px, py, scale, scale, color);
+                }
+            }
+        }
+    }
+}
+
+// draws as much of the text as fits in `width`, centered on (x, y)
+fn draw_centered_text(image: &mut RgbImage, x: u32, y: u32, width: u32, text: &str, scale: u32) {
+    let fitting = (width / (4 * scale)) as usize;
+    let text: String = text.chars().take(fitting).collect();
+    let half_width = text_width(&text, scale) / 2;
+    let half_height = text_height(scale) / 2;
+    draw_text(
+        image,
+        x.saturating_sub(half_width),
+        y.saturating_sub(half_height),
+        &text,
+        scale,
+        TEXT,
+    );
+}
+
+/// Renders a board to an image `BOARD_PIXELS` long drawn to scale: headed by its index, id and
+/// dimensions, above a ruler marking each unit of its length and numbering each twelfth, with its
+/// cuts labeled and its crosscuts marked.
+pub fn render_board(index: usize, board: &solver::Board) -> RgbImage {
+    let scale = BOARD_PIXELS as f32 / board.length;
+    let px = |value: f32| (value * scale).round() as u32;
+
+    let header = format!(
+        "Board {}: {} ({} by {})",
+        index + 1,
+        board.id,
+        board.nominal_length,
+        board.nominal_width
+    );
+    let ruler_height = text_height(LABEL_SCALE) + 16;
+    let board_top = MARGIN + text_height(HEADER_SCALE) + MARGIN + ruler_height;
+    let board_height = px(board.width).max(1);
+    let mut image = RgbImage::from_pixel(
+        BOARD_PIXELS + MARGIN * 2,
+        board_top + board_height + MARGIN,
+        BACKGROUND,
+    );
+    draw_text(&mut image, MARGIN, MARGIN, &header, HEADER_SCALE, TEXT);
+
+    // ticks rise from the board's top edge; each unit gets one if they're legibly far apart
+    for unit in 0..=board.length.floor() as u32 {
+        let tick = if unit % 12 == 0 {
+            12
+        } else if unit % 6 == 0 {
+            8
+        } else if scale >= 4f32 {
+            4
+        } else {
+            continue;
+        };
+        let x = MARGIN + px(unit as f32).min(BOARD_PIXELS - 1);
+        fill_rect(&mut image, x, board_top - tick, 1, tick, TEXT);
+        if unit % 12 == 0 {
+            let label = unit.to_string();
+            let label_x = x.saturating_sub(text_width(&label, LABEL_SCALE) / 2);
+            let label_y = board_top - 12 - 4 - text_height(LABEL_SCALE);
+            draw_text(&mut image, label_x, label_y, &label, LABEL_SCALE, TEXT);
+        }
+    }
+
+    fill_rect(
+        &mut image,
+        MARGIN,
+        board_top,
+        BOARD_PIXELS,
+        board_height,
+        BOARD_FILL,
+    );
+    for solver::Placement { cut, x, y, .. } in board.placements() {
+        let (left, top) = (MARGIN + px(x), board_top + px(y));
+        let (width, height) = (px(cut.length).max(1), px(cut.width).max(1));
+        fill_rect(&mut image, left, top, width, height, CUT_FILL);
+        stroke_rect(&mut image, left, top, width, height, CUT_STROKE);
+        if height > text_height(LABEL_SCALE) {
+            draw_centered_text(
+                &mut image,
+                left + width / 2,
+                top + height / 2,
+                width.saturating_sub(4),
+                &cut.id,
+                LABEL_SCALE,
+            );
+        }
+    }
+    // over the cuts, as the stack following each would cover it
+    for crosscut in board.crosscuts() {
+        let x = MARGIN + px(crosscut) - 1;
+        fill_rect(&mut image, x, board_top, 3, board_height, CROSSCUT_STROKE);
+    }
+    image
+}
+
+// board ids may hold anything, so keep only what's safe in a file name
+fn file_name(index: usize, id: &str) -> String {
+    let id: String = id
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    format!("board-{}-{}.png", index + 1, id)
+}
+
+/// Renders each board of the solution, per `render_board`, to its own png in `dir`, e.g.,
+/// board-1-Oak.png, creating `dir` if need be. Files are numbered as boards often share an id.
+/// Returns the paths written, in board order.
+pub fn write_board_images(solution: &[solver::Board], dir: &Path) -> Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir)?;
+    let mut paths = Vec::new();
+    for (index, board) in solution.iter().enumerate() {
+        let path = dir.join(file_name(index, &board.id));
+        render_board(index, board).save(&path)?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod images_tests {
+    use super::*;
+
+    #[test]
+    fn writes_an_image_per_board() {
+        let solution = solver::test_support::solve(
+            "
+            boards:
+              - 96x8:A
+              - 48x4:B
+            cutlist:
+              - 6@25x6:Leg
+              - 2@40x4:Apron
+            ",
+        );
+        assert!(solution.len() > 1);
+
+        let dir = std::env::temp_dir().join(format!("cutlist-images-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let paths = write_board_images(&solution, &dir).unwrap();
+        assert_eq!(paths.len(), solution.len());
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), solution.len());
+
+        for (path, board) in paths.iter().zip(&solution) {
+            let image = image::open(path).unwrap().to_rgb8();
+            assert_eq!(image.width(), BOARD_PIXELS + MARGIN * 2);
+            assert!(image.height() > (BOARD_PIXELS as f32 * board.width / board.length) as u32);
+        }
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(file_name(0, "White Oak/4"), "board-1-White-Oak-4.png");
+    }
+}
@@ -1,3 +1,59 @@
+use anyhow::Result;
+
+pub mod ascii;
+pub mod cache;
+#[cfg(feature = "db")]
+pub mod db;
+#[cfg(feature = "pdf")]
+pub mod export;
+pub mod generate;
+#[cfg(feature = "images")]
+pub mod images;
 pub mod model;
 pub mod solver;
+pub mod svg;
+pub mod table;
+#[cfg(feature = "gui")]
 pub mod visualizer;
+
+/// Parses a yaml input document, solves it, and returns the best solution as json.
+/// This entry point has no dependency on the visualizer, so it's usable from a wasm build.
+pub fn solve_from_yaml(yaml: &str, attempts: usize) -> Result<String> {
+    let input = model::Input::parse(yaml, model::InputFormat::Yaml)?;
+    let options = solver::Options {
+        attempts,
+        ..Default::default()
+    };
+    let solutions = solver::compute(&input, &options)?;
+    Ok(serde_json::to_string(&solutions[0])?)
+}
+
+#[cfg(test)]
+mod lib_tests {
+    use super::*;
+
+    #[test]
+    fn solve_from_yaml_returns_json() {
+        let json = solve_from_yaml(
+            "
+            boards:
+              - 96x8:A
+            cutlist:
+              - 2@24x4:Shelf
+              - 1@12x2:Cleat
+            ",
+            16,
+        )
+        .expect("Expected input to solve");
+
+        let solution: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let boards = solution.as_array().expect("Expected an array of boards");
+        assert!(!boards.is_empty());
+        assert_eq!(boards[0]["id"], "A");
+    }
+
+    #[test]
+    fn solve_from_yaml_reports_bad_input() {
+        assert!(solve_from_yaml("boards: []", 16).is_err());
+    }
+}
@@ -1,5 +1,11 @@
-use anyhow::{bail, Result};
-use yaml_rust::Yaml;
+use std::{collections::BTreeMap, fmt, path::Path, str::FromStr};
+
+use anyhow::{bail, Context, Result};
+use log::warn;
+use rand::prelude::*;
+use rand_pcg::Pcg64;
+use serde::{Deserialize, Serialize};
+use yaml_rust::{Yaml, YamlEmitter, YamlLoader};
 
 fn f32_eq(a: f32, b: f32) -> bool {
     (a - b).abs() < 1e-4
@@ -10,6 +16,108 @@ pub struct Board {
     pub width: f32,
     pub length: f32,
     pub id: String,
+    /// If set, solutions must use exactly this many of this board
+    pub exact_count: Option<usize>,
+    /// Extra usable length beyond the nominal `length`, as boards are often sold a little long
+    pub overage: f32,
+    /// If the width was converted from a nominal lumber size, the nominal width it was sold as
+    pub nominal_width: Option<f32>,
+    /// If true, the board is sheet stock, e.g., plywood, which has no grain and is packed in 2D
+    /// by `solver::pack_sheets` rather than in stacks
+    pub sheet: bool,
+    /// If set, solutions use at most this many of this board, e.g., one of each board of a lot
+    pub max_count: Option<usize>,
+    /// If true, the board is already surfaced, e.g., S4S, so no jointing allowance is taken from it
+    pub dressed: bool,
+    /// Length and width lost to jointing and squaring a rough board; set from the input's
+    /// `jointing` for boards which aren't dressed
+    pub jointing: f32,
+    /// Lengths trimmed off the board's start and far end to square them; set from the input's
+    /// `end_trim` for boards which aren't sheet stock
+    pub end_trims: (f32, f32),
+    /// Regions of the board no cut may overlap, e.g., knots or checks
+    pub defects: Vec<Defect>,
+    /// The board's thickness, if given; cuts which give one only go on boards at least as thick
+    pub thickness: Option<f32>,
+    /// If set, the most a cut's thickness may be planed down from the board's; set from the
+    /// input's `planing_allowance`
+    pub planing_allowance: Option<f32>,
+    /// If the board was expanded from a lot, the lot's id, e.g., "Oak" for "Oak #1"; pins and
+    /// materials naming the lot match any of its boards
+    pub lot: Option<String>,
+}
+
+/// True if a cut of `cut_thickness` may be planed from a board of `board_thickness`: the board is
+/// at least as thick, and thicker by no more than `planing_allowance` if it's set. A cut or board
+/// without a thickness matches any.
+pub fn thickness_fits(
+    board_thickness: Option<f32>,
+    planing_allowance: Option<f32>,
+    cut_thickness: Option<f32>,
+) -> bool {
+    match (board_thickness, cut_thickness) {
+        (Some(board_thickness), Some(cut_thickness)) => {
+            let excess = board_thickness - cut_thickness;
+            excess > -1e-4 && planing_allowance.is_none_or(|allowance| excess < allowance + 1e-4)
+        }
+        _ => true,
+    }
+}
+
+// splits a spec's width from the thickness which may follow it, e.g., 8x0.75
+fn split_thickness(width: &str) -> Result<(f32, Option<f32>)> {
+    let (width, thickness) = match width.split_once('x') {
+        Some((width, thickness)) => (width, Some(thickness.parse::<f32>()?)),
+        None => (width, None),
+    };
+    if thickness.is_some_and(|thickness| thickness <= 0f32) {
+        bail!("Thickness must be greater than 0")
+    }
+    Ok((width.parse::<f32>()?, thickness))
+}
+
+/// A region of a board which can't be used, e.g., a knot or check, positioned by its offsets
+/// along the board's length from its end and across its width from its edge
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Defect {
+    pub x: f32,
+    pub y: f32,
+    pub length: f32,
+    pub width: f32,
+}
+
+impl Defect {
+    /// Parses a defect specification format string in form of: 3x1@10x2, which yields
+    /// Defect { length: 3, width: 1, x: 10, y: 2 }, a 3 by 1 region 10 along the board and 2 across
+    pub fn parse(spec: &str) -> Result<Defect> {
+        if let Some((size, offset)) = spec.split_once("@") {
+            if let (Some((length, width)), Some((x, y))) =
+                (size.split_once("x"), offset.split_once("x"))
+            {
+                let defect = Defect {
+                    x: x.parse::<f32>()?,
+                    y: y.parse::<f32>()?,
+                    length: length.parse::<f32>()?,
+                    width: width.parse::<f32>()?,
+                };
+                if defect.length <= 0f32 || defect.width <= 0f32 {
+                    bail!("A defect's length and width must be greater than 0")
+                }
+                if defect.x < 0f32 || defect.y < 0f32 {
+                    bail!("A defect's offsets must not be negative")
+                }
+                return Ok(defect);
+            }
+        }
+        bail!("Invalid Defect format string");
+    }
+}
+
+/// Formats the defect as a spec string which `Defect::parse` accepts
+impl fmt::Display for Defect {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}x{}@{}x{}", self.length, self.width, self.x, self.y)
+    }
 }
 
 impl PartialEq for Board {
@@ -19,38 +127,363 @@ impl PartialEq for Board {
 }
 
 impl Board {
-    /// Parses a Board specification format string in form of: 96x6.5, which yields
-    /// Board { length: 96, width: 6.5 }
+    /// Creates a Board, validating its dimensions and id
+    pub fn new(
+        length: f32,
+        width: f32,
+        id: &str,
+        exact_count: Option<usize>,
+        overage: f32,
+    ) -> Result<Board> {
+        if length <= 0f32 {
+            bail!("Length must be greater than 0")
+        }
+        if width <= 0f32 {
+            bail!("Width must be greater than 0")
+        }
+        if id.is_empty() {
+            bail!("Id must be non-empty")
+        }
+        if exact_count == Some(0) {
+            bail!("Exact count must be at least 1")
+        }
+        if overage < 0f32 {
+            bail!("Overage must not be negative")
+        }
+        Ok(Board {
+            length,
+            width,
+            id: id.to_owned(),
+            exact_count,
+            overage,
+            nominal_width: None,
+            sheet: false,
+            max_count: None,
+            dressed: false,
+            jointing: 0f32,
+            end_trims: (0f32, 0f32),
+            defects: Vec::new(),
+            thickness: None,
+            planing_allowance: None,
+            lot: None,
+        })
+    }
+
+    /// True if the board goes by `id`: its own, or that of the lot it was expanded from
+    pub fn has_id(&self, id: &str) -> bool {
+        self.id == id || self.lot.as_deref() == Some(id)
+    }
+
+    /// The length of the board available to cuts, including any overage, less any jointing and
+    /// end trims
+    pub fn usable_length(&self) -> f32 {
+        self.length + self.overage - self.jointing - self.end_trims.0 - self.end_trims.1
+    }
+
+    /// The width of the board available to cuts, less any jointing
+    pub fn usable_width(&self) -> f32 {
+        self.width - self.jointing
+    }
+
+    /// True if a cut of the given thickness may be planed from the board; see `thickness_fits`
+    pub fn accepts_thickness(&self, thickness: Option<f32>) -> bool {
+        thickness_fits(self.thickness, self.planing_allowance, thickness)
+    }
+
+    /// True if the board is wider than it is long, as when the length and width of its spec are
+    /// written the wrong way around, e.g., 6x96:Oak. Sheet stock has no grain, so is never transposed.
+    pub fn looks_transposed(&self) -> bool {
+        !self.sheet && self.width > self.length
+    }
+
+    /// Parses a Board specification format string in form of: 96x6.5:A, which yields
+    /// Board { length: 96, width: 6.5, id: "A" }
+    /// An exact count may be appended to the id, e.g., 96x6.5:A=3, requiring exactly 3 of the board be used.
+    /// An overage may follow the id, e.g., 96x6.5:A+1, for a board sold as 96 but usable to 97.
+    /// Sheet stock is marked by a #sheet suffix on the id, e.g., 96x48:Ply#sheet.
+    /// Dressed stock is marked by a ! suffix on the id, e.g., 96x6.5:A!, and loses no jointing.
+    /// A thickness may follow the width, e.g., 96x6.5x0.75:A.
+    /// Defects follow everything else, each as a #defect suffix holding a `Defect` spec, e.g.,
+    /// 96x6.5:A=3#defect(3x1@10x2)#defect(2x2@40x0), and must lie within the board.
+    /// A board wider than it is long is accepted, with a warning; see `looks_transposed`.
     pub fn parse(spec: &str) -> Result<Board> {
-        if let Some((length, remainder)) = spec.split_once("x") {
+        let (board_spec, defect_specs) = Self::split_defects(spec);
+        let defects = defect_specs
+            .iter()
+            .map(|defect| Defect::parse(defect))
+            .collect::<Result<Vec<_>>>()?;
+        if let Some((length, remainder)) = board_spec.split_once("x") {
             let length = length.parse::<f32>()?;
             if let Some((width, id)) = remainder.split_once(":") {
-                let width = width.parse::<f32>()?;
-                let id = String::from(id);
-                if length <= 0f32 {
-                    bail!("Length must be greater than 0")
-                }
-                if width <= 0f32 {
-                    bail!("Width must be greater than 0")
-                }
-                if id.is_empty() {
-                    bail!("Id must be non-empty")
+                let (width, thickness) = split_thickness(width)?;
+                let (id, exact_count) = match id.split_once("=") {
+                    Some((id, exact_count)) => (id, Some(exact_count.parse::<usize>()?)),
+                    None => (id, None),
+                };
+                // a + is only an overage if a number follows it, so ids like Oak+Walnut still parse
+                let (id, overage) = match id
+                    .rsplit_once("+")
+                    .and_then(|(id, overage)| Some((id, overage.parse::<f32>().ok()?)))
+                {
+                    Some((id, overage)) => (id, overage),
+                    None => (id, 0f32),
+                };
+                let (id, sheet) = match id.strip_suffix("#sheet") {
+                    Some(id) => (id, true),
+                    None => (id, false),
+                };
+                let (id, dressed) = match id.strip_suffix("!") {
+                    Some(id) => (id, true),
+                    None => (id, false),
+                };
+                let mut board = Board::new(length, width, id, exact_count, overage)?;
+                board.sheet = sheet;
+                board.thickness = thickness;
+                board.dressed = dressed;
+                let board = board.with_defects(defects)?;
+                if board.looks_transposed() {
+                    warn!(
+                        "Board {} is wider than it is long; did you mean {}x{}:{}?",
+                        spec, board.width, board.length, board.id
+                    );
                 }
-                return Ok(Board { length, width, id });
+                return Ok(board);
             }
         }
-        bail!("Invalid format string");
+        bail!("Invalid Board format string");
+    }
+
+    /// Marks the defects on the board, validating that each lies within it
+    pub fn with_defects(mut self, defects: Vec<Defect>) -> Result<Board> {
+        for defect in &defects {
+            if defect.x + defect.length > self.length + self.overage
+                || defect.y + defect.width > self.width
+            {
+                bail!("Defect {} lies outside of the board", defect);
+            }
+        }
+        self.defects = defects;
+        Ok(self)
+    }
+
+    // splits the spec into the board itself and the specs of its trailing #defect suffixes
+    fn split_defects(spec: &str) -> (&str, Vec<&str>) {
+        match spec.split_once("#defect(") {
+            Some((board, defects)) => (
+                board,
+                defects
+                    .split("#defect(")
+                    .map(|defect| defect.strip_suffix(")").unwrap_or(defect))
+                    .collect(),
+            ),
+            None => (spec, Vec::new()),
+        }
+    }
+
+    /// Parses a Board specification whose numbers use a comma as their decimal separator, e.g.,
+    /// 96,5x5,5:A+0,5=3. Commas in the id are left alone.
+    pub fn parse_decimal_comma(spec: &str) -> Result<Board> {
+        Self::parse_in(spec, Decimal::Comma)
+    }
+
+    fn parse_in(spec: &str, decimal: Decimal) -> Result<Board> {
+        let spec = match (decimal, spec.split_once(":")) {
+            (Decimal::Comma, Some((dimensions, id))) => {
+                let (id, defects) = id.split_at(id.find("#defect(").unwrap_or(id.len()));
+                let defects = defects.replace(',', ".");
+                let (id, exact_count) = match id.split_once("=") {
+                    Some((id, exact_count)) => (id, format!("={}", exact_count)),
+                    None => (id, String::new()),
+                };
+                let id = match id.rsplit_once("+") {
+                    Some((id, overage)) if overage.replace(',', ".").parse::<f32>().is_ok() => {
+                        format!("{}+{}", id, overage.replace(',', "."))
+                    }
+                    _ => id.to_owned(),
+                };
+                format!(
+                    "{}:{}{}{}",
+                    dimensions.replace(',', "."),
+                    id,
+                    exact_count,
+                    defects
+                )
+            }
+            _ => spec.to_owned(),
+        };
+        Self::parse(&spec)
     }
 }
 
 impl Eq for Board {}
 
+/// A lot of rough boards sharing a width but varying in length, e.g., a bundle of 10 boards
+/// averaging 96 long, give or take 6
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoardLot {
+    pub count: usize,
+    /// The mean length of the lot's boards
+    pub length: f32,
+    /// How far a board's length may fall from the mean, either way
+    pub variance: f32,
+    pub width: f32,
+    pub id: String,
+}
+
+impl BoardLot {
+    /// Parses a lot specification format string in form of: 10@96~6x8:A, which yields
+    /// BoardLot { count: 10, length: 96, variance: 6, width: 8, id: "A" }
+    /// The variance may also be written with a plus-minus sign, e.g., 10@96±6x8:A.
+    pub fn parse(spec: &str) -> Result<BoardLot> {
+        if let Some((count, remainder)) = spec.split_once("@") {
+            let count = count.parse::<usize>()?;
+            if let Some((length, remainder)) = remainder.split_once(['~', '±'].as_ref()) {
+                let length = length.parse::<f32>()?;
+                if let Some((variance, remainder)) = remainder.split_once("x") {
+                    let variance = variance.parse::<f32>()?;
+                    if let Some((width, id)) = remainder.split_once(":") {
+                        let width = width.parse::<f32>()?;
+                        if count < 1 {
+                            bail!("A lot must have at least 1 board")
+                        }
+                        if variance < 0f32 || variance >= length {
+                            bail!("Variance must be at least 0 and less than the length")
+                        }
+                        // validates the dimensions and id shared by the lot's boards
+                        Board::new(length, width, id, None, 0f32)?;
+                        return Ok(BoardLot {
+                            count,
+                            length,
+                            variance,
+                            width,
+                            id: id.to_owned(),
+                        });
+                    }
+                }
+            }
+        }
+        bail!("Invalid Board lot format string");
+    }
+
+    /// Expands the lot into `count` distinct boards, numbered e.g., "A #1", "A #2", each used at
+    /// most once, with lengths drawn uniformly from within the variance of the mean, to the
+    /// nearest quarter. The same seed expands the same boards. Fails if a drawn board isn't valid,
+    /// e.g., when the variance reaches down to a zero length.
+    pub fn expand(&self, seed: u64) -> Result<Vec<Board>> {
+        let mut rng = Pcg64::seed_from_u64(seed);
+        let (shortest, longest) = (self.length - self.variance, self.length + self.variance);
+        (1..=self.count)
+            .map(|i| {
+                let length = rng.gen_range(shortest..=longest);
+                let length = ((length * 4f32).round() / 4f32).clamp(shortest, longest);
+                Ok(Board {
+                    max_count: Some(1),
+                    lot: Some(self.id.clone()),
+                    ..Board::new(
+                        length,
+                        self.width,
+                        &format!("{} #{}", self.id, i),
+                        None,
+                        0f32,
+                    )?
+                })
+            })
+            .collect()
+    }
+}
+
+// the notation numbers in spec strings are written in
+#[derive(Clone, Copy)]
+enum Decimal {
+    Point,
+    Comma,
+}
+
+/// Formats the board as a spec string which `Board::parse` accepts
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}x{}", self.length, self.width)?;
+        if let Some(thickness) = self.thickness {
+            write!(f, "x{}", thickness)?;
+        }
+        write!(f, ":{}", self.id)?;
+        if self.dressed {
+            write!(f, "!")?;
+        }
+        if self.sheet {
+            write!(f, "#sheet")?;
+        }
+        if self.overage > 0f32 {
+            write!(f, "+{}", self.overage)?;
+        }
+        if let Some(exact_count) = self.exact_count {
+            write!(f, "={}", exact_count)?;
+        }
+        for defect in &self.defects {
+            write!(f, "#defect({})", defect)?;
+        }
+        Ok(())
+    }
+}
+
+/// How many of a cut to make
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CountSpec {
+    /// Exactly this many
+    Fixed(i32),
+    /// As many as fit in the space left over once the fixed cuts are placed
+    Fill,
+}
+
+impl CountSpec {
+    /// The number of pieces which must be cut; none for `Fill`, whose pieces are made from leftovers
+    pub fn fixed(&self) -> i32 {
+        match self {
+            CountSpec::Fixed(count) => *count,
+            CountSpec::Fill => 0,
+        }
+    }
+}
+
+impl fmt::Display for CountSpec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CountSpec::Fixed(count) => write!(f, "{}", count),
+            CountSpec::Fill => write!(f, "fill"),
+        }
+    }
+}
+
+impl FromStr for CountSpec {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if s == "fill" {
+            Ok(CountSpec::Fill)
+        } else {
+            Ok(CountSpec::Fixed(s.parse::<i32>()?))
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Cut {
     pub length: f32,
     pub width: f32,
-    pub count: i32,
+    pub count: CountSpec,
     pub name: String,
+    /// A note that the part isn't rectangular, e.g., "curved"; it's packed by its bounding box
+    pub shape: Option<String>,
+    /// If true, both faces of the part get marked, so plans note which face is up; packing ignores it
+    pub double_sided: bool,
+    /// If true, the part's grain must run along its length, e.g., for a structural part, so it's
+    /// never laid out rotated on a board
+    pub grain: bool,
+    /// If true, the pieces are a set, e.g., for a bookmatched panel, ripped one beside the next
+    /// from a single stack so their grain flows from piece to piece
+    pub sequential: bool,
+    /// The part's thickness, if given; it only goes on boards at least as thick
+    pub thickness: Option<f32>,
 }
 
 impl PartialEq for Cut {
@@ -59,104 +492,1168 @@ impl PartialEq for Cut {
             && f32_eq(self.width, other.width)
             && self.count == other.count
             && self.name == other.name
+            && self.shape == other.shape
+            && self.double_sided == other.double_sided
+            && self.grain == other.grain
+            && self.sequential == other.sequential
+            && self.thickness == other.thickness
     }
 }
 
 impl Eq for Cut {}
 
+/// Formats the cut as a spec string which `Cut::parse` accepts
+impl fmt::Display for Cut {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}@{}x{}", self.count, self.length, self.width)?;
+        if let Some(thickness) = self.thickness {
+            write!(f, "x{}", thickness)?;
+        }
+        write!(f, ":{}", self.name)?;
+        if let Some(shape) = &self.shape {
+            write!(f, "#{}", shape)?;
+        }
+        if self.double_sided {
+            write!(f, "#double-sided")?;
+        }
+        if self.grain {
+            write!(f, "#grain")?;
+        }
+        if self.sequential {
+            write!(f, "#sequential")?;
+        }
+        Ok(())
+    }
+}
+
 impl Cut {
+    /// Creates a Cut, validating its count and dimensions
+    pub fn new(
+        length: f32,
+        width: f32,
+        count: CountSpec,
+        name: &str,
+        shape: Option<&str>,
+    ) -> Result<Cut> {
+        if let CountSpec::Fixed(count) = count {
+            if count < 1 {
+                bail!("Count must be at least 1");
+            }
+        }
+        if length <= 0f32 {
+            bail!("Length must be greater than 0");
+        }
+        if width <= 0f32 {
+            bail!("Width must be greater than 0");
+        }
+        Ok(Cut {
+            length,
+            width,
+            count,
+            name: name.to_owned(),
+            shape: shape.map(str::to_owned),
+            double_sided: false,
+            grain: false,
+            sequential: false,
+            thickness: None,
+        })
+    }
+
     /// Parses a cut specification format string in form of: 2@12x4:Apron, which yields
     /// Cut { length: 12, width: 4, count: 2, name: "Apron" }
+    /// A thickness may follow the width, e.g., 2@12x4x0.75:Apron.
+    /// The spec splits at the first '@', 'x' and ':' in turn, so the name is everything after the
+    /// first ':' and may itself contain any of them, e.g., 1@12x4:3:1 Taper.
+    /// A shape may follow the name, e.g., 1@12x6:Bracket#curved, for a part cut from a rectangular blank.
+    /// The count may be `fill`, e.g., fill@24x4:Slat, to make as many as fit in the leftover stock.
+    /// A part marked on both faces ends in #double-sided, e.g., 1@30x12:Door#double-sided, after any shape.
+    /// A part whose grain must run its length ends in #grain, e.g., 2@30x3:Rail#grain, after #double-sided.
+    /// A set of pieces kept side by side ends in #sequential, e.g., 4@30x5:Panel#sequential, after all else.
     pub fn parse(spec: &str) -> Result<Cut> {
         if let Some((count, remainder)) = spec.split_once("@") {
-            let count = count.parse::<i32>()?;
-            if count < 1 {
-                bail!("Count must be at least 1");
+            let count = count.parse::<CountSpec>()?;
+            if let Some((length, remainder)) = remainder.split_once("x") {
+                let length = length.parse::<f32>()?;
+                if let Some((width, name)) = remainder.split_once(":") {
+                    let (width, thickness) = split_thickness(width)?;
+                    let (name, sequential) = match name.strip_suffix("#sequential") {
+                        Some(name) => (name, true),
+                        None => (name, false),
+                    };
+                    let (name, grain) = match name.strip_suffix("#grain") {
+                        Some(name) => (name, true),
+                        None => (name, false),
+                    };
+                    let (name, double_sided) = match name.strip_suffix("#double-sided") {
+                        Some(name) => (name, true),
+                        None => (name, false),
+                    };
+                    let (name, shape) = Self::split_shape(name);
+                    return Ok(Cut {
+                        double_sided,
+                        grain,
+                        sequential,
+                        thickness,
+                        ..Cut::new(length, width, count, name, shape)?
+                    });
+                }
+            }
+        }
+        bail!("Invalid Cut format string")
+    }
+
+    /// Parses a cut specification whose dimensions use a comma as their decimal separator, e.g.,
+    /// 2@12,5x4:Apron. Commas in the name are left alone.
+    pub fn parse_decimal_comma(spec: &str) -> Result<Cut> {
+        Self::parse_in(spec, Decimal::Comma)
+    }
+
+    fn parse_in(spec: &str, decimal: Decimal) -> Result<Cut> {
+        match (decimal, spec.split_once(":")) {
+            (Decimal::Comma, Some((dimensions, name))) => {
+                Self::parse(&format!("{}:{}", dimensions.replace(',', "."), name))
+            }
+            _ => Self::parse(spec),
+        }
+    }
+
+    // splits a trailing shape annotation from a name, e.g., "Bracket#curved"; a '#' followed
+    // by anything other than a word, e.g., "Shelf #1", is part of the name
+    fn split_shape(name: &str) -> (&str, Option<&str>) {
+        match name.rsplit_once('#') {
+            Some((name, shape))
+                if shape.starts_with(char::is_alphabetic)
+                    && shape
+                        .chars()
+                        .all(|c| c.is_alphanumeric() || c == '-' || c == '_') =>
+            {
+                (name, Some(shape))
+            }
+            _ => (name, None),
+        }
+    }
+}
+
+/// The formats an Input document may be written in
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InputFormat {
+    Yaml,
+    Json,
+}
+
+impl InputFormat {
+    /// Determines the format of an input file by its extension, defaulting to yaml
+    pub fn from_path(path: &str) -> InputFormat {
+        match Path::new(path).extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => InputFormat::Json,
+            _ => InputFormat::Yaml,
+        }
+    }
+}
+
+impl FromStr for InputFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "yaml" | "yml" => Ok(InputFormat::Yaml),
+            "json" => Ok(InputFormat::Json),
+            _ => bail!("Unrecognized input format \"{}\", expected yaml or json", s),
+        }
+    }
+}
+
+/// A board in a json document, either as a spec string or a structured object
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonBoard {
+    Spec(String),
+    Structured {
+        length: f32,
+        width: f32,
+        id: String,
+        exact_count: Option<usize>,
+        #[serde(default)]
+        overage: f32,
+        #[serde(default)]
+        sheet: bool,
+        #[serde(default)]
+        dressed: bool,
+        #[serde(default)]
+        defects: Vec<String>,
+        thickness: Option<f32>,
+    },
+}
+
+/// Converts a nominal softwood lumber dimension to its actual, dressed dimension, e.g., a 4 to 3.5.
+/// Returns None if the dimension isn't a standard nominal size.
+pub fn actual_lumber_dimension(nominal: f32) -> Option<f32> {
+    const DRESSING: [(f32, f32); 12] = [
+        (1f32, 0.75),
+        (2f32, 1.5),
+        (3f32, 2.5),
+        (4f32, 3.5),
+        (5f32, 4.5),
+        (6f32, 5.5),
+        (7f32, 6.5),
+        (8f32, 7.25),
+        (10f32, 9.25),
+        (12f32, 11.25),
+        (14f32, 13.25),
+        (16f32, 15.25),
+    ];
+    DRESSING
+        .iter()
+        .find(|(n, _)| f32_eq(*n, nominal))
+        .map(|(_, actual)| *actual)
+}
+
+/// Converts a nominal lumber size, e.g., a 2x4, to its actual (thickness, width), e.g., 1.5x3.5
+pub fn actual_lumber_size(thickness: f32, width: f32) -> Option<(f32, f32)> {
+    Some((
+        actual_lumber_dimension(thickness)?,
+        actual_lumber_dimension(width)?,
+    ))
+}
+
+/// A cut in a json document, either as a spec string or a structured object
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonCut {
+    Spec(String),
+    Structured {
+        length: f32,
+        width: f32,
+        #[serde(default = "JsonCut::default_count")]
+        count: JsonCount,
+        name: String,
+        shape: Option<String>,
+        #[serde(default)]
+        double_sided: bool,
+        #[serde(default)]
+        grain: bool,
+        #[serde(default)]
+        sequential: bool,
+        thickness: Option<f32>,
+    },
+}
+
+impl JsonCut {
+    fn default_count() -> JsonCount {
+        JsonCount::Number(1)
+    }
+}
+
+/// A cut's count in a json document, either a number or a token such as "fill"
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonCount {
+    Number(i32),
+    Token(String),
+}
+
+impl JsonCount {
+    fn to_count_spec(&self) -> Result<CountSpec> {
+        match self {
+            JsonCount::Number(count) => Ok(CountSpec::Fixed(*count)),
+            JsonCount::Token(token) => token.parse(),
+        }
+    }
+}
+
+/// The newest input document version this build understands. Version 2 renamed `spacing` to `kerf`.
+pub const INPUT_VERSION: i64 = 2;
+
+// names an entry of the input document's `key` list, as context for an error parsing it,
+// e.g., "cutlist[3] '2@x4:Bad'"
+fn entry(key: &str, index: usize, spec: &str) -> String {
+    format!("{}[{}] '{}'", key, index, spec)
+}
+
+/// Mirrors the schema of the yaml input document
+#[derive(Deserialize)]
+struct JsonInput {
+    version: Option<i64>,
+    spacing: Option<f32>,
+    kerf: Option<f32>,
+    crosscut_kerf: Option<f32>,
+    rip_kerf: Option<f32>,
+    #[serde(default)]
+    boards: Vec<JsonBoard>,
+    #[serde(default)]
+    cutlist: Vec<JsonCut>,
+    #[serde(default)]
+    same_board_groups: Vec<Vec<String>>,
+    #[serde(default)]
+    pinned: BTreeMap<String, String>,
+    #[serde(default)]
+    densities: BTreeMap<String, f32>,
+    #[serde(default)]
+    isolate: Vec<String>,
+    #[serde(default)]
+    uniform_orientation: Vec<String>,
+    #[serde(default)]
+    hardware: Vec<Hardware>,
+    #[serde(default)]
+    operation_times: OperationTimes,
+    #[serde(default)]
+    seed: u64,
+    #[serde(default)]
+    scrap: Vec<String>,
+    max_handling: Option<String>,
+    jointing: Option<f32>,
+    end_trim: Option<f32>,
+    #[serde(default)]
+    trim_both_ends: bool,
+    planing_allowance: Option<f32>,
+}
+
+/// A piece of hardware the project needs, e.g., hinges or screws; never packed, but carried into
+/// the bill of materials alongside the stock
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Hardware {
+    pub name: String,
+    #[serde(default = "Hardware::default_quantity")]
+    pub quantity: usize,
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+impl Hardware {
+    fn default_quantity() -> usize {
+        1
+    }
+
+    fn validated(self) -> Result<Self> {
+        if self.name.trim().is_empty() {
+            bail!("Hardware must have a name");
+        }
+        if self.quantity < 1 {
+            bail!("Quantity of hardware \"{}\" must be at least 1", self.name);
+        }
+        Ok(self)
+    }
+}
+
+impl fmt::Display for Hardware {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} x {}", self.quantity, self.name)?;
+        if let Some(note) = &self.note {
+            write!(f, " ({})", note)?;
+        }
+        Ok(())
+    }
+}
+
+/// How long each kind of saw operation takes, for estimating cutting time, e.g., in seconds
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct OperationTimes {
+    /// Crosscutting a stack from its board, or trimming a cut to length
+    pub crosscut: f32,
+    /// Ripping a cut from its stack
+    pub rip: f32,
+}
+
+impl Default for OperationTimes {
+    fn default() -> Self {
+        OperationTimes {
+            crosscut: 20f32,
+            rip: 30f32,
+        }
+    }
+}
+
+impl OperationTimes {
+    fn validated(self) -> Result<Self> {
+        if self.crosscut < 0f32 || self.rip < 0f32 {
+            bail!("Operation times must not be negative");
+        }
+        Ok(self)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Input {
+    pub spacing: f32,
+    pub boards: Vec<Board>,
+    pub cutlist: Vec<Cut>,
+    /// Groups of cut names; all pieces of the cuts in a group must come from the same board.
+    pub same_board_groups: Vec<Vec<String>>,
+    /// Maps cut names to the id of the board their pieces must be cut from.
+    pub pinned: BTreeMap<String, String>,
+    /// Maps board ids to the density of their species, in pounds per board-foot, for weight estimates.
+    pub densities: BTreeMap<String, f32>,
+    /// Names of cuts whose pieces each get a stack of their own, so no crosscut is shared with a
+    /// neighbor which might tear out.
+    pub isolate: Vec<String>,
+    /// Names of cuts whose pieces must all be laid out the same way, all rotated or none, so
+    /// they're indistinguishable. Only sheet stock rotates cuts.
+    pub uniform_orientation: Vec<String>,
+    /// Hardware the project needs besides its stock, for the bill of materials
+    pub hardware: Vec<Hardware>,
+    /// How long each saw operation takes, for the time objective.
+    pub operation_times: OperationTimes,
+    /// If set, the (length, width) beyond which a part needs help to handle safely; see
+    /// `oversize_cuts`. Packing ignores it.
+    pub max_handling: Option<(f32, f32)>,
+    /// If set, the kerf of the crosscut blade, taken from each cut's length instead of `spacing`
+    pub crosscut_kerf: Option<f32>,
+    /// If set, the kerf of the rip blade, taken from each cut's width instead of `spacing`
+    pub rip_kerf: Option<f32>,
+}
+
+impl Input {
+    /// Parses an Input from the first document in `input`, written in the specified format
+    pub fn parse(input: &str, format: InputFormat) -> Result<Input> {
+        Self::parse_in(input, format, Decimal::Point)
+    }
+
+    /// Parses an Input like `parse`, but with the numbers of its spec strings using a comma as
+    /// their decimal separator, e.g., 96,5x5,5:A and 2@12,5x4:Apron
+    pub fn parse_decimal_comma(input: &str, format: InputFormat) -> Result<Input> {
+        Self::parse_in(input, format, Decimal::Comma)
+    }
+
+    /// Parses an Input from each document in `input`, e.g., the `---` separated documents of a yaml
+    /// file, so variants of a project can be kept together; a json input holds just one
+    pub fn parse_all(input: &str, format: InputFormat) -> Result<Vec<Input>> {
+        Self::parse_all_in(input, format, Decimal::Point)
+    }
+
+    /// Parses every document like `parse_all`, with decimal commas as in `parse_decimal_comma`
+    pub fn parse_all_decimal_comma(input: &str, format: InputFormat) -> Result<Vec<Input>> {
+        Self::parse_all_in(input, format, Decimal::Comma)
+    }
+
+    /// Returns the text of each document in `input`, as `parse_all` splits them, e.g., to key each
+    /// project by its own document rather than the whole file. Yaml documents are re-emitted, so
+    /// their text is normalized; a json input is returned whole.
+    pub fn document_texts(input: &str, format: InputFormat) -> Result<Vec<String>> {
+        match format {
+            InputFormat::Yaml => YamlLoader::load_from_str(input)?
+                .iter()
+                .map(|doc| {
+                    let mut text = String::new();
+                    YamlEmitter::new(&mut text).dump(doc)?;
+                    Ok(text)
+                })
+                .collect(),
+            InputFormat::Json => Ok(vec![input.to_owned()]),
+        }
+    }
+
+    fn parse_all_in(input: &str, format: InputFormat, decimal: Decimal) -> Result<Vec<Input>> {
+        match format {
+            InputFormat::Yaml => {
+                let docs = YamlLoader::load_from_str(input)?;
+                if docs.is_empty() {
+                    bail!("No input document found")
+                }
+                docs.iter()
+                    .enumerate()
+                    .map(|(i, doc)| {
+                        Self::from_yaml(doc, decimal).with_context(|| format!("document {}", i + 1))
+                    })
+                    .collect()
+            }
+            InputFormat::Json => Ok(vec![Self::from_json_in(input, decimal)?]),
+        }
+    }
+
+    fn parse_in(input: &str, format: InputFormat, decimal: Decimal) -> Result<Input> {
+        match format {
+            InputFormat::Yaml => {
+                let docs = YamlLoader::load_from_str(input)?;
+                if let Some(doc) = docs.first() {
+                    Self::from_yaml(doc, decimal)
+                } else {
+                    bail!("No input document found")
+                }
+            }
+            InputFormat::Json => Self::from_json_in(input, decimal),
+        }
+    }
+
+    pub fn from(doc: &Yaml) -> Result<Input> {
+        Self::from_yaml(doc, Decimal::Point)
+    }
+
+    fn from_yaml(doc: &Yaml, decimal: Decimal) -> Result<Input> {
+        if doc.as_hash().is_none() {
+            bail!("Input document must be a mapping with `boards` and `cutlist` keys")
+        }
+        let version = Self::version(doc["version"].as_i64(), &doc["version"])?;
+        let (cutlist, materials) = Self::cutlist(doc, decimal)?;
+        Input {
+            hardware: Self::hardware(doc)?,
+            operation_times: Self::operation_times(doc)?,
+            max_handling: match &doc["max_handling"] {
+                Yaml::String(size) => Some(Self::handling_size(size)?),
+                Yaml::Null | Yaml::BadValue => None,
+                _ => bail!("`max_handling` must be a size, e.g., 48x24"),
+            },
+            crosscut_kerf: Self::blade_kerf("crosscut_kerf", &doc["crosscut_kerf"])?,
+            rip_kerf: Self::blade_kerf("rip_kerf", &doc["rip_kerf"])?,
+            ..Self::new(
+                Self::spacing(
+                    version,
+                    Self::number(&doc["spacing"]),
+                    Self::number(&doc["kerf"]),
+                )?,
+                Self::boards(doc, decimal)?,
+                cutlist,
+                Self::same_board_groups(doc)?,
+                Self::pinned(doc, materials)?,
+                Self::densities(doc)?,
+                Self::cut_names(doc, "isolate")?,
+            )?
+        }
+        .with_uniform_orientation(Self::cut_names(doc, "uniform_orientation")?)?
+        .with_jointing(Self::number(&doc["jointing"]))?
+        .with_end_trim(
+            Self::number(&doc["end_trim"]),
+            doc["trim_both_ends"].as_bool().unwrap_or(false),
+        )?
+        .with_planing_allowance(Self::number(&doc["planing_allowance"]))
+    }
+
+    pub fn from_json(json: &str) -> Result<Input> {
+        Self::from_json_in(json, Decimal::Point)
+    }
+
+    fn from_json_in(json: &str, decimal: Decimal) -> Result<Input> {
+        let doc: JsonInput = serde_json::from_str(json)?;
+        let version = Self::version(doc.version, &Yaml::Null)?;
+        let spacing = Self::spacing(version, doc.spacing, doc.kerf)?;
+
+        let mut boards = Vec::new();
+        for (i, board) in doc.boards.into_iter().enumerate() {
+            boards.append(&mut match board {
+                JsonBoard::Spec(spec) => Self::board_entry(&spec, decimal, doc.seed, i)
+                    .with_context(|| entry("boards", i, &spec))?,
+                JsonBoard::Structured {
+                    length,
+                    width,
+                    id,
+                    exact_count,
+                    overage,
+                    sheet,
+                    dressed,
+                    defects,
+                    thickness,
+                } => {
+                    let defects = defects
+                        .iter()
+                        .map(|defect| Defect::parse(defect))
+                        .collect::<Result<Vec<_>>>()
+                        .with_context(|| format!("boards[{}] '{}'", i, id))?;
+                    vec![Board {
+                        sheet,
+                        dressed,
+                        thickness,
+                        ..Board::new(length, width, &id, exact_count, overage)?
+                    }
+                    .with_defects(defects)?]
+                }
+            });
+        }
+        for (i, spec) in doc.scrap.iter().enumerate() {
+            boards.push(Self::scrap_entry(spec, decimal).with_context(|| entry("scrap", i, spec))?);
+        }
+
+        let mut cutlist = Vec::new();
+        for (i, cut) in doc.cutlist.into_iter().enumerate() {
+            cutlist.push(match cut {
+                JsonCut::Spec(spec) => {
+                    Cut::parse_in(&spec, decimal).with_context(|| entry("cutlist", i, &spec))?
+                }
+                JsonCut::Structured {
+                    length,
+                    width,
+                    count,
+                    name,
+                    shape,
+                    double_sided,
+                    grain,
+                    sequential,
+                    thickness,
+                } => Cut {
+                    double_sided,
+                    grain,
+                    sequential,
+                    thickness,
+                    ..Cut::new(
+                        length,
+                        width,
+                        count.to_count_spec()?,
+                        &name,
+                        shape.as_deref(),
+                    )?
+                },
+            });
+        }
+
+        Input {
+            hardware: doc
+                .hardware
+                .into_iter()
+                .map(Hardware::validated)
+                .collect::<Result<_>>()?,
+            operation_times: doc.operation_times.validated()?,
+            max_handling: doc
+                .max_handling
+                .as_deref()
+                .map(Self::handling_size)
+                .transpose()?,
+            crosscut_kerf: Self::validated_kerf("crosscut_kerf", doc.crosscut_kerf)?,
+            rip_kerf: Self::validated_kerf("rip_kerf", doc.rip_kerf)?,
+            ..Self::new(
+                spacing,
+                boards,
+                cutlist,
+                doc.same_board_groups,
+                doc.pinned,
+                doc.densities,
+                doc.isolate,
+            )?
+        }
+        .with_uniform_orientation(doc.uniform_orientation)?
+        .with_jointing(doc.jointing)?
+        .with_end_trim(doc.end_trim, doc.trim_both_ends)?
+        .with_planing_allowance(doc.planing_allowance)
+    }
+
+    // requires the named cuts' pieces to share one orientation
+    fn with_uniform_orientation(mut self, names: Vec<String>) -> Result<Input> {
+        for name in &names {
+            if !self.cutlist.iter().any(|cut| &cut.name == name) {
+                bail!("Uniform orientation references unknown cut \"{}\"", name);
             }
+        }
+        self.uniform_orientation = names;
+        Ok(self)
+    }
+
+    // takes the jointing allowance, if any, from each board which isn't dressed or sheet stock
+    fn with_jointing(mut self, jointing: Option<f32>) -> Result<Input> {
+        let jointing = jointing.unwrap_or_default();
+        if jointing < 0f32 {
+            bail!("`jointing` must not be negative");
+        }
+        for board in &mut self.boards {
+            if !board.dressed && !board.sheet {
+                board.jointing = jointing;
+                if board.usable_length() <= 0f32 || board.usable_width() <= 0f32 {
+                    bail!("Board \"{}\" has nothing left after jointing", board.id);
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    // takes the end trim, if any, off the start of each board which isn't sheet stock, and off its
+    // far end too if `both_ends`
+    fn with_end_trim(mut self, end_trim: Option<f32>, both_ends: bool) -> Result<Input> {
+        let end_trim = end_trim.unwrap_or_default();
+        if end_trim < 0f32 {
+            bail!("`end_trim` must not be negative");
+        }
+        for board in &mut self.boards {
+            if !board.sheet {
+                board.end_trims = (end_trim, if both_ends { end_trim } else { 0f32 });
+                if board.usable_length() <= 0f32 {
+                    bail!(
+                        "Board \"{}\" has nothing left after trimming its ends",
+                        board.id
+                    );
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    // limits how far each board may be planed down to a cut's thickness, if set
+    fn with_planing_allowance(mut self, planing_allowance: Option<f32>) -> Result<Input> {
+        if planing_allowance.is_some_and(|allowance| allowance < 0f32) {
+            bail!("`planing_allowance` must not be negative");
+        }
+        for board in &mut self.boards {
+            board.planing_allowance = planing_allowance;
+        }
+        Ok(self)
+    }
+
+    /// Creates an Input, validating the relationships between its parts. Shared by all input formats.
+    fn new(
+        spacing: f32,
+        boards: Vec<Board>,
+        cutlist: Vec<Cut>,
+        same_board_groups: Vec<Vec<String>>,
+        pinned: BTreeMap<String, String>,
+        densities: BTreeMap<String, f32>,
+        isolate: Vec<String>,
+    ) -> Result<Input> {
+        if boards.is_empty() {
+            bail!("No boards specified; list board stock under `boards`, e.g., 96x6:Oak")
+        }
+        if cutlist.is_empty() {
+            bail!("No cuts specified; list the parts to cut under `cutlist`, e.g., 2@12x4:Apron")
+        }
+        if cutlist.iter().all(|cut| cut.count.fixed() < 1) {
+            bail!("The cutlist has no fixed pieces, so there's no stock for fill cuts to use")
+        }
+        for name in same_board_groups.iter().flatten() {
+            if !cutlist.iter().any(|cut| &cut.name == name) {
+                bail!("Same board group references unknown cut \"{}\"", name);
+            }
+        }
+        for (name, board_id) in &pinned {
+            if !cutlist.iter().any(|cut| &cut.name == name) {
+                bail!("Pin references unknown cut \"{}\"", name);
+            }
+            if !boards.iter().any(|board| board.has_id(board_id)) {
+                bail!(
+                    "Cut \"{}\" is pinned to unknown board \"{}\"",
+                    name,
+                    board_id
+                );
+            }
+            if same_board_groups.iter().flatten().any(|n| n == name) {
+                bail!(
+                    "Cut \"{}\" cannot be both pinned and in a same board group",
+                    name
+                );
+            }
+        }
+
+        for (board_id, density) in &densities {
+            if !boards.iter().any(|board| &board.id == board_id) {
+                bail!("Density specified for unknown board \"{}\"", board_id);
+            }
+            if *density <= 0f32 {
+                bail!("Density of board \"{}\" must be greater than 0", board_id);
+            }
+        }
+
+        for name in &isolate {
+            if !cutlist.iter().any(|cut| &cut.name == name) {
+                bail!("Isolate references unknown cut \"{}\"", name);
+            }
+        }
+
+        for cut in cutlist.iter().filter(|cut| cut.sequential) {
+            if cut.count == CountSpec::Fill {
+                bail!("Cut \"{}\" cannot be both fill and sequential", cut.name);
+            }
+            if isolate.contains(&cut.name) {
+                bail!(
+                    "Cut \"{}\" cannot be both isolated and sequential",
+                    cut.name
+                );
+            }
+            // a sequential set is already kept to one board
+            if same_board_groups.iter().flatten().any(|n| *n == cut.name) {
+                bail!(
+                    "Cut \"{}\" cannot be both sequential and in a same board group",
+                    cut.name
+                );
+            }
+        }
+
+        Ok(Input {
+            spacing,
+            boards,
+            cutlist,
+            same_board_groups,
+            pinned,
+            densities,
+            isolate,
+            uniform_orientation: Vec::new(),
+            hardware: Vec::new(),
+            operation_times: Default::default(),
+            max_handling: None,
+            crosscut_kerf: None,
+            rip_kerf: None,
+        })
+    }
+
+    /// Returns the boards which look to have their length and width transposed; see
+    /// `Board::looks_transposed`
+    pub fn transposed_boards(&self) -> Vec<&Board> {
+        self.boards
+            .iter()
+            .filter(|board| board.looks_transposed())
+            .collect()
+    }
+
+    /// Returns the names of cuts larger than `max_handling` in either dimension, whichever way
+    /// they're turned, so the parts needing help to handle can be planned for. Empty if no
+    /// `max_handling` is set.
+    pub fn oversize_cuts(&self) -> Vec<&str> {
+        let (max_length, max_width) = match self.max_handling {
+            Some((length, width)) => (length.max(width), length.min(width)),
+            None => return Vec::new(),
+        };
+        self.cutlist
+            .iter()
+            .filter(|cut| {
+                cut.length.max(cut.width) > max_length || cut.length.min(cut.width) > max_width
+            })
+            .map(|cut| cut.name.as_str())
+            .collect()
+    }
+
+    // parses a `max_handling` size in form of: 48x24
+    fn handling_size(size: &str) -> Result<(f32, f32)> {
+        let parse = || -> Option<(f32, f32)> {
+            let (length, width) = size.split_once("x")?;
+            Some((length.trim().parse().ok()?, width.trim().parse().ok()?))
+        };
+        match parse() {
+            Some((length, width)) if length > 0f32 && width > 0f32 => Ok((length, width)),
+            _ => bail!(
+                "Invalid `max_handling` size \"{}\", expected e.g., 48x24",
+                size
+            ),
+        }
+    }
+
+    /// Treats board widths as nominal lumber sizes, converting each standard size to its actual
+    /// dressed width while remembering the nominal width for labels. Other widths are left alone.
+    pub fn convert_nominal_sizes(&mut self) {
+        for board in &mut self.boards {
+            if board.nominal_width.is_none() {
+                if let Some(actual) = actual_lumber_dimension(board.width) {
+                    board.nominal_width = Some(board.width);
+                    board.width = actual;
+                }
+            }
+        }
+    }
+
+    // validates the document's version, where `version` is the parsed value of `doc_version`, if any;
+    // documents which don't declare a version are version 1
+    fn version(version: Option<i64>, doc_version: &Yaml) -> Result<i64> {
+        let version = match (version, doc_version) {
+            (Some(version), _) => version,
+            (None, Yaml::Null) | (None, Yaml::BadValue) => 1,
+            (None, _) => bail!("`version` must be an integer"),
+        };
+        if version < 1 {
+            bail!("Input version must be at least 1");
+        }
+        if version > INPUT_VERSION {
+            bail!(
+                "Input version {} is newer than the newest supported version, {}",
+                version,
+                INPUT_VERSION
+            );
+        }
+        Ok(version)
+    }
+
+    // reads the gap left between cuts, which version 1 documents call `spacing` and version 2 `kerf`
+    fn spacing(version: i64, spacing: Option<f32>, kerf: Option<f32>) -> Result<f32> {
+        match version {
+            1 => {
+                if kerf.is_some() {
+                    bail!("`kerf` requires version 2; version 1 documents use `spacing`");
+                }
+                Ok(spacing.unwrap_or(0f32))
+            }
+            _ => {
+                if spacing.is_some() {
+                    bail!("`spacing` was renamed `kerf` in version 2");
+                }
+                Ok(kerf.unwrap_or(0f32))
+            }
+        }
+    }
+
+    // reads the kerf of one blade, `crosscut_kerf` or `rip_kerf`, if given
+    fn blade_kerf(key: &str, doc: &Yaml) -> Result<Option<f32>> {
+        match doc {
+            Yaml::Null | Yaml::BadValue => Ok(None),
+            _ => match Self::number(doc) {
+                Some(kerf) => Self::validated_kerf(key, Some(kerf)),
+                None => bail!("`{}` must be a number", key),
+            },
+        }
+    }
+
+    fn validated_kerf(key: &str, kerf: Option<f32>) -> Result<Option<f32>> {
+        match kerf {
+            Some(kerf) if kerf < 0f32 => bail!("`{}` must not be negative", key),
+            _ => Ok(kerf),
+        }
+    }
+
+    /// The kerf taken from each cut's length by the crosscut blade: `crosscut_kerf` if set,
+    /// otherwise `spacing`
+    pub fn crosscut_kerf(&self) -> f32 {
+        self.crosscut_kerf.unwrap_or(self.spacing)
+    }
+
+    /// The kerf taken from each cut's width by the rip blade: `rip_kerf` if set, otherwise `spacing`
+    pub fn rip_kerf(&self) -> f32 {
+        self.rip_kerf.unwrap_or(self.spacing)
+    }
+
+    fn number(doc: &Yaml) -> Option<f32> {
+        doc.as_f64()
+            .or_else(|| doc.as_i64().map(|number| number as f64))
+            .map(|number| number as f32)
+    }
+
+    fn boards(doc: &Yaml, decimal: Decimal) -> Result<Vec<Board>> {
+        let seed = match &doc["seed"] {
+            Yaml::Integer(seed) if *seed >= 0 => *seed as u64,
+            Yaml::Null | Yaml::BadValue => 0,
+            _ => bail!("`seed` must be a non-negative integer"),
+        };
+        let mut boards = Vec::new();
+        for (i, doc_board) in Self::entries(doc, "boards")?.into_iter().enumerate() {
+            boards.append(
+                &mut Self::board_entry(doc_board, decimal, seed, i)
+                    .with_context(|| entry("boards", i, doc_board))?,
+            );
+        }
+        for (i, doc_scrap) in Self::entries(doc, "scrap")?.into_iter().enumerate() {
+            boards.push(
+                Self::scrap_entry(doc_scrap, decimal)
+                    .with_context(|| entry("scrap", i, doc_scrap))?,
+            );
+        }
+
+        Ok(boards)
+    }
+
+    // parses the spec of a `scrap` entry, a leftover piece, e.g., from `solver::offcut_inventory`,
+    // which as a single piece can be used at most once
+    fn scrap_entry(spec: &str, decimal: Decimal) -> Result<Board> {
+        Ok(Board {
+            max_count: Some(1),
+            ..Board::parse_in(spec, decimal)?
+        })
+    }
 
-            if let Some((length, remainder)) = remainder.split_once("x") {
-                let length = length.parse::<f32>()?;
-                if length <= 0f32 {
-                    bail!("Length must be greater than 0");
+    // parses the spec of the `index`th board entry, expanding a lot into its boards; each lot's
+    // lengths derive from the document's seed and its index, so lots don't share a pattern
+    fn board_entry(spec: &str, decimal: Decimal, seed: u64, index: usize) -> Result<Vec<Board>> {
+        // a lot's count is written before an @, as is a defect's offset
+        if Board::split_defects(spec).0.contains('@') {
+            let spec = match (decimal, spec.split_once(":")) {
+                (Decimal::Comma, Some((dimensions, id))) => {
+                    format!("{}:{}", dimensions.replace(',', "."), id)
                 }
+                _ => spec.to_owned(),
+            };
+            BoardLot::parse(&spec)?.expand(seed.wrapping_add(index as u64))
+        } else {
+            Ok(vec![Board::parse_in(spec, decimal)?])
+        }
+    }
 
-                if let Some((width, remainder)) = remainder.split_once(":") {
-                    let width = width.parse::<f32>()?;
-                    if width <= 0f32 {
-                        bail!("Width must be greater than 0");
-                    }
+    // the cutlist is either a list of cut specs, or a mapping of named groups, each listing its
+    // `parts` and optionally the `material` (a board id) they're cut from, e.g.,
+    //   table: { material: Oak, parts: [4@28x2:Leg, 2@40x4:Apron] }
+    // the groups flatten, in order, into the one cutlist; each group's material pins its parts,
+    // which are returned alongside
+    fn cutlist(doc: &Yaml, decimal: Decimal) -> Result<(Vec<Cut>, BTreeMap<String, String>)> {
+        let mut cutlist = Vec::new();
+        let mut materials = BTreeMap::new();
+        let doc_groups = match &doc["cutlist"] {
+            Yaml::Hash(doc_groups) => doc_groups,
+            _ => {
+                for (i, doc_cut) in Self::entries(doc, "cutlist")?.into_iter().enumerate() {
+                    cutlist.push(
+                        Cut::parse_in(doc_cut, decimal)
+                            .with_context(|| entry("cutlist", i, doc_cut))?,
+                    );
+                }
+                return Ok((cutlist, materials));
+            }
+        };
 
-                    let name = remainder.to_owned();
-                    return Ok(Cut {
-                        length,
-                        width,
-                        count,
-                        name,
-                    });
+        for (doc_name, doc_group) in doc_groups {
+            let name = match doc_name.as_str() {
+                Some(name) => name,
+                None => bail!("Each group in `cutlist` must be named"),
+            };
+            let key = format!("cutlist.{}.parts", name);
+            let material = match &doc_group["material"] {
+                Yaml::String(material) => Some(material.as_str()),
+                Yaml::Null | Yaml::BadValue => None,
+                _ => bail!("`cutlist.{}.material` must be a board id", name),
+            };
+            for (i, doc_cut) in Self::entries(doc_group, "parts")
+                .with_context(|| format!("In `cutlist.{}`", name))?
+                .into_iter()
+                .enumerate()
+            {
+                let cut =
+                    Cut::parse_in(doc_cut, decimal).with_context(|| entry(&key, i, doc_cut))?;
+                if let Some(material) = material {
+                    match materials.insert(cut.name.clone(), material.to_owned()) {
+                        Some(other) if other != material => bail!(
+                            "Cut \"{}\" is listed in groups of both \"{}\" and \"{}\"",
+                            cut.name,
+                            other,
+                            material
+                        ),
+                        _ => {}
+                    }
                 }
+                cutlist.push(cut);
             }
         }
-        bail!("Invalid Cut format string")
+
+        Ok((cutlist, materials))
     }
-}
 
-#[derive(Debug, Clone)]
-pub struct Input {
-    pub spacing: f32,
-    pub boards: Vec<Board>,
-    pub cutlist: Vec<Cut>,
-}
+    // returns the spec strings listed under `key`; a missing or empty key yields no entries
+    fn entries<'a>(doc: &'a Yaml, key: &str) -> Result<Vec<&'a str>> {
+        match &doc[key] {
+            Yaml::Array(doc_entries) => doc_entries
+                .iter()
+                .map(|doc_entry| match doc_entry.as_str() {
+                    Some(entry) => Ok(entry),
+                    None => bail!("Each entry in `{}` must be a spec string", key),
+                })
+                .collect(),
+            Yaml::Null | Yaml::BadValue => Ok(Vec::new()),
+            _ => bail!("`{}` must be a list of spec strings", key),
+        }
+    }
 
-impl Input {
-    pub fn from(doc: &Yaml) -> Result<Input> {
-        Ok(Self {
-            spacing: Self::spacing(doc)?,
-            boards: Self::boards(doc)?,
-            cutlist: Self::cutlist(doc)?,
-        })
+    // reads the list of cut names under `key`, e.g., `isolate`
+    fn cut_names(doc: &Yaml, key: &str) -> Result<Vec<String>> {
+        match &doc[key] {
+            Yaml::Array(doc_names) => doc_names
+                .iter()
+                .map(|doc_name| match doc_name.as_str() {
+                    Some(name) => Ok(name.to_owned()),
+                    None => bail!("Each entry in `{}` must be a cut name", key),
+                })
+                .collect(),
+            Yaml::Null | Yaml::BadValue => Ok(Vec::new()),
+            _ => bail!("`{}` must be a list of cut names", key),
+        }
     }
 
-    fn spacing(doc: &Yaml) -> Result<f32> {
-        if let Some(spacing) = doc["spacing"].as_f64() {
-            Ok(spacing as f32)
-        } else {
-            Ok(0f32)
+    fn hardware(doc: &Yaml) -> Result<Vec<Hardware>> {
+        match &doc["hardware"] {
+            Yaml::Array(doc_items) => doc_items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| {
+                    let name = match item["name"].as_str() {
+                        Some(name) => name.to_owned(),
+                        None => bail!("hardware[{}] must be a mapping with a `name`", i),
+                    };
+                    let quantity = match &item["quantity"] {
+                        Yaml::Integer(quantity) if *quantity >= 0 => *quantity as usize,
+                        Yaml::Null | Yaml::BadValue => Hardware::default_quantity(),
+                        _ => bail!("The quantity of hardware \"{}\" must be a count", name),
+                    };
+                    let note = match &item["note"] {
+                        Yaml::String(note) => Some(note.clone()),
+                        Yaml::Null | Yaml::BadValue => None,
+                        _ => bail!("The note of hardware \"{}\" must be text", name),
+                    };
+                    Hardware {
+                        name,
+                        quantity,
+                        note,
+                    }
+                    .validated()
+                })
+                .collect(),
+            Yaml::Null | Yaml::BadValue => Ok(Vec::new()),
+            _ => bail!("`hardware` must be a list of items with a name, quantity and note"),
         }
     }
 
-    fn boards(doc: &Yaml) -> Result<Vec<Board>> {
-        let mut boards = Vec::new();
-        if let Yaml::Array(ref doc_boards) = doc["boards"] {
-            for doc_board in doc_boards {
-                if let Some(doc_board) = doc_board.as_str() {
-                    boards.push(Board::parse(doc_board)?);
+    fn operation_times(doc: &Yaml) -> Result<OperationTimes> {
+        let defaults = OperationTimes::default();
+        match &doc["operation_times"] {
+            Yaml::Hash(_) => {
+                let time = |key: &str, default: f32| match &doc["operation_times"][key] {
+                    Yaml::Null | Yaml::BadValue => Ok(default),
+                    value => Self::number(value)
+                        .with_context(|| format!("`operation_times.{}` must be a number", key)),
+                };
+                OperationTimes {
+                    crosscut: time("crosscut", defaults.crosscut)?,
+                    rip: time("rip", defaults.rip)?,
                 }
+                .validated()
             }
+            Yaml::Null | Yaml::BadValue => Ok(defaults),
+            _ => bail!("`operation_times` must be a mapping with `crosscut` and `rip` times"),
         }
+    }
 
-        if !boards.is_empty() {
-            Ok(boards)
-        } else {
-            bail!("No boards specified")
+    fn same_board_groups(doc: &Yaml) -> Result<Vec<Vec<String>>> {
+        let mut groups = Vec::new();
+
+        if let Yaml::Array(ref doc_groups) = doc["same_board_groups"] {
+            for doc_group in doc_groups {
+                let mut group = Vec::new();
+                if let Yaml::Array(ref doc_names) = doc_group {
+                    for doc_name in doc_names {
+                        if let Some(name) = doc_name.as_str() {
+                            group.push(name.to_owned());
+                        }
+                    }
+                }
+                if !group.is_empty() {
+                    groups.push(group);
+                }
+            }
         }
+
+        Ok(groups)
     }
 
-    fn cutlist(doc: &Yaml) -> Result<Vec<Cut>> {
-        let mut cutlist = Vec::new();
+    // `materials` are the pins implied by the cutlist's groups, which explicit pins must agree with
+    fn pinned(doc: &Yaml, materials: BTreeMap<String, String>) -> Result<BTreeMap<String, String>> {
+        let mut pinned = materials;
 
-        if let Yaml::Array(ref doc_cutlist) = doc["cutlist"] {
-            for doc_cut in doc_cutlist {
-                if let Some(doc_cut) = doc_cut.as_str() {
-                    cutlist.push(Cut::parse(doc_cut)?);
+        if let Yaml::Hash(ref doc_pins) = doc["pinned"] {
+            for (doc_name, doc_board_id) in doc_pins {
+                if let (Some(name), Some(board_id)) = (doc_name.as_str(), doc_board_id.as_str()) {
+                    match pinned.insert(name.to_owned(), board_id.to_owned()) {
+                        Some(material) if material != board_id => bail!(
+                            "Cut \"{}\" is pinned to \"{}\" but its group's material is \"{}\"",
+                            name,
+                            board_id,
+                            material
+                        ),
+                        _ => {}
+                    }
                 }
             }
         }
 
-        if !cutlist.is_empty() {
-            Ok(cutlist)
-        } else {
-            bail!("No cuts specified")
+        Ok(pinned)
+    }
+
+    fn densities(doc: &Yaml) -> Result<BTreeMap<String, f32>> {
+        let mut densities = BTreeMap::new();
+
+        if let Yaml::Hash(ref doc_densities) = doc["densities"] {
+            for (doc_board_id, doc_density) in doc_densities {
+                let density = doc_density
+                    .as_f64()
+                    .or_else(|| doc_density.as_i64().map(|density| density as f64));
+                if let (Some(board_id), Some(density)) = (doc_board_id.as_str(), density) {
+                    densities.insert(board_id.to_owned(), density as f32);
+                }
+            }
         }
+
+        Ok(densities)
     }
 }
 
@@ -172,6 +1669,18 @@ mod spec_tests {
                 length: 96.5,
                 width: 5.5,
                 id: "A".into(),
+                exact_count: None,
+                overage: 0f32,
+                nominal_width: None,
+                sheet: false,
+                max_count: None,
+                dressed: false,
+                jointing: 0f32,
+                end_trims: (0f32, 0f32),
+                defects: Vec::new(),
+                thickness: None,
+                planing_allowance: None,
+                lot: None,
             }
         );
         assert_eq!(
@@ -179,9 +1688,501 @@ mod spec_tests {
             Board {
                 length: 96f32,
                 width: 5f32,
-                id: "Foo".into()
+                id: "Foo".into(),
+                exact_count: None,
+                overage: 0f32,
+                nominal_width: None,
+                sheet: false,
+                max_count: None,
+                dressed: false,
+                jointing: 0f32,
+                end_trims: (0f32, 0f32),
+                defects: Vec::new(),
+                thickness: None,
+                planing_allowance: None,
+                lot: None,
+            }
+        );
+    }
+
+    #[test]
+    fn board_parse_accepts_sheet_marker() {
+        let board = Board::parse("96x48:Ply#sheet+1=2").expect("Expected format to parse");
+        assert_eq!(board.id, "Ply");
+        assert!(board.sheet);
+        assert_eq!(board.overage, 1f32);
+        assert_eq!(board.exact_count, Some(2));
+        assert!(!Board::parse("96x8:A").unwrap().sheet);
+
+        let input = Input::from_json(
+            r#"{ "boards": [{ "length": 96, "width": 48, "id": "Ply", "sheet": true }], "cutlist": ["1@12x4:Apron"] }"#,
+        )
+        .unwrap();
+        assert!(input.boards[0].sheet);
+    }
+
+    #[test]
+    fn board_parse_accepts_defects() {
+        let board = Board::parse("96x8:Oak!+1=2#defect(3x1@10x2)#defect(2x8@95x0)")
+            .expect("Expected format to parse");
+        assert_eq!(board.id, "Oak");
+        assert!(board.dressed);
+        assert_eq!(board.exact_count, Some(2));
+        assert_eq!(
+            board.defects,
+            vec![
+                Defect {
+                    x: 10f32,
+                    y: 2f32,
+                    length: 3f32,
+                    width: 1f32
+                },
+                Defect {
+                    x: 95f32,
+                    y: 0f32,
+                    length: 2f32,
+                    width: 8f32
+                },
+            ]
+        );
+        assert!(Board::parse("96x8:Oak").unwrap().defects.is_empty());
+        let comma = Board::parse_decimal_comma("96x8:Oak#defect(1,5x1@10,25x0)").unwrap();
+        assert_eq!(comma.defects[0].length, 1.5);
+        assert_eq!(comma.defects[0].x, 10.25);
+
+        // defects must have an area, and lie within the board, overage included
+        assert!(Board::parse("96x8:Oak#defect(0x1@10x2)").is_err());
+        assert!(Board::parse("96x8:Oak#defect(3x1@-1x2)").is_err());
+        assert!(Board::parse("96x8:Oak#defect(3x1@95x2)").is_err());
+        assert!(Board::parse("96x8:Oak#defect(3x1@10x7.5)").is_err());
+        assert!(Board::parse("96x8:Oak#defect(3x1)").is_err());
+
+        let input = Input::from_json(
+            r#"{ "boards": [{ "length": 96, "width": 8, "id": "Oak", "defects": ["3x1@10x2"] }], "cutlist": ["1@12x4:Apron"] }"#,
+        )
+        .unwrap();
+        assert_eq!(input.boards[0].defects.len(), 1);
+        let input = Input::parse(
+            "boards: ['96x8:Oak#defect(3x1@10x2)']\ncutlist: [1@12x4:Apron]",
+            InputFormat::Yaml,
+        )
+        .unwrap();
+        assert_eq!(input.boards[0].defects.len(), 1);
+    }
+
+    #[test]
+    fn dressed_boards_skip_jointing() {
+        let board = Board::parse("96x8:A!+1=2").expect("Expected format to parse");
+        assert_eq!(board.id, "A");
+        assert!(board.dressed);
+        assert_eq!(board.to_string(), "96x8:A!+1=2");
+        assert!(!Board::parse("96x8:A").unwrap().dressed);
+
+        let input = Input::parse(
+            "jointing: 0.5\nboards: [96x8:Rough, 96x8:Dressed!, 96x48:Ply#sheet]\ncutlist: [1@12x4:Apron]",
+            InputFormat::Yaml,
+        )
+        .unwrap();
+        let (rough, dressed) = (&input.boards[0], &input.boards[1]);
+        assert_eq!((rough.usable_length(), rough.usable_width()), (95.5, 7.5));
+        assert_eq!(
+            (dressed.usable_length(), dressed.usable_width()),
+            (96f32, 8f32)
+        );
+        assert_eq!(input.boards[2].usable_width(), 48f32);
+
+        let json = Input::parse(
+            r#"{"jointing": 0.5, "boards": ["96x8:Rough", {"length": 96, "width": 8, "id": "Dressed", "dressed": true}], "cutlist": ["1@12x4:Apron"]}"#,
+            InputFormat::Json,
+        )
+        .unwrap();
+        assert_eq!(json.boards[0].usable_width(), 7.5);
+        assert_eq!(json.boards[1].usable_width(), 8f32);
+
+        assert!(Input::parse(
+            "jointing: -1\nboards: [96x8:A]\ncutlist: [1@12x4:Apron]",
+            InputFormat::Yaml
+        )
+        .is_err());
+        assert!(Input::parse(
+            "jointing: 8\nboards: [96x8:A]\ncutlist: [1@12x4:Apron]",
+            InputFormat::Yaml
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn input_parses_end_trim() {
+        let yaml =
+            "end_trim: 1.5\nboards: [96x8:A, 96x8:B!+2, 96x48:Ply#sheet]\ncutlist: [1@12x4:Apron]";
+        let input = Input::parse(yaml, InputFormat::Yaml).unwrap();
+        assert_eq!(input.boards[0].end_trims, (1.5, 0f32));
+        assert_eq!(input.boards[0].usable_length(), 94.5);
+        assert_eq!(input.boards[1].usable_length(), 96.5);
+        assert_eq!(input.boards[2].usable_length(), 96f32);
+
+        let both = format!("trim_both_ends: true\n{}", yaml);
+        let input = Input::parse(&both, InputFormat::Yaml).unwrap();
+        assert_eq!(input.boards[0].end_trims, (1.5, 1.5));
+        assert_eq!(input.boards[0].usable_length(), 93f32);
+        // trimmed along with the jointing allowance
+        let jointed = format!("jointing: 0.5\n{}", both);
+        let input = Input::parse(&jointed, InputFormat::Yaml).unwrap();
+        assert_eq!(input.boards[0].usable_length(), 92.5);
+
+        let json = Input::parse(
+            r#"{"end_trim": 1, "trim_both_ends": true, "boards": ["96x8:A"], "cutlist": ["1@12x4:Apron"]}"#,
+            InputFormat::Json,
+        )
+        .unwrap();
+        assert_eq!(json.boards[0].usable_length(), 94f32);
+
+        assert!(Input::parse(&yaml.replace("1.5", "-1"), InputFormat::Yaml).is_err());
+        assert!(Input::parse(&both.replace("1.5", "48"), InputFormat::Yaml).is_err());
+    }
+
+    #[test]
+    fn thicker_boards_accept_thinner_cuts() {
+        let cut = Cut::parse("2@12x4x0.75:Apron").unwrap();
+        assert_eq!(cut.thickness, Some(0.75));
+        let board = Board::parse("96x8x1:Oak").unwrap();
+        assert_eq!(board.thickness, Some(1f32));
+        assert!(board.accepts_thickness(cut.thickness));
+        assert!(board.accepts_thickness(Some(1f32)));
+        // a thinner board can't be planed up to the cut
+        assert!(!Board::parse("96x8x0.5:Oak")
+            .unwrap()
+            .accepts_thickness(cut.thickness));
+        // thickness is only checked where both give one
+        assert!(Board::parse("96x8:Oak")
+            .unwrap()
+            .accepts_thickness(cut.thickness));
+        assert!(board.accepts_thickness(None));
+        assert!(Cut::parse("2@12x4x0:Apron").is_err());
+        assert!(Board::parse("96x8x-1:Oak").is_err());
+
+        // within the planing allowance, if one is set
+        let input = Input::parse(
+            "planing_allowance: 0.125\nboards: [96x8x1:Oak, 96x8x0.8:Ash]\ncutlist: [1@12x4x0.75:Apron]",
+            InputFormat::Yaml,
+        )
+        .unwrap();
+        assert!(!input.boards[0].accepts_thickness(Some(0.75)));
+        assert!(input.boards[1].accepts_thickness(Some(0.75)));
+        assert!(input.boards[0].accepts_thickness(Some(0.875)));
+        assert!(Input::parse(
+            "planing_allowance: -1\nboards: [96x8:A]\ncutlist: [1@12x4:Apron]",
+            InputFormat::Yaml,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn input_parses_every_yaml_document() {
+        let yaml = "
+boards: [96x8:A]
+cutlist: [2@24x4:Shelf]
+---
+boards: [96x6:B]
+cutlist: [4@30x3:Rail, 1@12x2:Cleat]
+";
+        let inputs = Input::parse_all(yaml, InputFormat::Yaml).unwrap();
+        assert_eq!(inputs.len(), 2);
+        assert_eq!(inputs[0], Input::parse(yaml, InputFormat::Yaml).unwrap());
+        assert_eq!(inputs[1].boards[0].id, "B");
+
+        // each document is a project of its own
+        let solutions: Vec<Vec<Vec<crate::solver::Board>>> = inputs
+            .iter()
+            .map(|input| crate::solver::compute(input, &Default::default()).unwrap())
+            .collect();
+        assert_eq!(solutions.len(), 2);
+        assert!(solutions[0][0].iter().all(|board| board.id == "A"));
+        assert!(solutions[1][0].iter().all(|board| board.id == "B"));
+        let placed: usize = solutions[1][0].iter().map(|board| board.cuts().len()).sum();
+        assert_eq!(placed, 5);
+
+        // errors name the document they're in
+        let error = Input::parse_all(&format!("{}---\nboards: [96x8:A]", yaml), InputFormat::Yaml)
+            .unwrap_err();
+        assert!(format!("{:#}", error).contains("document 3"));
+        assert!(Input::parse_all("", InputFormat::Yaml).is_err());
+
+        let json = r#"{"boards": ["96x8:A"], "cutlist": ["1@12x4:Apron"]}"#;
+        assert_eq!(Input::parse_all(json, InputFormat::Json).unwrap().len(), 1);
+
+        // each document's text parses as that document alone
+        let texts = Input::document_texts(yaml, InputFormat::Yaml).unwrap();
+        assert_eq!(texts.len(), 2);
+        for (text, input) in texts.iter().zip(&inputs) {
+            assert_eq!(&Input::parse(text, InputFormat::Yaml).unwrap(), input);
+        }
+        assert_ne!(texts[0], texts[1]);
+        assert_eq!(
+            Input::document_texts(json, InputFormat::Json).unwrap(),
+            [json]
+        );
+    }
+
+    #[test]
+    fn input_parses_hardware() {
+        let yaml = "
+boards: [96x8:A]
+cutlist: [2@24x4:Shelf]
+hardware:
+  - { name: Hinge, quantity: 4, note: 'brass, 2 inch' }
+  - name: Knob
+";
+        let input = Input::parse(yaml, InputFormat::Yaml).unwrap();
+        assert_eq!(
+            input.hardware,
+            vec![
+                Hardware {
+                    name: "Hinge".to_owned(),
+                    quantity: 4,
+                    note: Some("brass, 2 inch".to_owned()),
+                },
+                Hardware {
+                    name: "Knob".to_owned(),
+                    quantity: 1,
+                    note: None,
+                },
+            ]
+        );
+        assert_eq!(input.hardware[0].to_string(), "4 x Hinge (brass, 2 inch)");
+        assert!(Input::parse(
+            "boards: [96x8:A]\ncutlist: [1@12x4:Apron]",
+            InputFormat::Yaml
+        )
+        .unwrap()
+        .hardware
+        .is_empty());
+
+        let json = Input::parse(
+            r#"{"boards": ["96x8:A"], "cutlist": ["1@12x4:Apron"],
+                "hardware": [{"name": "Hinge", "quantity": 4, "note": "brass, 2 inch"}, {"name": "Knob"}]}"#,
+            InputFormat::Json,
+        )
+        .unwrap();
+        assert_eq!(json.hardware, input.hardware);
+
+        let error = |hardware: &str| {
+            Input::parse(
+                &format!(
+                    "boards: [96x8:A]\ncutlist: [1@12x4:Apron]\nhardware: {}",
+                    hardware
+                ),
+                InputFormat::Yaml,
+            )
+            .is_err()
+        };
+        assert!(error("[{ quantity: 2 }]"));
+        assert!(error("[{ name: Hinge, quantity: 0 }]"));
+        assert!(error("[{ name: Hinge, quantity: two }]"));
+        assert!(error("Hinge"));
+    }
+
+    #[test]
+    fn board_parse_keeps_plus_signs_in_ids() {
+        let board = Board::parse("96x6:Oak+Walnut").unwrap();
+        assert_eq!(board.id, "Oak+Walnut");
+        assert_eq!(board.overage, 0f32);
+
+        let board = Board::parse("96x6:Oak+Walnut+1").unwrap();
+        assert_eq!(board.id, "Oak+Walnut");
+        assert_eq!(board.overage, 1f32);
+        assert_eq!(board.to_string(), "96x6:Oak+Walnut+1");
+
+        let board = Board::parse_decimal_comma("96x6:Oak+Walnut+0,5").unwrap();
+        assert_eq!(board.id, "Oak+Walnut");
+        assert_eq!(board.overage, 0.5);
+        let board = Board::parse_decimal_comma("96x6:Oak+Walnut").unwrap();
+        assert_eq!(board.id, "Oak+Walnut");
+    }
+
+    #[test]
+    fn specs_round_trip_through_display() {
+        for &spec in &[
+            "96x6.5:A",
+            "96x48:Ply#sheet+1=2",
+            "120x8:Oak=3",
+            "96x8:Oak=2#defect(3x1@10x2)#defect(2x2@40x0)",
+            "96x8x1:Oak",
+        ] {
+            let board = Board::parse(spec).unwrap();
+            assert_eq!(board.to_string(), spec);
+            assert_eq!(Board::parse(&board.to_string()).unwrap(), board);
+        }
+        for &spec in &[
+            "2@12x4:Apron",
+            "fill@24x4:Slat",
+            "1@12x6:Bracket#curved#double-sided",
+            "2@30x3:Rail#double-sided#grain#sequential",
+            "1@12x4:3:1 Taper",
+            "2@12x4x0.75:Apron",
+        ] {
+            let cut = Cut::parse(spec).unwrap();
+            assert_eq!(cut.to_string(), spec);
+            assert_eq!(Cut::parse(&cut.to_string()).unwrap(), cut);
+        }
+    }
+
+    #[test]
+    fn specs_parse_with_decimal_commas() {
+        let board = Board::parse_decimal_comma("96,5x5,5:Oak, quartersawn+0,5=2").unwrap();
+        assert_eq!(board.length, 96.5);
+        assert_eq!(board.width, 5.5);
+        assert_eq!(board.id, "Oak, quartersawn");
+        assert_eq!(board.overage, 0.5);
+        assert_eq!(board.exact_count, Some(2));
+        assert_eq!(
+            Board::parse_decimal_comma("96x6:A").unwrap(),
+            Board::parse("96x6:A").unwrap()
+        );
+
+        let cut = Cut::parse_decimal_comma("2@12,5x4,25:Apron, left").unwrap();
+        assert_eq!(cut.length, 12.5);
+        assert_eq!(cut.width, 4.25);
+        assert_eq!(cut.name, "Apron, left");
+
+        // without the flag, commas aren't decimal separators
+        assert!(Cut::parse("2@12,5x4:Apron").is_err());
+
+        let yaml = "
+            boards:
+              - 96x5,5:A
+            cutlist:
+              - 2@12,5x4:Apron
+            ";
+        let input = Input::parse_decimal_comma(yaml, InputFormat::Yaml).unwrap();
+        assert_eq!(input.boards[0].width, 5.5);
+        assert_eq!(input.cutlist[0].length, 12.5);
+        assert!(Input::parse(yaml, InputFormat::Yaml).is_err());
+        let json = r#"{ "boards": ["96x5,5:A"], "cutlist": ["2@12,5x4:Apron"] }"#;
+        assert_eq!(
+            Input::parse_decimal_comma(json, InputFormat::Json).unwrap(),
+            input
+        );
+    }
+
+    #[test]
+    fn board_lots_expand_to_varied_inventory() {
+        let lot = BoardLot::parse("10@96~6x8:A").unwrap();
+        assert_eq!(
+            lot,
+            BoardLot {
+                count: 10,
+                length: 96f32,
+                variance: 6f32,
+                width: 8f32,
+                id: "A".to_owned(),
             }
         );
+        assert_eq!(BoardLot::parse("10@96±6x8:A").unwrap(), lot);
+
+        let boards = lot.expand(7).unwrap();
+        assert_eq!(boards.len(), 10);
+        assert!(boards
+            .iter()
+            .all(|board| (90f32..=102f32).contains(&board.length)
+                && board.width == 8f32
+                && board.max_count == Some(1)));
+        let mut ids: Vec<&str> = boards.iter().map(|board| board.id.as_str()).collect();
+        ids.dedup();
+        assert_eq!(ids.len(), 10);
+        assert_eq!(ids[0], "A #1");
+        let shortest = boards.iter().map(|b| b.length).fold(f32::MAX, f32::min);
+        let longest = boards.iter().map(|b| b.length).fold(0f32, f32::max);
+        assert!(longest - shortest > 3f32, "lengths barely vary");
+
+        // the same seed expands the same inventory
+        let lengths =
+            |seed| -> Vec<f32> { lot.expand(seed).unwrap().iter().map(|b| b.length).collect() };
+        assert_eq!(lengths(7), lengths(7));
+        assert_ne!(lengths(7), lengths(8));
+
+        for spec in &[
+            "0@96~6x8:A",
+            "10@96~-1x8:A",
+            "10@96~96x8:A",
+            "10@96x8:A",
+            "10@96~6x8:",
+        ] {
+            assert!(BoardLot::parse(spec).is_err(), "{}", spec);
+        }
+        // a lot built by hand is validated as it expands
+        let flat = BoardLot {
+            width: 0f32,
+            ..lot.clone()
+        };
+        assert!(flat.expand(7).is_err());
+
+        let input = Input::parse(
+            "seed: 3\nboards: [10@96~6x8:A, 96x4:B]\ncutlist: [1@12x4:Apron]",
+            InputFormat::Yaml,
+        )
+        .unwrap();
+        assert_eq!(input.boards.len(), 11);
+        assert_eq!(input.boards[10].id, "B");
+        assert_eq!(input.boards[10].max_count, None);
+    }
+
+    #[test]
+    fn board_parse_accepts_exact_count() {
+        let board = Board::parse("96x6:Oak=3").expect("Expected format to parse");
+        assert_eq!(board.id, "Oak");
+        assert_eq!(board.exact_count, Some(3));
+
+        assert!(Board::parse("96x6:Oak=0").is_err());
+        assert!(Board::parse("96x6:Oak=-1").is_err());
+        assert!(Board::parse("96x6:Oak=many").is_err());
+    }
+
+    #[test]
+    fn board_parse_accepts_overage() {
+        let board = Board::parse("96x6:Oak+1").expect("Expected format to parse");
+        assert_eq!(board.id, "Oak");
+        assert_eq!(board.length, 96f32);
+        assert_eq!(board.usable_length(), 97f32);
+
+        let board = Board::parse("96x6:Oak+0.5=2").expect("Expected format to parse");
+        assert_eq!(board.id, "Oak");
+        assert_eq!(board.exact_count, Some(2));
+        assert_eq!(board.usable_length(), 96.5);
+
+        assert_eq!(Board::parse("96x6:Oak").unwrap().overage, 0f32);
+        assert!(Board::parse("96x6:Oak+-1").is_err());
+        assert_eq!(Board::parse("96x6:Oak+long").unwrap().id, "Oak+long");
+    }
+
+    #[test]
+    fn nominal_lumber_sizes_convert_to_actual() {
+        assert_eq!(actual_lumber_size(2f32, 4f32), Some((1.5, 3.5)));
+        assert_eq!(actual_lumber_size(1f32, 6f32), Some((0.75, 5.5)));
+        assert_eq!(actual_lumber_size(2f32, 10f32), Some((1.5, 9.25)));
+        assert_eq!(actual_lumber_size(2f32, 9f32), None);
+        assert_eq!(actual_lumber_dimension(3.5), None);
+
+        let mut input = Input::parse(
+            "
+            boards:
+              - 96x6:A
+              - 96x5.5:B
+            cutlist:
+              - 1@12x4:Apron
+            ",
+            InputFormat::Yaml,
+        )
+        .unwrap();
+        input.convert_nominal_sizes();
+        assert_eq!(input.boards[0].width, 5.5);
+        assert_eq!(input.boards[0].nominal_width, Some(6f32));
+        assert_eq!(input.boards[1].width, 5.5);
+        assert_eq!(input.boards[1].nominal_width, None);
+
+        // converting twice is harmless
+        input.convert_nominal_sizes();
+        assert_eq!(input.boards[0].width, 5.5);
     }
 
     #[test]
@@ -213,8 +2214,13 @@ mod spec_tests {
             Cut {
                 length: 12f32,
                 width: 4f32,
-                count: 2,
-                name: "Apron".to_owned()
+                count: CountSpec::Fixed(2),
+                name: "Apron".to_owned(),
+                shape: None,
+                double_sided: false,
+                grain: false,
+                sequential: false,
+                thickness: None,
             }
         );
 
@@ -223,12 +2229,163 @@ mod spec_tests {
             Cut {
                 length: 12.5f32,
                 width: 4.8f32,
-                count: 22,
-                name: "This has multiple words".to_owned()
+                count: CountSpec::Fixed(22),
+                name: "This has multiple words".to_owned(),
+                shape: None,
+                double_sided: false,
+                grain: false,
+                sequential: false,
+                thickness: None,
             }
         );
     }
 
+    #[test]
+    fn cut_parse_keeps_delimiters_in_names() {
+        for name in ["Box x Lid", "3:1 Taper", "Lid@Top", "2x4 Block: @ the end"] {
+            let cut = Cut::parse(&format!("2@12x4:{}", name)).expect("Expected format to parse");
+            assert_eq!(cut.name, name);
+            assert_eq!(
+                (cut.length, cut.width, cut.count),
+                (12f32, 4f32, CountSpec::Fixed(2))
+            );
+        }
+    }
+
+    #[test]
+    fn cut_parse_accepts_fill_count() {
+        let cut = Cut::parse("fill@24x4:Slat").expect("Expected format to parse");
+        assert_eq!(cut.count, CountSpec::Fill);
+        assert_eq!(cut.count.fixed(), 0);
+        assert_eq!(
+            Cut::parse("3@24x4:Slat").unwrap().count,
+            CountSpec::Fixed(3)
+        );
+        assert!(Cut::parse("lots@24x4:Slat").is_err());
+
+        let json = Input::from_json(
+            r#"{ "boards": ["96x8:A"], "cutlist": ["1@12x4:Apron", { "length": 24, "width": 4, "count": "fill", "name": "Slat" }] }"#,
+        )
+        .expect("Expected json to parse");
+        assert_eq!(json.cutlist[1], Cut::parse("fill@24x4:Slat").unwrap());
+    }
+
+    #[test]
+    fn boards_wider_than_long_look_transposed() {
+        let transposed = Board::parse("6x96:Oak").unwrap();
+        assert!(transposed.looks_transposed());
+        assert_eq!((transposed.length, transposed.width), (6f32, 96f32));
+        assert!(!Board::parse("96x6:Oak").unwrap().looks_transposed());
+        assert!(!Board::parse("24x24:Square").unwrap().looks_transposed());
+        assert!(!Board::parse("48x96:Ply#sheet").unwrap().looks_transposed());
+
+        let input = Input::parse(
+            "boards: [96x6:Oak, 8x48:Maple]\ncutlist: [1@6x4:Block]",
+            InputFormat::Yaml,
+        )
+        .unwrap();
+        let ids: Vec<&str> = input
+            .transposed_boards()
+            .iter()
+            .map(|board| board.id.as_str())
+            .collect();
+        assert_eq!(ids, ["Maple"]);
+    }
+
+    #[test]
+    fn cut_parse_accepts_sequential() {
+        let cut = Cut::parse("4@30x5:Panel#figured#double-sided#sequential").unwrap();
+        assert!(cut.sequential && cut.double_sided);
+        assert_eq!(cut.shape.as_deref(), Some("figured"));
+        assert_eq!(Cut::parse(&cut.to_string()).unwrap(), cut);
+        assert!(!Cut::parse("4@30x5:Panel").unwrap().sequential);
+
+        let yaml = |extra: &str| {
+            format!(
+                "boards: [96x8:A]\ncutlist: [4@30x2:Panel#sequential, fill@12x2:Slat]\n{}",
+                extra
+            )
+        };
+        assert!(Input::parse(&yaml(""), InputFormat::Yaml).is_ok());
+        assert!(Input::parse(&yaml("isolate: [Panel]"), InputFormat::Yaml).is_err());
+        assert!(Input::parse(
+            &yaml("same_board_groups: [[Panel, Slat]]"),
+            InputFormat::Yaml
+        )
+        .is_err());
+        assert!(Input::parse(
+            "boards: [96x8:A]\ncutlist: [1@12x4:Apron, fill@30x2:Panel#sequential]",
+            InputFormat::Yaml
+        )
+        .is_err());
+
+        let input = Input::parse(
+            r#"{ "boards": ["96x8:A"], "cutlist": [{ "length": 30, "width": 2, "count": 4, "name": "Panel", "sequential": true }] }"#,
+            InputFormat::Json,
+        )
+        .unwrap();
+        assert!(input.cutlist[0].sequential);
+    }
+
+    #[test]
+    fn cut_parse_accepts_double_sided() {
+        let cut = Cut::parse("1@30x12:Door#double-sided").unwrap();
+        assert_eq!(cut.name, "Door");
+        assert!(cut.double_sided);
+        assert_eq!(cut.shape, None);
+
+        let cut = Cut::parse("1@12x6:Bracket#curved#double-sided").unwrap();
+        assert_eq!(cut.name, "Bracket");
+        assert_eq!(cut.shape.as_deref(), Some("curved"));
+        assert!(cut.double_sided);
+        assert!(!Cut::parse("1@12x6:Bracket#curved").unwrap().double_sided);
+
+        let input = Input::from_json(
+            r#"{ "boards": ["96x8:A"], "cutlist": [{ "length": 30, "width": 12, "name": "Door", "double_sided": true }] }"#,
+        )
+        .unwrap();
+        assert!(input.cutlist[0].double_sided);
+    }
+
+    #[test]
+    fn cut_parse_accepts_grain() {
+        let cut = Cut::parse("2@30x3:Rail#grain").unwrap();
+        assert_eq!(cut.name, "Rail");
+        assert!(cut.grain);
+        let cut = Cut::parse("1@12x6:Bracket#curved#grain").unwrap();
+        assert_eq!(cut.shape.as_deref(), Some("curved"));
+        assert!(cut.grain);
+        assert!(!Cut::parse("2@30x3:Rail").unwrap().grain);
+
+        let input = Input::from_json(
+            r#"{ "boards": ["96x8:A"], "cutlist": [{ "length": 30, "width": 3, "name": "Rail", "grain": true }] }"#,
+        )
+        .unwrap();
+        assert!(input.cutlist[0].grain);
+    }
+
+    #[test]
+    fn cut_parse_accepts_shape() {
+        let cut = Cut::parse("1@12x6:Bracket#curved").expect("Expected format to parse");
+        assert_eq!(cut.name, "Bracket");
+        assert_eq!(cut.shape.as_deref(), Some("curved"));
+        assert_eq!(cut.length, 12f32);
+
+        // a '#' which isn't followed by a word stays in the name
+        let cut = Cut::parse("1@12x6:Shelf #1").expect("Expected format to parse");
+        assert_eq!(cut.name, "Shelf #1");
+        assert_eq!(cut.shape, None);
+
+        let json = Input::from_json(
+            r#"{ "boards": ["96x8:A"], "cutlist": [{ "length": 12, "width": 6, "name": "Bracket", "shape": "curved" }] }"#,
+        )
+        .expect("Expected json to parse");
+        assert_eq!(
+            json.cutlist[0],
+            Cut::parse("1@12x6:Bracket#curved").unwrap()
+        );
+    }
+
     #[test]
     fn cut_parse_rejects_bad_input() {
         // count must be integer >= 1
@@ -251,4 +2408,431 @@ mod spec_tests {
         assert!(Cut::parse("This is not a cut format string").is_err());
         assert!(Cut::parse("1.2.3.4").is_err());
     }
+
+    #[test]
+    fn input_parses_same_board_groups() {
+        let docs = YamlLoader::load_from_str(
+            "
+            boards:
+              - 96x8:A
+            cutlist:
+              - 2@12x4:Drawer Front
+              - 2@12x4:Drawer Back
+            same_board_groups:
+              - [Drawer Front, Drawer Back]
+            ",
+        )
+        .unwrap();
+        let input = Input::from(&docs[0]).expect("Expected input to parse");
+        assert_eq!(
+            input.same_board_groups,
+            vec![vec!["Drawer Front".to_owned(), "Drawer Back".to_owned()]]
+        );
+    }
+
+    #[test]
+    fn input_rejects_same_board_group_with_unknown_cut() {
+        let docs = YamlLoader::load_from_str(
+            "
+            boards:
+              - 96x8:A
+            cutlist:
+              - 2@12x4:Drawer Front
+            same_board_groups:
+              - [Drawer Front, Drawer Side]
+            ",
+        )
+        .unwrap();
+        assert!(Input::from(&docs[0]).is_err());
+    }
+
+    #[test]
+    fn input_parses_grouped_cutlist() {
+        let verbose = Input::parse(
+            "
+            boards:
+              - 96x8:Oak
+              - 96x6:Pine
+            cutlist:
+              - 4@28x2:Leg
+              - 2@40x4:Apron
+              - 2@30x6:Shelf
+              - 1@12x3:Cleat
+            pinned:
+              Leg: Oak
+              Apron: Oak
+              Shelf: Pine
+            ",
+            InputFormat::Yaml,
+        )
+        .unwrap();
+        let yaml = "
+            boards:
+              - 96x8:Oak
+              - 96x6:Pine
+            cutlist:
+              table:
+                material: Oak
+                parts: [4@28x2:Leg, 2@40x4:Apron]
+              shelves:
+                material: Pine
+                parts:
+                  - 2@30x6:Shelf
+              jig:
+                parts: [1@12x3:Cleat]
+            ";
+        let grouped = Input::parse(yaml, InputFormat::Yaml).expect("Expected input to parse");
+        assert_eq!(grouped.cutlist, verbose.cutlist);
+        assert_eq!(grouped.pinned, verbose.pinned);
+
+        // explicit pins may repeat a group's material, but not contradict it
+        let pinned = format!("{}pinned: {{ Leg: Oak, Cleat: Pine }}", yaml);
+        let pinned = Input::parse(&pinned, InputFormat::Yaml).unwrap();
+        assert_eq!(pinned.pinned.get("Cleat").map(String::as_str), Some("Pine"));
+        let contradicting = format!("{}pinned: {{ Leg: Pine }}", yaml);
+        assert!(Input::parse(&contradicting, InputFormat::Yaml).is_err());
+
+        // a material must be a known board
+        assert!(Input::parse(
+            &yaml.replace("material: Pine", "material: Ash"),
+            InputFormat::Yaml
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn input_parses_pinned_cuts() {
+        let yaml = "
+            boards:
+              - 96x8:A
+              - 96x6:B
+            cutlist:
+              - 2@12x4:Apron
+              - 4@25x2:Leg
+            pinned:
+              Apron: B
+            ";
+        let input = Input::parse(yaml, InputFormat::Yaml).expect("Expected input to parse");
+        assert_eq!(input.pinned.get("Apron").map(String::as_str), Some("B"));
+        assert_eq!(input.pinned.len(), 1);
+
+        // pins must reference known cuts and boards
+        assert!(Input::parse(&yaml.replace("Apron: B", "Rail: B"), InputFormat::Yaml).is_err());
+        assert!(Input::parse(&yaml.replace("Apron: B", "Apron: C"), InputFormat::Yaml).is_err());
+    }
+
+    #[test]
+    fn degenerate_inputs_report_clear_errors() {
+        let error = |yaml: &str| {
+            Input::parse(yaml, InputFormat::Yaml)
+                .expect_err("Expected input to be rejected")
+                .to_string()
+        };
+
+        assert!(error("").contains("No input document"));
+        assert!(error("just a string").contains("must be a mapping"));
+        assert!(error("- 96x8:A").contains("must be a mapping"));
+        assert!(error("cutlist: [1@12x4:Apron]").contains("No boards"));
+        assert!(error("boards:\ncutlist: [1@12x4:Apron]").contains("No boards"));
+        assert!(error("boards: []\ncutlist: [1@12x4:Apron]").contains("No boards"));
+        assert!(error("boards: [96x8:A]").contains("No cuts"));
+        assert!(error("boards: [96x8:A]\ncutlist:").contains("No cuts"));
+        assert!(error("boards: 96x8:A\ncutlist: [1@12x4:Apron]").contains("must be a list"));
+        assert!(error("boards: [96x8:A, 5]\ncutlist: [1@12x4:Apron]").contains("spec string"));
+
+        let no_pieces = Input::new(
+            0f32,
+            vec![Board::parse("96x8:A").unwrap()],
+            vec![Cut {
+                length: 12f32,
+                width: 4f32,
+                count: CountSpec::Fixed(0),
+                name: "Apron".into(),
+                shape: None,
+                double_sided: false,
+                grain: false,
+                sequential: false,
+                thickness: None,
+            }],
+            Vec::new(),
+            BTreeMap::new(),
+            BTreeMap::new(),
+            Vec::new(),
+        );
+        assert!(no_pieces
+            .unwrap_err()
+            .to_string()
+            .contains("no fixed pieces"));
+        assert!(error("boards: [96x8:A]\ncutlist: [fill@12x4:Apron]").contains("no fixed pieces"));
+    }
+
+    #[test]
+    fn input_rejects_bad_densities() {
+        let yaml = |densities: &str| {
+            format!(
+                "
+                boards:
+                  - 96x8:A
+                cutlist:
+                  - 1@12x4:Apron
+                densities:
+                  {}
+                ",
+                densities
+            )
+        };
+        let input = Input::parse(&yaml("A: 3.5"), InputFormat::Yaml).unwrap();
+        assert_eq!(input.densities.get("A"), Some(&3.5));
+        assert!(Input::parse(&yaml("B: 3.5"), InputFormat::Yaml).is_err());
+        assert!(Input::parse(&yaml("A: 0"), InputFormat::Yaml).is_err());
+    }
+
+    #[test]
+    fn input_parses_blade_kerfs() {
+        let yaml = |kerfs: &str| {
+            format!(
+                "version: 2\nkerf: 0.125\nboards: [96x8:A]\ncutlist: [1@12x4:Apron]\n{}",
+                kerfs
+            )
+        };
+        let input = Input::parse(&yaml(""), InputFormat::Yaml).unwrap();
+        assert_eq!((input.crosscut_kerf(), input.rip_kerf()), (0.125, 0.125));
+
+        let input = Input::parse(
+            &yaml("crosscut_kerf: 0.0625\nrip_kerf: 0.25"),
+            InputFormat::Yaml,
+        )
+        .unwrap();
+        assert_eq!((input.crosscut_kerf(), input.rip_kerf()), (0.0625, 0.25));
+        assert_eq!(input.spacing, 0.125);
+
+        let input = Input::parse(&yaml("rip_kerf: 0"), InputFormat::Yaml).unwrap();
+        assert_eq!((input.crosscut_kerf(), input.rip_kerf()), (0.125, 0f32));
+
+        assert!(Input::parse(&yaml("rip_kerf: -0.125"), InputFormat::Yaml).is_err());
+        assert!(Input::parse(&yaml("crosscut_kerf: thin"), InputFormat::Yaml).is_err());
+
+        let json = Input::parse(
+            r#"{"boards": ["96x8:A"], "cutlist": ["1@12x4:Apron"], "crosscut_kerf": 0.0625}"#,
+            InputFormat::Json,
+        )
+        .unwrap();
+        assert_eq!((json.crosscut_kerf(), json.rip_kerf()), (0.0625, 0f32));
+    }
+
+    #[test]
+    fn input_parses_operation_times() {
+        let yaml = |times: &str| format!("boards: [96x8:A]\ncutlist: [1@12x4:Apron]\n{}", times);
+        let input = Input::parse(&yaml(""), InputFormat::Yaml).unwrap();
+        assert_eq!(input.operation_times, OperationTimes::default());
+
+        let input = Input::parse(
+            &yaml("operation_times: {crosscut: 15, rip: 42.5}"),
+            InputFormat::Yaml,
+        )
+        .unwrap();
+        assert_eq!(input.operation_times.crosscut, 15f32);
+        assert_eq!(input.operation_times.rip, 42.5);
+
+        // an omitted time keeps its default
+        let input = Input::parse(&yaml("operation_times: {rip: 10}"), InputFormat::Yaml).unwrap();
+        assert_eq!(
+            input.operation_times.crosscut,
+            OperationTimes::default().crosscut
+        );
+
+        assert!(Input::parse(&yaml("operation_times: {rip: -1}"), InputFormat::Yaml).is_err());
+        assert!(Input::parse(&yaml("operation_times: {rip: fast}"), InputFormat::Yaml).is_err());
+        assert!(Input::parse(&yaml("operation_times: 10"), InputFormat::Yaml).is_err());
+
+        let json = Input::parse(
+            r#"{"boards": ["96x8:A"], "cutlist": ["1@12x4:Apron"], "operation_times": {"crosscut": 15, "rip": 42.5}}"#,
+            InputFormat::Json,
+        )
+        .unwrap();
+        assert_eq!(json.operation_times.rip, 42.5);
+    }
+
+    #[test]
+    fn oversize_cuts_are_reported_by_name() {
+        let yaml = |max_handling: &str| {
+            format!(
+                "boards: [96x24:A]\ncutlist: [2@60x20:Top, 4@25x2:Leg, 1@20x30:Panel, 1@40x26:Shelf]\n{}",
+                max_handling
+            )
+        };
+        let input = Input::parse(&yaml(""), InputFormat::Yaml).unwrap();
+        assert!(input.oversize_cuts().is_empty());
+
+        let input = Input::parse(&yaml("max_handling: 48x24"), InputFormat::Yaml).unwrap();
+        assert_eq!(input.max_handling, Some((48f32, 24f32)));
+        // the panel is within the size once turned
+        assert_eq!(input.oversize_cuts(), vec!["Top", "Shelf"]);
+
+        let input = Input::parse(&yaml("max_handling: 24x48"), InputFormat::Yaml).unwrap();
+        assert_eq!(input.oversize_cuts(), vec!["Top", "Shelf"]);
+        let input = Input::parse(&yaml("max_handling: 60x30"), InputFormat::Yaml).unwrap();
+        assert!(input.oversize_cuts().is_empty());
+
+        for bad in &[
+            "max_handling: 48",
+            "max_handling: 0x24",
+            "max_handling: [48, 24]",
+        ] {
+            assert!(
+                Input::parse(&yaml(bad), InputFormat::Yaml).is_err(),
+                "{}",
+                bad
+            );
+        }
+
+        let json = Input::parse(
+            r#"{"boards": ["96x24:A"], "cutlist": ["2@60x20:Top"], "max_handling": "48x24"}"#,
+            InputFormat::Json,
+        )
+        .unwrap();
+        assert_eq!(json.oversize_cuts(), vec!["Top"]);
+    }
+
+    #[test]
+    fn spec_errors_name_the_entry() {
+        let error = Input::parse(
+            "
+            boards:
+              - 96x8:A
+            cutlist:
+              - 1@12x4:Apron
+              - 2@x4:Bad
+            ",
+            InputFormat::Yaml,
+        )
+        .unwrap_err();
+        // the context leads, followed by the cause
+        assert_eq!(error.to_string(), "cutlist[1] '2@x4:Bad'");
+        assert!(format!("{:#}", error).starts_with("cutlist[1] '2@x4:Bad': "));
+
+        let error = Input::parse(
+            "
+            boards:
+              - 96x8:A
+              - 96:B
+            cutlist:
+              - 1@12x4:Apron
+            ",
+            InputFormat::Yaml,
+        )
+        .unwrap_err();
+        assert_eq!(
+            format!("{:#}", error),
+            "boards[1] '96:B': Invalid Board format string"
+        );
+
+        let error =
+            Input::from_json(r#"{ "boards": ["96x8:A"], "cutlist": ["2@x4:Bad"] }"#).unwrap_err();
+        assert!(format!("{:#}", error).starts_with("cutlist[0] '2@x4:Bad'"));
+    }
+
+    #[test]
+    fn versioned_inputs_parse() {
+        let cuts = "boards: [96x8:A]\ncutlist: [1@12x4:Apron]\n";
+        let v1 = Input::parse(&format!("spacing: 0.125\n{}", cuts), InputFormat::Yaml).unwrap();
+        let explicit_v1 = Input::parse(
+            &format!("version: 1\nspacing: 0.125\n{}", cuts),
+            InputFormat::Yaml,
+        )
+        .unwrap();
+        let v2 = Input::parse(
+            &format!("version: 2\nkerf: 0.125\n{}", cuts),
+            InputFormat::Yaml,
+        )
+        .unwrap();
+        assert_eq!(v1.spacing, 0.125);
+        assert_eq!(v1, explicit_v1);
+        assert_eq!(v1, v2);
+
+        let json = Input::from_json(
+            r#"{ "version": 2, "kerf": 0.125, "boards": ["96x8:A"], "cutlist": ["1@12x4:Apron"] }"#,
+        )
+        .unwrap();
+        assert_eq!(json, v2);
+
+        let error = |yaml: String| {
+            Input::parse(&yaml, InputFormat::Yaml)
+                .unwrap_err()
+                .to_string()
+        };
+        assert!(error(format!("version: 3\n{}", cuts)).contains("newer"));
+        assert!(error(format!("version: 0\n{}", cuts)).contains("at least 1"));
+        assert!(error(format!("version: two\n{}", cuts)).contains("integer"));
+        assert!(error(format!("version: 2\nspacing: 0.125\n{}", cuts)).contains("renamed"));
+        assert!(error(format!("kerf: 0.125\n{}", cuts)).contains("requires version 2"));
+    }
+
+    #[test]
+    fn json_and_yaml_inputs_parse_equivalently() {
+        let yaml = Input::parse(
+            "
+            spacing: 0.25
+            boards:
+              - 96x8:A
+              - 96x6:B=2
+            cutlist:
+              - 4@25x2:Leg
+              - 2@12x4:Apron
+            same_board_groups:
+              - [Apron]
+            pinned:
+              Leg: B
+            densities:
+              A: 3.5
+              B: 2
+            ",
+            InputFormat::Yaml,
+        )
+        .expect("Expected yaml to parse");
+
+        let json = Input::parse(
+            r#"{
+                "spacing": 0.25,
+                "boards": [
+                    "96x8:A",
+                    { "length": 96, "width": 6, "id": "B", "exact_count": 2 }
+                ],
+                "cutlist": [
+                    { "length": 25, "width": 2, "count": 4, "name": "Leg" },
+                    "2@12x4:Apron"
+                ],
+                "same_board_groups": [["Apron"]],
+                "pinned": { "Leg": "B" },
+                "densities": { "A": 3.5, "B": 2 }
+            }"#,
+            InputFormat::Json,
+        )
+        .expect("Expected json to parse");
+
+        assert_eq!(yaml, json);
+        for (yaml_board, json_board) in yaml.boards.iter().zip(json.boards.iter()) {
+            assert_eq!(yaml_board.id, json_board.id);
+            assert_eq!(yaml_board.exact_count, json_board.exact_count);
+        }
+    }
+
+    #[test]
+    fn json_input_shares_validation() {
+        // structured entries are validated the same as spec strings
+        assert!(Input::from_json(
+            r#"{ "boards": [{ "length": 0, "width": 6, "id": "A" }], "cutlist": ["1@12x4:Apron"] }"#
+        )
+        .is_err());
+        assert!(Input::from_json(r#"{ "boards": ["96x6:A"], "cutlist": [] }"#).is_err());
+    }
+
+    #[test]
+    fn input_format_detects_by_extension() {
+        assert_eq!(InputFormat::from_path("input.json"), InputFormat::Json);
+        assert_eq!(InputFormat::from_path("input.yaml"), InputFormat::Yaml);
+        assert_eq!(InputFormat::from_path("input"), InputFormat::Yaml);
+        assert_eq!("JSON".parse::<InputFormat>().unwrap(), InputFormat::Json);
+        assert!("toml".parse::<InputFormat>().is_err());
+    }
 }
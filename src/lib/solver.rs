@@ -1,13 +1,81 @@
+use std::{
+    cell::Cell,
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap},
+    fmt,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use anyhow::{bail, Result};
+use log::{debug, info, warn};
 use rand::prelude::*;
 use rand_pcg::Pcg64;
+use serde::{Deserialize, Serialize};
 
 use super::model;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Cut {
     pub length: f32,
     pub width: f32,
     pub id: String,
+    /// Name of the model cut this was expanded from; unlike `id` this is never numbered.
+    pub name: String,
+    /// The order in which this cut was placed while generating its solution, starting at 0
+    pub placement: usize,
+    /// How this cut came to be placed where it is, once placed
+    pub outcome: Option<PlacementOutcome>,
+    /// The shape of the part to be cut from this rectangular blank, if it isn't rectangular
+    pub shape: Option<String>,
+    /// If true, both faces of the part get marked; the face up as laid out is the one to mark first
+    pub double_sided: bool,
+    /// If true, this cut gets a stack of its own, shared with no other cut
+    pub isolated: bool,
+    /// The part's thickness, if given; it only goes on boards which may be planed to it
+    #[serde(default)]
+    pub thickness: Option<f32>,
+    /// If true, every copy of this cut is laid out the same way, all rotated or none
+    #[serde(default)]
+    pub uniform_orientation: bool,
+    /// If true, the cut's length must run along the board's length, with the grain
+    #[serde(default)]
+    pub grain: bool,
+    /// If true, the cut is laid out rotated, its length running across the board
+    #[serde(default)]
+    pub rotated: bool,
+}
+
+/// The result of offering a cut to a board
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum PlacementOutcome {
+    /// The cut joined the existing stack which best fit it, whose length differed from the cut's by `length_difference`
+    ExistingStack { length_difference: f32 },
+    /// The cut opened a new stack on a board already in use
+    NewStack,
+    /// The cut opened the first stack on a newly vended board, as it didn't fit any board in use
+    NewBoard,
+    /// The board could not take the cut
+    Rejected,
+}
+
+impl PlacementOutcome {
+    pub fn is_placed(&self) -> bool {
+        *self != PlacementOutcome::Rejected
+    }
+
+    /// A human readable explanation of the outcome
+    pub fn reason(&self) -> String {
+        match self {
+            PlacementOutcome::ExistingStack { length_difference } => format!(
+                "placed in existing stack (best fit, \u{394}len {:.2})",
+                length_difference
+            ),
+            PlacementOutcome::NewStack => "opened new stack".to_owned(),
+            PlacementOutcome::NewBoard => "vended new board (no fit)".to_owned(),
+            PlacementOutcome::Rejected => "rejected".to_owned(),
+        }
+    }
 }
 
 impl PartialEq for Cut {
@@ -30,11 +98,31 @@ impl std::hash::Hash for Cut {
 }
 
 impl Cut {
-    fn from(cut: &model::Cut, outset: f32) -> Cut {
+    // the cut grown by the kerf of each blade: the crosscut's along its length, the rip's across
+    // its width, so neighbors in a stack are a rip kerf apart and neighboring stacks a crosscut's
+    fn from(cut: &model::Cut, crosscut_kerf: f32, rip_kerf: f32) -> Cut {
         Cut {
-            length: cut.length + outset,
-            width: cut.width + outset,
+            length: cut.length + crosscut_kerf,
+            width: cut.width + rip_kerf,
             id: cut.name.clone(),
+            name: cut.name.clone(),
+            placement: 0,
+            outcome: None,
+            shape: cut.shape.clone(),
+            double_sided: cut.double_sided,
+            isolated: false,
+            thickness: cut.thickness,
+            uniform_orientation: false,
+            grain: cut.grain,
+            rotated: false,
+        }
+    }
+
+    // returns a copy of this cut, recording how it was placed
+    fn placed(&self, outcome: PlacementOutcome) -> Cut {
+        Cut {
+            outcome: Some(outcome),
+            ..self.clone()
         }
     }
 
@@ -43,12 +131,22 @@ impl Cut {
             length: self.width,
             width: self.length,
             id: self.id,
+            name: self.name,
+            placement: self.placement,
+            outcome: self.outcome,
+            shape: self.shape,
+            double_sided: self.double_sided,
+            isolated: self.isolated,
+            thickness: self.thickness,
+            uniform_orientation: self.uniform_orientation,
+            grain: self.grain,
+            rotated: !self.rotated,
         }
     }
 }
 
 /// Represents a stack of cuts which can be easily crosscut from a board, and then ripped and crosscut to dimension.
-#[derive(Clone)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CutStack {
     pub cuts: Vec<Cut>,
 }
@@ -95,63 +193,291 @@ impl CutStack {
     }
 }
 
-#[derive(Clone)]
+/// Orders floats totally, so sorts never panic: NaN sorts above every number, and -0 below 0.
+/// Values comparing Equal are identical, so stable sorts keep them in their original order.
+pub fn total_cmp_f32(a: f32, b: f32) -> Ordering {
+    a.total_cmp(&b)
+}
+
+/// Crosscuts within this distance of a board's end land on the end of the board, and are unnecessary
+const CROSSCUT_EPSILON: f32 = 1e-4;
+
+/// How strongly a board's score favors concentrating its waste into a single offcut, from 0 (ignored) to 1
+const OFFCUT_CONCENTRATION_WEIGHT: f32 = 0.25;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Board {
+    /// The length available to cuts, including any overage the stock came with
     pub length: f32,
+    /// The length the board is sold as, e.g., for labels
+    pub nominal_length: f32,
     pub width: f32,
+    /// The width the board is sold as, e.g., a nominal lumber width, for labels
+    pub nominal_width: f32,
     pub id: String,
     pub stacks: Vec<CutStack>,
+    /// Regions of the board no cut may overlap, e.g., knots or checks
+    #[serde(default)]
+    pub defects: Vec<Rect>,
+    /// Lengths trimmed off the board's start and far end to square them, outside of `length`;
+    /// cuts are laid out from the end of the start's trim
+    #[serde(default)]
+    pub end_trims: (f32, f32),
+    /// The board's thickness, if given, and the most it may be planed down to a cut's
+    #[serde(default)]
+    pub thickness: Option<f32>,
+    #[serde(default)]
+    pub planing_allowance: Option<f32>,
+    /// The id of the lot the board was expanded from, if any; see `model::Board::lot`
+    #[serde(default)]
+    pub lot: Option<String>,
 }
 
 impl From<&model::Board> for Board {
     fn from(board: &model::Board) -> Self {
         Board {
-            length: board.length,
-            width: board.width,
+            length: board.usable_length(),
+            nominal_length: board.length,
+            width: board.usable_width(),
+            nominal_width: board.nominal_width.unwrap_or(board.width),
             id: board.id.clone(),
             stacks: Vec::new(),
+            // defects are placed from the board's end, before its start is trimmed
+            defects: board
+                .defects
+                .iter()
+                .map(|defect| Rect {
+                    x: defect.x - board.end_trims.0,
+                    ..Rect::from(defect)
+                })
+                .collect(),
+            end_trims: board.end_trims,
+            thickness: board.thickness,
+            planing_allowance: board.planing_allowance,
+            lot: board.lot.clone(),
         }
     }
 }
 
 impl Board {
-    fn can_accept(&self, cut: &Cut) -> bool {
-        self.width >= cut.width
-            && self.best_stack_for_cut(cut).is_some()
-            && self.unallocated_length() >= cut.length
+    /// True if the board goes by `id`: its own, or that of the lot it was expanded from
+    pub fn has_id(&self, id: &str) -> bool {
+        self.id == id || self.lot.as_deref() == Some(id)
+    }
+
+    fn can_accept(&self, cut: &Cut, options: &Options) -> bool {
+        self.placement_for(cut, options).is_some()
+    }
+
+    // decides where `accept` would place the cut, without placing it, as
+    // `placement_clear_of_defects_for` does; with `strict_guillotine` set, None if the board with
+    // the cut placed couldn't then be cut edge-to-edge
+    fn placement_for(
+        &self,
+        cut: &Cut,
+        options: &Options,
+    ) -> Option<(Option<usize>, PlacementOutcome)> {
+        let placement = self.placement_clear_of_defects_for(cut, options)?;
+        if options.strict_guillotine {
+            let mut candidate = self.clone();
+            candidate.place(cut, placement.clone());
+            if !candidate.is_guillotine_valid() {
+                return None;
+            }
+        }
+        Some(placement)
+    }
+
+    // decides where the cut goes: in the best existing stack for it, by index, unless growing that
+    // stack would overrun the board, otherwise in a new stack if there's length left for one. Where
+    // a cut would then overlap a defect, as growing a stack shifts the stacks after it, a new stack
+    // is tried instead. None if the board can't take the cut clear of its defects.
+    fn placement_clear_of_defects_for(
+        &self,
+        cut: &Cut,
+        options: &Options,
+    ) -> Option<(Option<usize>, PlacementOutcome)> {
+        let placement = self.placement_ignoring_defects_for(cut, options)?;
+        if self.defects.is_empty() {
+            return Some(placement);
+        }
+        let is_clear = |placement: &(Option<usize>, PlacementOutcome)| {
+            let mut placed = self.clone();
+            placed.place(cut, placement.clone());
+            !placed.overlaps_defect()
+        };
+        if is_clear(&placement) {
+            return Some(placement);
+        }
+        let new_stack = (None, PlacementOutcome::NewStack);
+        if placement.0.is_some() && self.unallocated_length() >= cut.length && is_clear(&new_stack)
+        {
+            Some(new_stack)
+        } else {
+            None
+        }
     }
 
-    // if the board can take this cut into its allocation, take it in, returning true, otherwise return false
-    fn accept(&mut self, cut: &Cut) -> bool {
+    fn placement_ignoring_defects_for(
+        &self,
+        cut: &Cut,
+        options: &Options,
+    ) -> Option<(Option<usize>, PlacementOutcome)> {
         if cut.length > self.length || cut.width > self.width {
             // cut simply will not fit this board
-            return false;
-        } else if let Some(best_stack_index) = self.best_stack_for_cut(cut) {
-            // if we found a viable stack for this cut att it
+            None
+        } else if !model::thickness_fits(self.thickness, self.planing_allowance, cut.thickness) {
+            // the board can't be planed to the cut's thickness
+            None
+        } else if cut.grain && cut.rotated {
+            // a grained cut's length must run with the board's grain
+            None
+        } else if !options.is_safe_to_crosscut(cut) {
+            // crosscutting a piece this short is unsafe
+            None
+        } else if let Some(best_stack_index) = self.best_stack_for_cut(cut, options) {
+            let stack_length = self.stacks[best_stack_index].length();
+            let grown_length =
+                self.allocated_length() - stack_length + stack_length.max(cut.length);
+            if grown_length > self.length {
+                // adding to this stack would overflow the board
+                return None;
+            }
+            let outcome = PlacementOutcome::ExistingStack {
+                length_difference: (cut.length - stack_length).abs(),
+            };
+            Some((Some(best_stack_index), outcome))
+        } else if self.unallocated_length() >= cut.length {
+            // a new stack for this cut; if it's the first, this board was vended for it
+            let outcome = if self.stacks.is_empty() {
+                PlacementOutcome::NewBoard
+            } else {
+                PlacementOutcome::NewStack
+            };
+            Some((None, outcome))
+        } else {
+            None
+        }
+    }
+
+    /// If the board can take this cut into its allocation, takes it in, returning how it was placed, otherwise returns Rejected
+    pub fn accept(&mut self, cut: &Cut, options: &Options) -> PlacementOutcome {
+        match self.placement_for(cut, options) {
+            Some(placement) => self.place(cut, placement),
+            None => PlacementOutcome::Rejected,
+        }
+    }
 
-            // Checking if adding to this stack would overflow the board
-            self.stacks[best_stack_index].cuts.push(cut.clone());
-            if self.allocated_length() > self.length {
-                self.stacks[best_stack_index].cuts.pop();
-                return false;
+    // places the cut as `placement_for` decided, in the stack at its index or a new one
+    fn place(
+        &mut self,
+        cut: &Cut,
+        (stack_index, outcome): (Option<usize>, PlacementOutcome),
+    ) -> PlacementOutcome {
+        match stack_index {
+            Some(stack_index) => {
+                self.stacks[stack_index]
+                    .cuts
+                    .push(cut.placed(outcome.clone()));
             }
+            None => {
+                let mut new_stack = CutStack::new();
+                new_stack.cuts.push(cut.placed(outcome.clone()));
+                self.stacks.push(new_stack);
+            }
+        }
+        outcome
+    }
 
-            return true;
+    /// Returns each cut with its offset on the board, stack by stack, each stack's cuts ripped from
+    /// the board's edge onward. The one source of where cuts lie on a board, shared by the
+    /// exports and the visualizer.
+    pub fn placements(&self) -> impl Iterator<Item = Placement<'_>> {
+        let mut x = 0f32;
+        self.stacks.iter().flat_map(move |stack| {
+            let stack_x = x;
+            x += stack.length();
+            let mut y = 0f32;
+            stack.cuts.iter().map(move |cut| {
+                let placement = Placement {
+                    board: self,
+                    cut,
+                    x: stack_x,
+                    y,
+                };
+                y += cut.width;
+                placement
+            })
+        })
+    }
+
+    /// Returns the rectangle of the board each cut covers, in the order of `placements`
+    pub fn cut_rects(&self) -> Vec<Rect> {
+        self.placements()
+            .map(|placement| Rect {
+                x: placement.x,
+                y: placement.y,
+                length: placement.cut.length,
+                width: placement.cut.width,
+            })
+            .collect()
+    }
+
+    // true if any cut covers part of one of the board's defects
+    fn overlaps_defect(&self) -> bool {
+        let rects = self.cut_rects();
+        self.defects
+            .iter()
+            .any(|defect| rects.iter().any(|rect| rect.overlaps(defect)))
+    }
+
+    /// Takes in all of `cuts`, the identical pieces of a sequential set, one after the next in a
+    /// single stack so they stay side by side: placed as a block as wide as the set, as `accept` would
+    /// place a cut. Returns how the first piece was placed, or Rejected, taking none, if no stack can
+    /// take them all.
+    pub fn accept_sequence(&mut self, cuts: &[Cut], options: &Options) -> PlacementOutcome {
+        let first = match cuts.first() {
+            Some(first) => first,
+            None => return PlacementOutcome::Rejected,
+        };
+        let block = Cut {
+            width: cuts.iter().map(|cut| cut.width).sum(),
+            ..first.clone()
+        };
+        let (stack_index, outcome) = match self.placement_for(&block, options) {
+            Some(placement) => placement,
+            None => return PlacementOutcome::Rejected,
+        };
+        let stack_index = stack_index.unwrap_or_else(|| {
+            self.stacks.push(CutStack::new());
+            self.stacks.len() - 1
+        });
+        let rest = PlacementOutcome::ExistingStack {
+            length_difference: 0f32,
+        };
+        for (i, cut) in cuts.iter().enumerate() {
+            let outcome = if i == 0 {
+                outcome.clone()
+            } else {
+                rest.clone()
+            };
+            self.stacks[stack_index].cuts.push(cut.placed(outcome));
         }
+        outcome
+    }
 
-        if self.unallocated_length() >= cut.length {
-            // Create a new stack for this cut
-            let mut new_stack = CutStack::new();
-            new_stack.cuts.push(cut.clone());
-            self.stacks.push(new_stack);
-            true
-        } else {
-            false
+    /// Takes out the cut at `cut_index` of the stack at `stack_index`, returning it; a stack left
+    /// empty is dropped. The other cuts keep their places.
+    pub fn remove(&mut self, stack_index: usize, cut_index: usize) -> Cut {
+        let cut = self.stacks[stack_index].cuts.remove(cut_index);
+        if self.stacks[stack_index].cuts.is_empty() {
+            self.stacks.remove(stack_index);
         }
+        cut
     }
 
     // total length used by stacks
-    fn allocated_length(&self) -> f32 {
+    pub(crate) fn allocated_length(&self) -> f32 {
         self.stacks
             .iter()
             .fold(0f32, |acc, stack| acc + stack.length())
@@ -162,15 +488,27 @@ impl Board {
         self.length - self.allocated_length()
     }
 
-    fn best_stack_for_cut(&self, cut: &Cut) -> Option<usize> {
-        // find the best stack in the board for this cut
-        // TODO: Consider a vetting criteria such as, is this stack less than 50% different in length?
+    fn best_stack_for_cut(&self, cut: &Cut, options: &Options) -> Option<usize> {
+        // find the best stack in the board for this cut, among those close enough to its length
+        let max_length_difference = cut.length * options.stack_fit_tolerance;
         let mut best_stack_index: Option<usize> = None;
         let mut best_stack_length_difference: f32 = f32::MAX;
+        if cut.isolated {
+            return None;
+        }
         for (i, stack) in self.stacks.iter().enumerate() {
+            if stack.cuts.iter().any(|c| c.isolated) {
+                continue;
+            }
+            if options.uniform_rips && stack.cuts.iter().any(|c| c.name != cut.name) {
+                // this stack holds a different part; uniform rips forbid mixing them
+                continue;
+            }
             if stack.width() + cut.width < self.width {
                 let length_difference = (cut.length - stack.length()).abs();
-                if length_difference < best_stack_length_difference {
+                if length_difference <= max_length_difference
+                    && length_difference < best_stack_length_difference
+                {
                     best_stack_index = Some(i);
                     best_stack_length_difference = length_difference;
                 }
@@ -180,55 +518,698 @@ impl Board {
         best_stack_index
     }
 
+    /// Orders the cuts within each stack longest first, and the stacks themselves longest first, so
+    /// output doesn't reflect the randomness of placement. The set of cuts on the board is unchanged.
+    /// A board whose tidied cuts would overlap a defect is left as it was.
+    /// `compute` tidies the solutions it returns, once they've been ranked.
+    pub fn tidy(&mut self) {
+        let untidy = self.stacks.clone();
+        let by_size = |a: (f32, f32), b: (f32, f32)| {
+            total_cmp_f32(b.0, a.0).then_with(|| total_cmp_f32(b.1, a.1))
+        };
+        for stack in &mut self.stacks {
+            // by name before id, so the pieces of a sequential set stay together
+            stack.cuts.sort_by(|a, b| {
+                by_size((a.length, a.width), (b.length, b.width))
+                    .then_with(|| a.name.cmp(&b.name))
+                    .then_with(|| a.id.cmp(&b.id))
+            });
+        }
+        self.stacks.sort_by(|a, b| {
+            by_size((a.length(), a.width()), (b.length(), b.width())).then_with(|| {
+                let first_id = |stack: &CutStack| stack.cuts.first().map(|cut| cut.id.clone());
+                first_id(a).cmp(&first_id(b))
+            })
+        });
+        if self.overlaps_defect() {
+            self.stacks = untidy;
+        }
+    }
+
+    /// Returns true if the board can be cut edge-to-edge: each stack crosscut from the board, each cut
+    /// ripped from its stack, then trimmed to length. This holds so long as the stacks fit the board's
+    /// length and width, and each cut fits its stack; `accept` maintains these as it places cuts.
+    pub fn is_guillotine_valid(&self) -> bool {
+        self.allocated_length() <= self.length + CROSSCUT_EPSILON
+            && self.stacks.iter().all(|stack| {
+                stack.width() <= self.width + CROSSCUT_EPSILON
+                    && stack
+                        .cuts
+                        .iter()
+                        .all(|cut| cut.length <= stack.length() + CROSSCUT_EPSILON)
+            })
+    }
+
+    /// Returns the board's volume in board-feet, by its nominal dimensions at 4/4 thickness
+    pub fn board_feet(&self) -> f32 {
+        self.nominal_length * self.nominal_width / 144f32
+    }
+
+    /// Returns all the cuts on this board, across all of its stacks
+    pub fn cuts(&self) -> Vec<&Cut> {
+        self.stacks.iter().flat_map(|stack| &stack.cuts).collect()
+    }
+
+    /// Returns true if a crosscut `offset` along the board's length would separate stock,
+    /// rather than landing on (or past) the end of the board.
+    pub fn requires_crosscut_at(&self, offset: f32) -> bool {
+        offset < self.length - CROSSCUT_EPSILON
+    }
+
+    /// Returns the offsets along the board's length of the crosscuts separating its stacks.
+    /// A stack ending at the board's end needs no crosscut; it's only ripped.
+    pub fn crosscuts(&self) -> Vec<f32> {
+        let mut crosscuts = Vec::new();
+        let mut offset = 0f32;
+        for stack in &self.stacks {
+            offset += stack.length();
+            if self.requires_crosscut_at(offset) {
+                crosscuts.push(offset);
+            }
+        }
+        crosscuts
+    }
+
+    // returns the fraction of the board's area covered by cuts
+    fn fill(&self) -> f32 {
+        1f32 - self.scrap_area() / (self.length * self.width)
+    }
+
+    // returns the area of the board not covered by cuts
+    fn scrap_area(&self) -> f32 {
+        let used_area: f32 = self.stacks.iter().map(|stack| stack.used_area()).sum();
+        self.length * self.width - used_area
+    }
+
+    /// Returns the extent (along the length, across the width) of the board occupied by cuts, from
+    /// the board's origin to the furthest cut edges; (0, 0) if it has no cuts. Past the extent's
+    /// length is the board's single tail offcut.
+    pub fn used_bounds(&self) -> (f32, f32) {
+        self.placements()
+            .fold((0f32, 0f32), |(length, width), placement| {
+                (
+                    length.max(placement.x + placement.cut.length),
+                    width.max(placement.y + placement.cut.width),
+                )
+            })
+    }
+
+    /// Returns the (length, width) of the largest single rectangular offcut left on the board.
+    /// Candidates are the space past the end of each cut within its stack, and any run of the
+    /// free space beside the stacks (and the unallocated tail of the board).
+    pub fn largest_offcut(&self) -> (f32, f32) {
+        let mut largest = (0f32, 0f32);
+        let mut consider = |length: f32, width: f32| {
+            if length * width > largest.0 * largest.1 {
+                largest = (length, width);
+            }
+        };
+
+        for stack in &self.stacks {
+            let stack_length = stack.length();
+            for cut in &stack.cuts {
+                consider(stack_length - cut.length, cut.width);
+            }
+        }
+
+        // each stack leaves a column of free width beside it; the tail is entirely free.
+        // the largest offcut spanning columns is bounded by the narrowest column in the run.
+        let mut columns: Vec<(f32, f32)> = self
+            .stacks
+            .iter()
+            .map(|stack| (stack.length(), self.width - stack.width()))
+            .collect();
+        columns.push((self.unallocated_length(), self.width));
+
+        for start in 0..columns.len() {
+            let mut length = 0f32;
+            let mut width = f32::MAX;
+            for column in &columns[start..] {
+                length += column.0;
+                width = width.min(column.1);
+                consider(length, width);
+            }
+        }
+
+        largest
+    }
+
+    /// Returns the (length, width) of each loose offcut which would be left once the board is cut:
+    /// the end of each cut shorter than its stack, the free width beside each stack, and the tail.
+    pub fn offcuts(&self) -> Vec<(f32, f32)> {
+        let mut offcuts = Vec::new();
+        let mut consider = |length: f32, width: f32| {
+            if length > CROSSCUT_EPSILON && width > CROSSCUT_EPSILON {
+                offcuts.push((length, width));
+            }
+        };
+
+        for stack in &self.stacks {
+            let stack_length = stack.length();
+            for cut in &stack.cuts {
+                consider(stack_length - cut.length, cut.width);
+            }
+            consider(stack_length, self.width - stack.width());
+        }
+        consider(self.unallocated_length(), self.width);
+
+        offcuts
+    }
+
+    // returns the fraction of the scrap which is in the largest offcut, where 1 means
+    // all waste is concentrated in one reusable block.
+    fn offcut_concentration(&self) -> f32 {
+        let scrap_area = self.scrap_area();
+        if scrap_area > 0f32 {
+            let (length, width) = self.largest_offcut();
+            (length * width / scrap_area).min(1f32)
+        } else {
+            1f32
+        }
+    }
+
     fn score(&self) -> Option<f32> {
+        self.score_breakdown().map(|breakdown| breakdown.score)
+    }
+
+    /// Returns the components of the board's score, or None if the board holds no cuts
+    pub fn score_breakdown(&self) -> Option<ScoreBreakdown> {
         if !self.stacks.is_empty() {
-            Some(
-                self.stacks
-                    .iter()
-                    .fold(1f32, |acc, stack| acc * stack.score()),
-            )
+            let density = self
+                .stacks
+                .iter()
+                .fold(1f32, |acc, stack| acc * stack.score());
+
+            // scattered waste is penalized relative to the same waste as a single offcut
+            let concentration = self.offcut_concentration();
+            let shape = 1f32 - OFFCUT_CONCENTRATION_WEIGHT * (1f32 - concentration);
+            Some(ScoreBreakdown {
+                score: density * shape,
+                density,
+                concentration,
+                scrap_area: self.scrap_area(),
+            })
         } else {
             None
         }
     }
 }
 
-fn score(boards: &[Board]) -> f32 {
+/// The components of a board's score
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct ScoreBreakdown {
+    /// The board's score, from density and concentration
+    pub score: f32,
+    /// The product of the board's stack scores, reflecting how tightly cuts fill their stacks
+    pub density: f32,
+    /// The fraction of the board's scrap in its largest offcut
+    pub concentration: f32,
+    /// The area of the board not covered by cuts
+    pub scrap_area: f32,
+}
+
+/// Finds a board in the solution by id. Since a solution may use several boards of the same id,
+/// a specific one may be selected with a 1-based index suffix, e.g., "Oak#2" for the second Oak board.
+pub fn find_board<'a>(solution: &'a [Board], id: &str) -> Option<&'a Board> {
+    if let Some(board) = solution.iter().find(|board| board.id == id) {
+        return Some(board);
+    }
+
+    let (id, index) = id.rsplit_once('#')?;
+    let index = index.parse::<usize>().ok()?;
+    solution
+        .iter()
+        .filter(|board| board.id == id)
+        .nth(index.checked_sub(1)?)
+}
+
+/// A saw operation in a cutting sequence
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum Operation {
+    /// Crosscut a stack from its board; the setting is the stack's length
+    Crosscut,
+    /// Rip a cut from its stack; the setting is the cut's width
+    Rip,
+    /// Crosscut a ripped cut to its final length; the setting is the cut's length
+    Trim,
+}
+
+/// A single step in a cutting sequence
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct OperationStep {
+    pub operation: Operation,
+    /// The fence or stop setting for the operation
+    pub setting: f32,
+    /// Index of the board in the solution
+    pub board: usize,
+    /// Index of the stack on the board
+    pub stack: usize,
+    /// The id of the cut produced, for rips and trims
+    pub cut: Option<String>,
+}
+
+/// Sequences the saw operations needed to execute a solution, so that consecutive operations share
+/// the same fence/stop setting wherever possible. All stacks are crosscut from their boards first,
+/// then all cuts are ripped from their stacks, and finally trimmed to length; within each phase,
+/// operations with identical settings are adjacent.
+pub fn optimize_operation_order(solution: &[Board]) -> Vec<OperationStep> {
+    let mut crosscuts = Vec::new();
+    let mut rips = Vec::new();
+    let mut trims = Vec::new();
+
+    for (board_index, board) in solution.iter().enumerate() {
+        let mut offset = 0f32;
+        for (stack_index, stack) in board.stacks.iter().enumerate() {
+            let stack_length = stack.length();
+            offset += stack_length;
+            if board.requires_crosscut_at(offset) {
+                crosscuts.push(OperationStep {
+                    operation: Operation::Crosscut,
+                    setting: stack_length,
+                    board: board_index,
+                    stack: stack_index,
+                    cut: None,
+                });
+            }
+
+            for cut in &stack.cuts {
+                rips.push(OperationStep {
+                    operation: Operation::Rip,
+                    setting: cut.width,
+                    board: board_index,
+                    stack: stack_index,
+                    cut: Some(cut.id.clone()),
+                });
+                if stack_length - cut.length > CROSSCUT_EPSILON {
+                    trims.push(OperationStep {
+                        operation: Operation::Trim,
+                        setting: cut.length,
+                        board: board_index,
+                        stack: stack_index,
+                        cut: Some(cut.id.clone()),
+                    });
+                }
+            }
+        }
+    }
+
+    // stable sorts keep board/stack order among operations sharing a setting
+    let mut steps = Vec::new();
+    for mut phase in [crosscuts, rips, trims] {
+        phase.sort_by(|a, b| total_cmp_f32(b.setting, a.setting));
+        steps.append(&mut phase);
+    }
+    steps
+}
+
+/// Returns the number of (crosscuts, rips) needed to execute the solution, where crosscuts include
+/// both crosscutting stacks from boards and trimming cuts to length, per `optimize_operation_order`
+pub fn operation_counts(solution: &[Board]) -> (usize, usize) {
+    let steps = optimize_operation_order(solution);
+    let rips = steps
+        .iter()
+        .filter(|step| step.operation == Operation::Rip)
+        .count();
+    (steps.len() - rips, rips)
+}
+
+/// One phase of a `Schedule`: every step of a single kind of saw operation, back to back
+#[derive(Clone, Debug, PartialEq)]
+pub struct SchedulePhase {
+    pub operation: Operation,
+    pub steps: Vec<OperationStep>,
+    /// When the phase begins, from the start of the session, in the units of the operation times
+    pub start: f32,
+    pub duration: f32,
+}
+
+/// A timeline of the saw operations executing a solution, in the order `optimize_operation_order`
+/// sequences them, grouped by phase
+#[derive(Clone, Debug, PartialEq)]
+pub struct Schedule {
+    pub phases: Vec<SchedulePhase>,
+}
+
+impl Schedule {
+    /// The estimated duration of the whole session
+    pub fn total(&self) -> f32 {
+        self.phases
+            .last()
+            .map(|phase| phase.start + phase.duration)
+            .unwrap_or_default()
+    }
+}
+
+// formats seconds as h:mm:ss, or m:ss for sessions under an hour
+fn clock(seconds: f32) -> String {
+    let seconds = seconds.round() as u64;
+    match seconds / 3600 {
+        0 => format!("{}:{:02}", seconds / 60, seconds % 60),
+        hours => format!("{}:{:02}:{:02}", hours, seconds / 60 % 60, seconds % 60),
+    }
+}
+
+/// Formats the schedule as a text timeline, a row per phase with a bar spanning its share of the
+/// session, reading the operation times as seconds
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const BAR_COLUMNS: f32 = 40f32;
+        let total = self.total();
+        writeln!(f, "Schedule, estimated {}:", clock(total))?;
+        for phase in &self.phases {
+            let (start, end) = if total > 0f32 {
+                let column = |time: f32| (time / total * BAR_COLUMNS).round() as usize;
+                (column(phase.start), column(phase.start + phase.duration))
+            } else {
+                (0, 0)
+            };
+            let name = match phase.operation {
+                Operation::Crosscut => "Rough cut",
+                Operation::Rip => "Rip",
+                Operation::Trim => "Crosscut to length",
+            };
+            writeln!(
+                f,
+                "  {:>8} - {:<8} |{}{}{}| {} ({} operations)",
+                clock(phase.start),
+                clock(phase.start + phase.duration),
+                " ".repeat(start),
+                "#".repeat(end - start),
+                " ".repeat(BAR_COLUMNS as usize - end),
+                name,
+                phase.steps.len()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Builds a schedule for cutting the solution: its stacks rough cut from their boards, then its
+/// cuts ripped, then crosscut to length, each phase's duration estimated from `times`. Phases
+/// without any operations are left out.
+pub fn build_schedule(solution: &[Board], times: &model::OperationTimes) -> Schedule {
+    let mut phases: Vec<SchedulePhase> = Vec::new();
+    for step in optimize_operation_order(solution) {
+        let time = match step.operation {
+            Operation::Rip => times.rip,
+            Operation::Crosscut | Operation::Trim => times.crosscut,
+        };
+        match phases.last_mut() {
+            Some(phase) if phase.operation == step.operation => {
+                phase.duration += time;
+                phase.steps.push(step);
+            }
+            _ => {
+                let start = phases
+                    .last()
+                    .map(|phase| phase.start + phase.duration)
+                    .unwrap_or_default();
+                phases.push(SchedulePhase {
+                    operation: step.operation,
+                    steps: vec![step],
+                    start,
+                    duration: time,
+                });
+            }
+        }
+    }
+    Schedule { phases }
+}
+
+/// Estimates the time to cut the solution, in the units of `times`, from its operation counts
+pub fn cutting_time(solution: &[Board], times: &model::OperationTimes) -> f32 {
+    let (crosscuts, rips) = operation_counts(solution);
+    crosscuts as f32 * times.crosscut + rips as f32 * times.rip
+}
+
+/// Scores a solution, where higher is better; the product of the scores of its boards
+pub fn score(boards: &[Board]) -> f32 {
     boards
         .iter()
-        .map(|board| board.score())
-        .flatten()
+        .filter_map(|board| board.score())
         .fold(1f32, |acc, score| acc * score)
 }
 
-fn is_a_solution_possible(model: &model::Input) -> bool {
-    // if any cut in the cutlist is wider than available board stock,
-    // no solution is possible!
+/// Returns the solution's longest board, by the length it's sold at, if it has any boards
+pub fn longest_board(solution: &[Board]) -> Option<&Board> {
+    solution
+        .iter()
+        .max_by(|a, b| total_cmp_f32(a.nominal_length, b.nominal_length))
+}
+
+/// Returns the length of the longest section crosscut from any of the solution's boards, i.e.,
+/// its longest stack; 0 if it has none
+pub fn longest_stack(solution: &[Board]) -> f32 {
+    solution
+        .iter()
+        .flat_map(|board| &board.stacks)
+        .map(|stack| stack.length())
+        .fold(0f32, f32::max)
+}
+
+/// Returns the number of loose offcuts the solution would leave, across all of its boards
+pub fn offcut_count(solution: &[Board]) -> usize {
+    solution.iter().map(|board| board.offcuts().len()).sum()
+}
+
+/// Estimates the effort of handling the solution's boards, as big boards are harder to wrangle
+/// than small ones: the sum of each board's area, so two short boards cost what a long one does.
+pub fn handling_effort(solution: &[Board]) -> f32 {
+    solution
+        .iter()
+        .map(|board| board.nominal_length * board.nominal_width)
+        .sum()
+}
+
+/// Returns the largest fraction of any one board's area the solution leaves uncut, e.g., 0.9 for a
+/// board vended for a single small cut; 0 if it has no boards
+pub fn max_board_waste(solution: &[Board]) -> f32 {
+    solution
+        .iter()
+        .map(|board| 1f32 - board.fill())
+        .fold(0f32, f32::max)
+}
 
-    for cut in &model.cutlist {
-        for board in &model.boards {
-            if cut.width > board.width {
-                return false;
+/// Measures how scattered copies of the same part are across the solution's boards: the mean,
+/// over each part (cuts sharing a name), of the number of boards beyond the first holding it.
+/// 0 when every part is on a single board.
+pub fn part_spread(solution: &[Board]) -> f32 {
+    let mut boards_by_part: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (i, board) in solution.iter().enumerate() {
+        for cut in board.cuts() {
+            let boards = boards_by_part.entry(&cut.name).or_default();
+            if !boards.contains(&i) {
+                boards.push(i);
             }
         }
     }
+    if boards_by_part.is_empty() {
+        return 0f32;
+    }
+    let extra_boards: usize = boards_by_part.values().map(|boards| boards.len() - 1).sum();
+    extra_boards as f32 / boards_by_part.len() as f32
+}
+
+/// A board covered less than this is considered a spare when reserving a board
+pub const RESERVE_FILL_THRESHOLD: f32 = 0.25;
+
+/// Scores how well a solution leaves a spare board: zero unless its emptiest board is covered
+/// less than `RESERVE_FILL_THRESHOLD`, otherwise higher the emptier that board and the denser the
+/// rest of the boards are.
+pub fn reserve_score(solution: &[Board]) -> f32 {
+    if solution.len() < 2 {
+        return 0f32;
+    }
+    let fills: Vec<f32> = solution.iter().map(|board| board.fill()).collect();
+    let (emptiest, emptiest_fill) = fills
+        .iter()
+        .copied()
+        .enumerate()
+        .min_by(|a, b| total_cmp_f32(a.1, b.1))
+        .unwrap();
+    if emptiest_fill >= RESERVE_FILL_THRESHOLD {
+        return 0f32;
+    }
+    let rest_fill = fills
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != emptiest)
+        .map(|(_, fill)| fill)
+        .sum::<f32>()
+        / (fills.len() - 1) as f32;
+    (1f32 - emptiest_fill) * rest_fill
+}
+
+/// What the search optimizes for when ranking solutions
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Objective {
+    /// Favor dense solutions whose waste is concentrated in reusable offcuts
+    Waste,
+    /// Favor solutions leaving the fewest loose offcuts, falling back to `Waste` for ties
+    Offcuts,
+    /// Favor solutions keeping copies of each part on the fewest boards, per `part_spread`,
+    /// falling back to `Waste` for ties
+    Cluster,
+    /// Favor solutions which are quickest to cut, per `cutting_time` with the input's operation
+    /// times, falling back to `Waste` for ties
+    Time,
+    /// Favor solutions whose boards are least effort to handle, per `handling_effort`, falling
+    /// back to `Waste` for ties
+    Effort,
+    /// Favor solutions whose most wasteful board wastes least, per `max_board_waste`, so no one
+    /// board is badly used; falls back to `Waste` for ties
+    MaxWaste,
+}
+
+impl FromStr for Objective {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "waste" => Ok(Objective::Waste),
+            "offcuts" => Ok(Objective::Offcuts),
+            "cluster" => Ok(Objective::Cluster),
+            "time" => Ok(Objective::Time),
+            "effort" => Ok(Objective::Effort),
+            "max-waste" => Ok(Objective::MaxWaste),
+            _ => bail!(
+                "Unrecognized objective \"{}\", expected waste, offcuts, cluster, time, effort or max-waste",
+                s
+            ),
+        }
+    }
+}
+
+// orders solutions of the model per the options' objective, where the better solution is Less;
+// when reserving a board, solutions leaving a better spare come first. Ties go to fewer boards,
+// then fewer offcuts, then by fingerprint, so no two solutions laid out differently are equal.
+fn compare(a: &[Board], b: &[Board], model: &model::Input, options: &Options) -> Ordering {
+    compare_by_preference(a, b, model, options).then_with(|| tie_breaker(a).cmp(&tie_breaker(b)))
+}
+
+// the (board count, offcut count, fingerprint) ordering solutions `compare_by_preference` finds equal
+fn tie_breaker(solution: &[Board]) -> (usize, usize, String) {
+    (
+        solution.len(),
+        offcut_count(solution),
+        fingerprint(solution),
+    )
+}
+
+fn compare_by_preference(
+    a: &[Board],
+    b: &[Board],
+    model: &model::Input,
+    options: &Options,
+) -> Ordering {
+    let by_score = || total_cmp_f32(score(b), score(a));
+    let by_objective = || match options.objective {
+        Objective::Waste => by_score(),
+        Objective::Offcuts => offcut_count(a).cmp(&offcut_count(b)).then_with(by_score),
+        Objective::Cluster => total_cmp_f32(part_spread(a), part_spread(b)).then_with(by_score),
+        Objective::Time => total_cmp_f32(
+            cutting_time(a, &model.operation_times),
+            cutting_time(b, &model.operation_times),
+        )
+        .then_with(by_score),
+        Objective::Effort => {
+            total_cmp_f32(handling_effort(a), handling_effort(b)).then_with(by_score)
+        }
+        Objective::MaxWaste => {
+            total_cmp_f32(max_board_waste(a), max_board_waste(b)).then_with(by_score)
+        }
+    };
+    let by_preference = || {
+        if options.reserve_board {
+            total_cmp_f32(reserve_score(b), reserve_score(a)).then_with(by_objective)
+        } else {
+            by_objective()
+        }
+    };
+    by_preference()
+}
+
+/// Describes the solution's layout exactly: each board's id and size, and the cuts of each of its
+/// stacks, in order. Solutions share a fingerprint only if they're laid out alike.
+pub fn fingerprint(solution: &[Board]) -> String {
+    solution
+        .iter()
+        .map(|board| {
+            let stacks: Vec<String> = board
+                .stacks
+                .iter()
+                .map(|stack| {
+                    let cuts: Vec<String> = stack
+                        .cuts
+                        .iter()
+                        .map(|cut| format!("{} {}x{}", cut.id, cut.length, cut.width))
+                        .collect();
+                    format!("[{}]", cuts.join(", "))
+                })
+                .collect();
+            format!(
+                "{} {}x{}: {}",
+                board.id,
+                board.length,
+                board.width,
+                stacks.join(" ")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+// sorts solutions of the model with the best, per the options, at front, as `compare` would; each
+// solution's tie breaker is found once, as many solutions tie
+fn rank(solutions: &mut [Vec<Board>], model: &model::Input, options: &Options) {
+    let mut keyed: Vec<_> = solutions
+        .iter_mut()
+        .map(|solution| (tie_breaker(solution), std::mem::take(solution)))
+        .collect();
+    keyed.sort_by(|(tie_breaker_a, a), (tie_breaker_b, b)| {
+        compare_by_preference(a, b, model, options).then_with(|| tie_breaker_a.cmp(tie_breaker_b))
+    });
+    for (slot, (_, solution)) in solutions.iter_mut().zip(keyed) {
+        *slot = solution;
+    }
+}
 
-    true
+/// Returns true if the solution uses exactly the required number of each board which specifies an exact count
+fn meets_exact_counts(model: &model::Input, boards: &[Board]) -> bool {
+    model
+        .boards
+        .iter()
+        .all(|board_model| match board_model.exact_count {
+            Some(exact_count) => {
+                boards
+                    .iter()
+                    .filter(|board| board.id == board_model.id)
+                    .count()
+                    == exact_count
+            }
+            None => true,
+        })
 }
 
-struct CutRanges {
-    longest: f32,
-    shortest: f32,
-    widest: f32,
-    narrowest: f32,
+pub(crate) fn is_a_solution_possible(model: &model::Input) -> bool {
+    // if any cut in the cutlist is wider or longer than all available board stock,
+    // no solution is possible!
+
+    model.cutlist.iter().all(|cut| {
+        model.boards.iter().any(|board| {
+            cut.width <= board.usable_width()
+                && cut.length <= board.usable_length()
+                && board.accepts_thickness(cut.thickness)
+        })
+    })
 }
 
 /// Returns the index of the best board in `boards` to attempt to insert the cut, or None
-fn best_board_for_cut(boards: &[Board], cut: &Cut, cut_ranges: &CutRanges) -> Option<usize> {
+fn best_board_for_cut(boards: &[Board], cut: &Cut, options: &Options) -> Option<usize> {
     // naive approach - find first board that could accept this cut
     // TODO: Maybe try to put narrow cuts in narrow boards...
     for (i, board) in boards.iter().enumerate() {
-        if board.can_accept(cut) {
+        if board.can_accept(cut, options) {
             return Some(i);
         }
     }
@@ -236,18 +1217,55 @@ fn best_board_for_cut(boards: &[Board], cut: &Cut, cut_ranges: &CutRanges) -> Op
     None
 }
 
+/// Returns the model's board options which may be vended given the boards already in use, in order of preference
+fn candidate_board_models(
+    model: &model::Input,
+    boards: &[Board],
+    options: &Options,
+) -> Vec<model::Board> {
+    let mut board_models = model.boards.to_vec();
+    board_models.sort_by(|a, b| total_cmp_f32(a.width, b.width));
+    board_models.retain(|board_model| {
+        options.is_within_max_board_width(board_model)
+            && board_model.max_count.is_none_or(|max_count| {
+                boards.iter().filter(|b| b.id == board_model.id).count() < max_count
+            })
+    });
+
+    if let Some(max_board_types) = options.max_board_types {
+        // once the cap on distinct board types is reached, only those types may be vended
+        let mut vended_ids: Vec<&str> = boards.iter().map(|board| board.id.as_str()).collect();
+        vended_ids.sort_unstable();
+        vended_ids.dedup();
+        if vended_ids.len() >= max_board_types {
+            board_models.retain(|board_model| vended_ids.contains(&board_model.id.as_str()));
+        }
+    }
+
+    if options.balance {
+        // prefer the board types vended least so far, so stock is consumed evenly
+        let vended_count =
+            |board_model: &model::Board| boards.iter().filter(|b| b.id == board_model.id).count();
+        board_models.sort_by_key(|board_model| vended_count(board_model));
+    }
+
+    board_models
+}
+
 /// Vends a new board from the model's board options best suited for the specified cut
 fn vend_new_board_for_cut(
     model: &model::Input,
+    boards: &[Board],
     cut: &Cut,
-    cut_ranges: &CutRanges,
+    options: &Options,
 ) -> Option<Board> {
     // find first board wide enough for this cut
-    let mut board_models = model.boards.to_vec();
-    board_models.sort_by(|a, b| a.width.partial_cmp(&b.width).unwrap());
-
+    let board_models = candidate_board_models(model, boards, options);
     for board_model in &board_models {
-        if board_model.width > cut.width && board_model.length > cut.length {
+        if board_model.usable_width() >= cut.width
+            && board_model.usable_length() >= cut.length
+            && board_model.accepts_thickness(cut.thickness)
+        {
             return Some(board_model.into());
         }
     }
@@ -255,110 +1273,4349 @@ fn vend_new_board_for_cut(
     None
 }
 
-fn generate(model: &model::Input, cutlist: &[Cut], cut_ranges: &CutRanges) -> Option<Vec<Board>> {
-    let mut cutlist = cutlist.to_vec();
+/// A local improvement on a solution: for each board, least used first, tries to move all of its cuts
+/// onto the other boards' leftover space, dropping the board if they all fit. Boards holding a same
+/// board group, or of a type with an exact count, are left alone; pinned cuts only move to boards
+/// of the type they're pinned to. Returns the number of boards eliminated.
+pub fn try_eliminate_boards(
+    model: &model::Input,
+    boards: &mut Vec<Board>,
+    options: &Options,
+) -> usize {
+    let is_movable = |board: &Board| {
+        let exact = model
+            .boards
+            .iter()
+            .any(|b| b.id == board.id && b.exact_count.is_some());
+        let grouped = board.cuts().iter().any(|cut| {
+            is_sequential(model, &cut.name)
+                || model
+                    .same_board_groups
+                    .iter()
+                    .flatten()
+                    .any(|name| *name == cut.name)
+        });
+        !exact && !grouped
+    };
 
-    let mut boards: Vec<Board> = Vec::new();
+    let mut eliminated = 0;
+    let mut candidates: Vec<usize> = (0..boards.len()).collect();
+    candidates.sort_by(|a, b| {
+        let used_area =
+            |board: &Board| -> f32 { board.stacks.iter().map(|stack| stack.used_area()).sum() };
+        total_cmp_f32(used_area(&boards[*a]), used_area(&boards[*b]))
+    });
 
-    'cutlist: while let Some(cut) = cutlist.pop() {
-        // Check if there's a decent candidate board
-        if let Some(board_index) = best_board_for_cut(&boards, &cut, cut_ranges) {
-            if boards[board_index].accept(&cut) {
-                continue 'cutlist;
-            }
-        }
+    // indices shift as boards are removed, so track boards by their original index
+    let mut remaining: Vec<Option<Board>> = boards.drain(..).map(Some).collect();
+    for candidate in candidates {
+        let board = match &remaining[candidate] {
+            Some(board) if is_movable(board) => board.clone(),
+            _ => continue,
+        };
 
-        // See if any of the boards will accept this cut
-        for (i, board) in boards.iter_mut().enumerate() {
-            if board.accept(&cut) {
-                continue 'cutlist;
-            }
-        }
+        let mut others: Vec<(usize, Board)> = remaining
+            .iter()
+            .enumerate()
+            .filter(|(i, other)| *i != candidate && other.is_some())
+            .map(|(i, other)| (i, other.clone().unwrap()))
+            .collect();
+        let all_moved = board.cuts().iter().all(|cut| {
+            let pinned_to = model.pinned.get(&cut.name);
+            others.iter_mut().any(|(_, other)| {
+                pinned_to.is_none_or(|board_id| other.has_id(board_id))
+                    && other.accept(cut, options).is_placed()
+            })
+        });
 
-        // Looks like we need to vend a new board
-        if let Some(mut new_board) = vend_new_board_for_cut(model, &cut, cut_ranges) {
-            if new_board.accept(&cut) {
-                boards.push(new_board);
-                continue 'cutlist;
-            } else {
-                // This really should not happen as the `is_solution_possible` function should
-                // prevent this function from ever running if the model is insufficient to compute a solution.
-                return None;
+        if all_moved {
+            for (i, other) in others {
+                remaining[i] = Some(other);
             }
-        } else {
-            // This also should not occur for same reason as above - `is_solution_possible` should
-            // guard against this occurance.
-            return None;
+            remaining[candidate] = None;
+            eliminated += 1;
         }
     }
 
-    Some(boards)
+    boards.extend(remaining.into_iter().flatten());
+    eliminated
 }
 
-/// Atempts to find a best solution for computing the cutlist for the given model.
-pub fn compute(
+/// A hill climb from a solution: repeatedly moves a single cut onto another board, or swaps two cuts
+/// between boards, keeping the first change which the options' objective prefers, until no move or
+/// swap improves on the solution. A board left empty is dropped. Cuts in a same board group or a
+/// sequential set stay put, pinned cuts only move to boards of the type they're pinned to, and no
+/// change breaks an exact board count. Returns the number of changes kept.
+pub fn polish(model: &model::Input, solution: &mut Vec<Board>, options: &Options) -> usize {
+    let mut improvements = 0;
+    while let Some(better) = polish_step(model, solution, options) {
+        *solution = better;
+        improvements += 1;
+    }
+    improvements
+}
+
+// returns the first single move, or failing that swap, which improves on the solution, if any
+fn polish_step(model: &model::Input, solution: &[Board], options: &Options) -> Option<Vec<Board>> {
+    let is_movable = |cut: &Cut| {
+        !is_sequential(model, &cut.name)
+            && !model
+                .same_board_groups
+                .iter()
+                .flatten()
+                .any(|name| *name == cut.name)
+    };
+    let may_take = |board: &Board, cut: &Cut| {
+        model
+            .pinned
+            .get(&cut.name)
+            .is_none_or(|board_id| board.has_id(board_id))
+    };
+    let is_improvement = |candidate: &mut Vec<Board>| {
+        candidate.retain(|board| !board.stacks.is_empty());
+        meets_exact_counts(model, candidate)
+            && compare_by_preference(candidate, solution, model, options) == Ordering::Less
+    };
+
+    // (board, stack, cut) indices of each cut free to move
+    let mut positions = Vec::new();
+    for (b, board) in solution.iter().enumerate() {
+        for (s, stack) in board.stacks.iter().enumerate() {
+            for (c, cut) in stack.cuts.iter().enumerate() {
+                if is_movable(cut) {
+                    positions.push((b, s, c));
+                }
+            }
+        }
+    }
+    let cut_at = |(b, s, c): (usize, usize, usize)| &solution[b].stacks[s].cuts[c];
+
+    for &(b, s, c) in &positions {
+        for other in 0..solution.len() {
+            if other == b || !may_take(&solution[other], cut_at((b, s, c))) {
+                continue;
+            }
+            let mut candidate = solution.to_vec();
+            let cut = candidate[b].remove(s, c);
+            if candidate[other].accept(&cut, options).is_placed() && is_improvement(&mut candidate)
+            {
+                return Some(candidate);
+            }
+        }
+    }
+
+    for (i, &(b, s, c)) in positions.iter().enumerate() {
+        for &(other_b, other_s, other_c) in &positions[i + 1..] {
+            let (cut, other_cut) = (cut_at((b, s, c)), cut_at((other_b, other_s, other_c)));
+            if other_b == b
+                || (cut.length == other_cut.length && cut.width == other_cut.width)
+                || !may_take(&solution[other_b], cut)
+                || !may_take(&solution[b], other_cut)
+            {
+                continue;
+            }
+            let mut candidate = solution.to_vec();
+            let cut = candidate[b].remove(s, c);
+            let other_cut = candidate[other_b].remove(other_s, other_c);
+            if candidate[other_b].accept(&cut, options).is_placed()
+                && candidate[b].accept(&other_cut, options).is_placed()
+                && is_improvement(&mut candidate)
+            {
+                return Some(candidate);
+            }
+        }
+    }
+
+    None
+}
+
+// places a pinned cut on a board with the specified id, vending a new one if those in use are full
+fn place_pinned(
     model: &model::Input,
-    attempts: usize,
-    result_count: usize,
-) -> Option<Vec<Vec<Board>>> {
-    if !is_a_solution_possible(model) {
+    boards: &mut Vec<Board>,
+    cut: &Cut,
+    board_id: &str,
+    options: &Options,
+) -> bool {
+    for board in boards.iter_mut().filter(|board| board.has_id(board_id)) {
+        if board.accept(cut, options).is_placed() {
+            return true;
+        }
+    }
+
+    // vend through the same route as unpinned cuts, so the caps on board types still apply
+    if let Some(board_model) = candidate_board_models(model, boards, options)
+        .into_iter()
+        .find(|board| board.has_id(board_id))
+    {
+        let mut board: Board = (&board_model).into();
+        if board.accept(cut, options).is_placed() {
+            boards.push(board);
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Attempts to place every cut in `group` on a single board, trying the boards already in use before
+/// vending a new one. Returns false if no single board can hold the whole group.
+/// The placement order of the cuts in the group is recorded starting from `first_placement`.
+fn place_group(
+    model: &model::Input,
+    boards: &mut Vec<Board>,
+    group: &[Cut],
+    first_placement: usize,
+    options: &Options,
+) -> bool {
+    // place the longest cuts first, they're the hardest to fit
+    let mut group = group.to_vec();
+    group.sort_by(|a, b| total_cmp_f32(b.length, a.length));
+    for (i, cut) in group.iter_mut().enumerate() {
+        cut.placement = first_placement + i;
+    }
+
+    for board in boards.iter_mut() {
+        let mut candidate = board.clone();
+        if group
+            .iter()
+            .all(|cut| candidate.accept(cut, options).is_placed())
+        {
+            *board = candidate;
+            return true;
+        }
+    }
+
+    for board_model in &candidate_board_models(model, boards, options) {
+        let mut candidate: Board = board_model.into();
+        if group
+            .iter()
+            .all(|cut| candidate.accept(cut, options).is_placed())
+        {
+            boards.push(candidate);
+            return true;
+        }
+    }
+
+    false
+}
+
+// returns true if the named cut of the model is a sequential set
+fn is_sequential(model: &model::Input, name: &str) -> bool {
+    model
+        .cutlist
+        .iter()
+        .any(|cut_model| cut_model.sequential && cut_model.name == name)
+}
+
+/// Attempts to place the pieces of a sequential set side by side in one stack, per
+/// `Board::accept_sequence`, trying the boards already in use before vending a new one; if the set
+/// is pinned, only boards it's pinned to are used. Returns false if no board can hold the set.
+fn place_sequence(
+    model: &model::Input,
+    boards: &mut Vec<Board>,
+    set: &[Cut],
+    first_placement: usize,
+    options: &Options,
+) -> bool {
+    let mut set = set.to_vec();
+    for (i, cut) in set.iter_mut().enumerate() {
+        cut.placement = first_placement + i;
+    }
+    let pinned_to = set.first().and_then(|cut| model.pinned.get(&cut.name));
+
+    for board in boards
+        .iter_mut()
+        .filter(|board| pinned_to.is_none_or(|board_id| board.has_id(board_id)))
+    {
+        if board.accept_sequence(&set, options).is_placed() {
+            return true;
+        }
+    }
+
+    for board_model in &candidate_board_models(model, boards, options) {
+        if pinned_to.is_some_and(|board_id| !board_model.has_id(board_id)) {
+            continue;
+        }
+        let mut candidate: Board = board_model.into();
+        if candidate.accept_sequence(&set, options).is_placed() {
+            boards.push(candidate);
+            return true;
+        }
+    }
+
+    false
+}
+
+// places the cutlist around the cuts already on `kept`, e.g., those kept from a warm start
+fn generate(
+    model: &model::Input,
+    kept: &[Board],
+    cutlist: &[Cut],
+    options: &Options,
+) -> Option<Vec<Board>> {
+    let mut cutlist = cutlist.to_vec();
+
+    let mut boards: Vec<Board> = kept.to_vec();
+    let mut placement = boards.iter().map(|board| board.cuts().len()).sum();
+
+    // Sequential sets are placed first, as they need the most room in a single stack
+    for cut_model in model
+        .cutlist
+        .iter()
+        .filter(|cut_model| cut_model.sequential)
+    {
+        let (set, remainder): (Vec<Cut>, Vec<Cut>) = cutlist
+            .into_iter()
+            .partition(|cut| cut.name == cut_model.name);
+        cutlist = remainder;
+        if set.is_empty() {
+            // kept in place from a warm start
+            continue;
+        }
+        if !place_sequence(model, &mut boards, &set, placement, options) {
+            return None;
+        }
+        placement += set.len();
+    }
+
+    // Pinned cuts are placed on their designated boards before the rest
+    for (name, board_id) in &model.pinned {
+        let (pinned, remainder): (Vec<Cut>, Vec<Cut>) =
+            cutlist.into_iter().partition(|cut| &cut.name == name);
+        cutlist = remainder;
+        for mut cut in pinned {
+            cut.placement = placement;
+            placement += 1;
+            if !place_pinned(model, &mut boards, &cut, board_id, options) {
+                return None;
+            }
+        }
+    }
+
+    // Cuts in a same board group are placed atomically before the rest of the cutlist is scattered
+    for group_names in &model.same_board_groups {
+        let (group, remainder): (Vec<Cut>, Vec<Cut>) = cutlist
+            .into_iter()
+            .partition(|cut| group_names.contains(&cut.name));
+        cutlist = remainder;
+        if !place_group(model, &mut boards, &group, placement, options) {
+            return None;
+        }
+        placement += group.len();
+    }
+
+    'cutlist: while let Some(mut cut) = cutlist.pop() {
+        cut.placement = placement;
+        placement += 1;
+
+        // Check if there's a decent candidate board
+        if let Some(board_index) = best_board_for_cut(&boards, &cut, options) {
+            let outcome = boards[board_index].accept(&cut, options);
+            if outcome.is_placed() {
+                debug!(
+                    "Placed {} on board {} ({}): {:?}",
+                    cut.id, board_index, boards[board_index].id, outcome
+                );
+                continue 'cutlist;
+            }
+        }
+
+        // See if any of the boards will accept this cut
+        for (i, board) in boards.iter_mut().enumerate() {
+            let outcome = board.accept(&cut, options);
+            if outcome.is_placed() {
+                debug!(
+                    "Placed {} on board {} ({}): {:?}",
+                    cut.id, i, board.id, outcome
+                );
+                continue 'cutlist;
+            }
+        }
+
+        // Looks like we need to vend a new board
+        if let Some(mut new_board) = vend_new_board_for_cut(model, &boards, &cut, options) {
+            if new_board.accept(&cut, options).is_placed() {
+                debug!(
+                    "Placed {} on new board {} ({})",
+                    cut.id,
+                    boards.len(),
+                    new_board.id
+                );
+                boards.push(new_board);
+                continue 'cutlist;
+            } else {
+                // This really should not happen as the `is_solution_possible` function should
+                // prevent this function from ever running if the model is insufficient to compute a solution.
+                return None;
+            }
+        } else {
+            // This also should not occur for same reason as above - `is_solution_possible` should
+            // guard against this occurance.
+            return None;
+        }
+    }
+
+    // moving kept cuts to other boards would undo the warm start
+    if kept.is_empty() {
+        let eliminated = try_eliminate_boards(model, &mut boards, options);
+        if eliminated > 0 {
+            debug!("Eliminated {} boards by moving their cuts", eliminated);
+        }
+    }
+
+    // Fill cuts are made from what's left, as many as the boards in use will take
+    for cut_model in &model.cutlist {
+        if cut_model.count != model::CountSpec::Fill {
+            continue;
+        }
+        for index in 1.. {
+            let mut cut = Cut::from(cut_model, model.crosscut_kerf(), model.rip_kerf());
+            cut.isolated = model.isolate.contains(&cut_model.name);
+            if options.number_cuts {
+                cut.id = format!("{} #{}", cut.id, index);
+            }
+            cut.placement = placement;
+            if !boards
+                .iter_mut()
+                .any(|board| board.accept(&cut, options).is_placed())
+            {
+                break;
+            }
+            placement += 1;
+        }
+    }
+
+    Some(boards)
+}
+
+/// Returns the estimated weight in pounds of the stock the solution uses, from each board's
+/// board-feet and the density of its species. Returns None if the model specifies no densities;
+/// boards without a density don't contribute.
+pub fn solution_weight(model: &model::Input, solution: &[Board]) -> Option<f32> {
+    if model.densities.is_empty() {
         return None;
     }
+    Some(
+        solution
+            .iter()
+            .filter_map(|board| {
+                model
+                    .densities
+                    .get(&board.id)
+                    .map(|density| board.board_feet() * density)
+            })
+            .sum(),
+    )
+}
 
-    // Create a vector of our required Cuts, sorted from longest to shortest
-    let (mut cutlist, cut_ranges) = {
-        let mut cutlist: Vec<Cut> = Vec::new();
-        let mut longest: f32 = 0f32;
-        let mut widest: f32 = 0f32;
-        let mut shortest: f32 = f32::MAX;
-        let mut narrowest: f32 = f32::MAX;
-        for cut_model in &model.cutlist {
-            for _ in 0..cut_model.count {
-                longest = longest.max(cut_model.length);
-                widest = widest.max(cut_model.width);
-                shortest = shortest.min(cut_model.length);
-                narrowest = narrowest.min(cut_model.width);
-                cutlist.push(Cut::from(cut_model, model.spacing));
-            }
-        }
-
-        (
-            cutlist,
-            CutRanges {
-                longest,
-                shortest,
-                widest,
-                narrowest,
-            },
-        )
+/// A row of a flat cut table, locating a cut on its board
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct CutRow {
+    /// Index of the board in the solution
+    pub board: usize,
+    pub board_id: String,
+    pub cut: String,
+    /// Name of the model cut, which unlike `cut` is never numbered
+    pub name: String,
+    /// Offset of the cut along the board's length
+    pub x: f32,
+    /// Offset of the cut across the board's width
+    pub y: f32,
+    pub length: f32,
+    pub width: f32,
+    /// True if the cut's length runs across the board; only sheet stock rotates cuts
+    pub rotated: bool,
+    /// True if both of the cut's faces get marked, with the face up as laid out marked first
+    pub double_sided: bool,
+}
+
+/// A cut as placed in a solution, with the board it's on and its offset along the board's length
+/// (`x`) and across its width (`y`)
+#[derive(Clone, Copy, Debug)]
+pub struct Placement<'a> {
+    pub board: &'a Board,
+    pub cut: &'a Cut,
+    pub x: f32,
+    pub y: f32,
+}
+
+/// Returns every cut placed in the solution, board by board, in the order of `Board::placements`
+pub fn placements(solution: &[Board]) -> impl Iterator<Item = Placement<'_>> {
+    solution.iter().flat_map(Board::placements)
+}
+
+/// Checks the solution for placements which break a cut's constraints, reporting every grained
+/// cut laid out cross-grain, e.g., on a board edited outside of `Board::accept`
+pub fn validate_solution(solution: &[Board]) -> Result<()> {
+    let cross_grain: Vec<String> = placements(solution)
+        .filter(|placement| placement.cut.grain && placement.cut.rotated)
+        .map(|placement| {
+            format!(
+                "\"{}\" on board \"{}\"",
+                placement.cut.id, placement.board.id
+            )
+        })
+        .collect();
+    if !cross_grain.is_empty() {
+        bail!(
+            "Grained cuts placed cross-grain: {}",
+            cross_grain.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Returns a row for every cut in the solution, ordered by board, then top to bottom, then left to right
+pub fn flat_cut_table(solution: &[Board]) -> Vec<CutRow> {
+    let mut rows = Vec::new();
+    for (board_index, board) in solution.iter().enumerate() {
+        let mut board_rows: Vec<CutRow> = board
+            .placements()
+            .map(|Placement { cut, x, y, .. }| CutRow {
+                board: board_index,
+                board_id: board.id.clone(),
+                cut: cut.id.clone(),
+                name: cut.name.clone(),
+                x,
+                y,
+                length: cut.length,
+                width: cut.width,
+                rotated: cut.rotated,
+                double_sided: cut.double_sided,
+            })
+            .collect();
+        board_rows.sort_by(|a, b| total_cmp_f32(a.y, b.y).then_with(|| total_cmp_f32(a.x, b.x)));
+        rows.append(&mut board_rows);
+    }
+    rows
+}
+
+/// A rectangle of a sheet, in the sheet's coordinates
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Rect {
+    pub x: f32,
+    pub y: f32,
+    pub length: f32,
+    pub width: f32,
+}
+
+impl From<&model::Defect> for Rect {
+    fn from(defect: &model::Defect) -> Self {
+        Rect {
+            x: defect.x,
+            y: defect.y,
+            length: defect.length,
+            width: defect.width,
+        }
+    }
+}
+
+impl Rect {
+    pub fn overlaps(&self, other: &Rect) -> bool {
+        self.x < other.x + other.length
+            && other.x < self.x + self.length
+            && self.y < other.y + other.width
+            && other.y < self.y + self.width
+    }
+
+    fn is_empty(&self) -> bool {
+        self.length <= 0f32 || self.width <= 0f32
+    }
+}
+
+/// A cut placed on a sheet by a `SheetPacker`, with its length along the sheet's length
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SheetPlacement {
+    pub cut: Cut,
+    pub x: f32,
+    pub y: f32,
+    /// If true, the cut was turned a quarter turn to fit, which sheet stock allows unless the cut
+    /// is grained
+    pub rotated: bool,
+}
+
+impl SheetPlacement {
+    pub fn rect(&self) -> Rect {
+        Rect {
+            x: self.x,
+            y: self.y,
+            length: self.cut.length,
+            width: self.cut.width,
+        }
+    }
+}
+
+/// Packs cuts onto a sheet of plywood or other sheet stock in 2D with a guillotine algorithm,
+/// rather than in stacks. Each cut goes in the free rectangle it fits most snugly, in either
+/// orientation, and that rectangle is split in two along the shorter leftover side, so the layout
+/// can always be cut with edge to edge passes on a table or panel saw. Defects are cut around.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SheetPacker {
+    pub id: String,
+    pub length: f32,
+    pub width: f32,
+    pub placements: Vec<SheetPlacement>,
+    /// Regions of the sheet no cut may overlap, e.g., a split in the veneer
+    pub defects: Vec<Rect>,
+    free: Vec<Rect>,
+}
+
+impl SheetPacker {
+    pub fn new(length: f32, width: f32, id: &str) -> Self {
+        SheetPacker {
+            id: id.to_owned(),
+            length,
+            width,
+            placements: Vec::new(),
+            defects: Vec::new(),
+            free: vec![Rect {
+                x: 0f32,
+                y: 0f32,
+                length,
+                width,
+            }],
+        }
+    }
+
+    /// Marks the defects on the sheet, splitting each free rectangle a defect overlaps into those
+    /// around it: the full width of the rectangle before and after the defect along the sheet's
+    /// length, and the spans beside the defect across its width.
+    pub fn with_defects(mut self, defects: &[Rect]) -> Self {
+        for defect in defects {
+            let mut free = Vec::new();
+            for rect in self.free.drain(..) {
+                if !rect.overlaps(defect) {
+                    free.push(rect);
+                    continue;
+                }
+                let start = defect.x.max(rect.x);
+                let end = (defect.x + defect.length).min(rect.x + rect.length);
+                let (bottom, top) = (rect.y, rect.y + rect.width);
+                let around = [
+                    Rect {
+                        length: start - rect.x,
+                        ..rect
+                    },
+                    Rect {
+                        x: end,
+                        length: rect.x + rect.length - end,
+                        ..rect
+                    },
+                    Rect {
+                        x: start,
+                        y: bottom,
+                        length: end - start,
+                        width: defect.y.max(bottom) - bottom,
+                    },
+                    Rect {
+                        x: start,
+                        y: (defect.y + defect.width).min(top),
+                        length: end - start,
+                        width: top - (defect.y + defect.width).min(top),
+                    },
+                ];
+                free.extend(around.iter().filter(|rect| !rect.is_empty()));
+            }
+            self.free = free;
+            self.defects.push(*defect);
+        }
+        self
+    }
+
+    /// Places the cut on the sheet, returning false if there's no room for it in either orientation
+    pub fn insert(&mut self, cut: &Cut) -> bool {
+        self.insert_oriented(cut, None)
+    }
+
+    /// Like `insert`, but if `rotated` is set, only places the cut rotated (or not) as it says
+    pub fn insert_oriented(&mut self, cut: &Cut, rotated: Option<bool>) -> bool {
+        let orientations: &[bool] = match rotated {
+            Some(false) => &[false],
+            Some(true) => &[true],
+            None => &[false, true],
+        };
+        // the (free rect index, rotated, fit) of the snuggest fit, by shorter leftover side
+        let mut best: Option<(usize, bool, f32)> = None;
+        for (i, free) in self.free.iter().enumerate() {
+            for &rotated in orientations {
+                if rotated && cut.grain {
+                    // a grained cut keeps its length along the sheet's, with the face grain
+                    continue;
+                }
+                let (length, width) = if rotated {
+                    (cut.width, cut.length)
+                } else {
+                    (cut.length, cut.width)
+                };
+                if length <= free.length && width <= free.width {
+                    let fit = (free.length - length).min(free.width - width);
+                    if best.is_none_or(|(_, _, best_fit)| fit < best_fit) {
+                        best = Some((i, rotated, fit));
+                    }
+                }
+            }
+        }
+
+        let (i, rotated) = match best {
+            Some((i, rotated, _)) => (i, rotated),
+            None => return false,
+        };
+        let free = self.free.swap_remove(i);
+        let cut = if rotated {
+            cut.clone().rotate()
+        } else {
+            cut.clone()
+        };
+
+        // split along the shorter leftover side, keeping the longer leftover whole
+        let (beside, above) = if free.length - cut.length < free.width - cut.width {
+            (
+                Rect {
+                    x: free.x + cut.length,
+                    y: free.y,
+                    length: free.length - cut.length,
+                    width: cut.width,
+                },
+                Rect {
+                    x: free.x,
+                    y: free.y + cut.width,
+                    length: free.length,
+                    width: free.width - cut.width,
+                },
+            )
+        } else {
+            (
+                Rect {
+                    x: free.x + cut.length,
+                    y: free.y,
+                    length: free.length - cut.length,
+                    width: free.width,
+                },
+                Rect {
+                    x: free.x,
+                    y: free.y + cut.width,
+                    length: cut.length,
+                    width: free.width - cut.width,
+                },
+            )
+        };
+        self.free.extend(
+            vec![beside, above]
+                .into_iter()
+                .filter(|rect| !rect.is_empty()),
+        );
+
+        self.placements.push(SheetPlacement {
+            cut,
+            x: free.x,
+            y: free.y,
+            rotated,
+        });
+        true
+    }
+
+    /// Returns the area of the sheet covered by cuts
+    pub fn used_area(&self) -> f32 {
+        self.placements
+            .iter()
+            .map(|placement| placement.cut.length * placement.cut.width)
+            .sum()
+    }
+}
+
+/// Packs the model's cutlist onto its sheet stock with `SheetPacker`, largest cuts first, opening
+/// a new sheet whenever a cut fits none of those opened so far. New sheets are taken from the
+/// first sheet board type the cut fits; a board's exact count is how many of it must be opened.
+/// Fill cuts aren't supported on sheets.
+pub fn pack_sheets(model: &model::Input, options: &Options) -> Result<Vec<SheetPacker>> {
+    if let Some(cut_model) = model
+        .cutlist
+        .iter()
+        .find(|cut_model| cut_model.count == model::CountSpec::Fill)
+    {
+        bail!(
+            "Cut \"{}\" fills leftover stock, which sheet stock doesn't support",
+            cut_model.name
+        );
+    }
+    let mut cutlist = expand_cutlist(model, options);
+    cutlist.sort_by(|a, b| total_cmp_f32(b.length * b.width, a.length * a.width));
+
+    let mut sheets: Vec<SheetPacker> = Vec::new();
+    let accepts_thickness = |id: &str, cut: &Cut| {
+        model
+            .boards
+            .iter()
+            .any(|board| board.id == id && board.accepts_thickness(cut.thickness))
+    };
+    // the orientation (rotated or not) of the first copy placed of each uniformly oriented cut
+    let mut orientations: HashMap<String, bool> = HashMap::new();
+    let mut insert = |sheet: &mut SheetPacker, cut: &Cut| {
+        if !cut.uniform_orientation {
+            return sheet.insert(cut);
+        }
+        let inserted = sheet.insert_oriented(cut, orientations.get(&cut.name).copied());
+        if inserted {
+            let rotated = sheet.placements.last().unwrap().rotated;
+            orientations.entry(cut.name.clone()).or_insert(rotated);
+        }
+        inserted
     };
+    'cuts: for cut in &cutlist {
+        if sheets
+            .iter_mut()
+            .any(|sheet| accepts_thickness(&sheet.id, cut) && insert(sheet, cut))
+        {
+            continue;
+        }
+        for board_model in model
+            .boards
+            .iter()
+            .filter(|board| board.sheet && board.accepts_thickness(cut.thickness))
+        {
+            let opened = sheets
+                .iter()
+                .filter(|sheet| sheet.id == board_model.id)
+                .count();
+            if board_model
+                .exact_count
+                .or(board_model.max_count)
+                .is_some_and(|limit| opened >= limit)
+            {
+                continue;
+            }
+            let defects: Vec<Rect> = board_model.defects.iter().map(Rect::from).collect();
+            let mut sheet = SheetPacker::new(
+                board_model.usable_length(),
+                board_model.usable_width(),
+                &board_model.id,
+            )
+            .with_defects(&defects);
+            if insert(&mut sheet, cut) {
+                sheets.push(sheet);
+                continue 'cuts;
+            }
+        }
+        bail!(
+            "{} ({} by {}) doesn't fit on any available sheet",
+            cut.id,
+            cut.length,
+            cut.width
+        );
+    }
+
+    // as a single pass, the packing either meets the exact counts or there's no solution
+    for board_model in &model.boards {
+        if let Some(exact_count) = board_model.exact_count {
+            let opened = sheets
+                .iter()
+                .filter(|sheet| sheet.id == board_model.id)
+                .count();
+            if opened != exact_count {
+                bail!(
+                    "Sheet \"{}\" must be used exactly {} times, but the cutlist needs {}",
+                    board_model.id,
+                    exact_count,
+                    opened
+                );
+            }
+        }
+    }
+    Ok(sheets)
+}
 
-    let mut results = Vec::new();
+/// Rounds a position to the nearest multiple of `increment`, e.g., 1/16, for marking by hand
+pub fn snap(value: f32, increment: f32) -> f32 {
+    (value / increment).round() * increment
+}
 
-    if attempts == 0 {
-        cutlist.sort_by(|a, b| b.length.partial_cmp(&a.length).unwrap());
-        if let Some(result) = generate(model, &cutlist, &cut_ranges) {
-            results.push(result);
+/// Returns the ids of cuts which would overlap another cut on their board if their positions in
+/// `flat_cut_table` were snapped to `increment`. Snapping is only for display; solutions keep
+/// their exact positions.
+pub fn snap_overlaps(solution: &[Board], increment: f32) -> Vec<String> {
+    let rows = flat_cut_table(solution);
+    let rect = |row: &CutRow| Rect {
+        x: snap(row.x, increment),
+        y: snap(row.y, increment),
+        length: row.length,
+        width: row.width,
+    };
+    let mut overlapping = Vec::new();
+    for (i, a) in rows.iter().enumerate() {
+        let overlaps = rows
+            .iter()
+            .enumerate()
+            .any(|(j, b)| i != j && a.board == b.board && rect(a).overlaps(&rect(b)));
+        if overlaps {
+            overlapping.push(a.cut.clone());
         }
-    } else {
-        // shuffle approach
-        let mut rng = Pcg64::seed_from_u64(12345);
+    }
+    overlapping
+}
+
+/// Returns the indices of the solution's boards which could still take a cut of this length and
+/// width, grown by the model's kerfs, into their leftover space as `Board::accept` would place it,
+/// e.g., to answer whether one more part can be squeezed in
+pub fn boards_with_room_for(
+    model: &model::Input,
+    solution: &[Board],
+    (length, width): (f32, f32),
+    options: &Options,
+) -> Vec<usize> {
+    let probe = Cut {
+        length: length + model.crosscut_kerf(),
+        width: width + model.rip_kerf(),
+        id: "Probe".to_owned(),
+        name: "Probe".to_owned(),
+        placement: 0,
+        outcome: None,
+        shape: None,
+        double_sided: false,
+        isolated: false,
+        thickness: None,
+        uniform_orientation: false,
+        grain: false,
+        rotated: false,
+    };
+    solution
+        .iter()
+        .enumerate()
+        .filter(|(_, board)| board.can_accept(&probe, options))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Returns the names of the model's cuts which are too short to crosscut safely under
+/// `options.min_crosscut_width`; `compute` leaves them out of its solutions.
+pub fn orphaned_cuts(model: &model::Input, options: &Options) -> Vec<String> {
+    model
+        .cutlist
+        .iter()
+        .filter(|cut_model| {
+            !options.is_safe_to_crosscut(&Cut::from(
+                cut_model,
+                model.crosscut_kerf(),
+                model.rip_kerf(),
+            ))
+        })
+        .map(|cut_model| cut_model.name.clone())
+        .collect()
+}
+
+/// For a model whose stock runs out before its cutlist is placed, e.g., a lot or scrap of which
+/// each board may be used once, returns the boards of each type which, added to the stock, would
+/// place all of the cuts, with how many of each. Cuts are placed greedily, longest first, and those
+/// left over packed onto as few more boards as possible. Empty if the stock suffices; cuts larger
+/// than every board type have no suggestion. Pinned cuts and same board groups are ignored.
+pub fn suggest_additional_stock(
+    model: &model::Input,
+    options: &Options,
+) -> Vec<(model::Board, usize)> {
+    let mut cutlist = expand_cutlist(model, options);
+    // `pop` takes from the back, so the longest cuts go first
+    cutlist.sort_by(|a, b| total_cmp_f32(a.length, b.length));
+
+    // places the cut on one of the boards, vending a new one if need be; false if none can take it
+    let place = |model: &model::Input, boards: &mut Vec<Board>, cut: &Cut| -> bool {
+        if boards
+            .iter_mut()
+            .any(|board| board.accept(cut, options).is_placed())
+        {
+            return true;
+        }
+        if let Some(mut board) = vend_new_board_for_cut(model, boards, cut, options) {
+            if board.accept(cut, options).is_placed() {
+                boards.push(board);
+                return true;
+            }
+        }
+        false
+    };
+
+    let mut boards = Vec::new();
+    let mut orphans = Vec::new();
+    while let Some(cut) = cutlist.pop() {
+        if !place(model, &mut boards, &cut) {
+            orphans.push(cut);
+        }
+    }
+
+    // the extra boards may be of any type, as many as needed
+    let unlimited = model::Input {
+        boards: model
+            .boards
+            .iter()
+            .map(|board| model::Board {
+                max_count: None,
+                ..board.clone()
+            })
+            .collect(),
+        ..model.clone()
+    };
+    let mut extra_boards = Vec::new();
+    for cut in &orphans {
+        place(&unlimited, &mut extra_boards, cut);
+    }
 
-        for attempt in 0..attempts {
-            cutlist.shuffle(&mut rng);
-            if let Some(result) = generate(model, &cutlist, &cut_ranges) {
-                results.push(result);
+    let mut suggestions: Vec<(model::Board, usize)> = Vec::new();
+    for board in &extra_boards {
+        match suggestions
+            .iter_mut()
+            .find(|(board_model, _)| board_model.id == board.id)
+        {
+            Some((_, count)) => *count += 1,
+            None => {
+                if let Some(board_model) = unlimited.boards.iter().find(|b| b.id == board.id) {
+                    suggestions.push((board_model.clone(), 1));
+                }
             }
         }
     }
+    suggestions
+}
 
-    if !results.is_empty() {
-        // sort results by score with best at front, and then return the desired count
-        results.sort_by(|a, b| score(b).partial_cmp(&score(a)).unwrap());
-        let result_count = result_count.min(results.len());
-        println!("Found {} viable solutions", result_count);
-        Some(results[0..result_count].to_vec())
-    } else {
-        None
+/// Returns the number of pieces made of each of the model's fill cuts in the solution
+pub fn fill_counts(model: &model::Input, solution: &[Board]) -> Vec<(String, usize)> {
+    model
+        .cutlist
+        .iter()
+        .filter(|cut_model| cut_model.count == model::CountSpec::Fill)
+        .map(|cut_model| {
+            let count = solution
+                .iter()
+                .flat_map(|board| board.cuts())
+                .filter(|cut| cut.name == cut_model.name)
+                .count();
+            (cut_model.name.clone(), count)
+        })
+        .collect()
+}
+
+/// Offcuts at least this (length, width) are worth keeping, by default
+pub const MIN_USABLE_OFFCUT: (f32, f32) = (12f32, 2f32);
+
+/// Returns a yaml `scrap:` list of the solution's offcuts at least `min_size` (length, width), as
+/// board specs which may be pasted into a future input as stock. Each is marked dressed, as it's
+/// cut from a board's usable, jointed area, and named for the board it came from, numbered e.g.,
+/// "A offcut 1", "A offcut 2", as each scrap entry may be used just once per id.
+pub fn offcut_inventory(solution: &[Board], min_size: (f32, f32)) -> String {
+    // rounded, so float noise from summing stacks doesn't reach the specs
+    let dimension = |value: f32| (value * 1000f32).round() / 1000f32;
+    let mut inventory = String::from("scrap:\n");
+    let mut offcut_counts: HashMap<String, usize> = HashMap::new();
+    for board in solution {
+        // characters which would be read as the spec's count or overage
+        let id: String = board
+            .id
+            .chars()
+            .map(|c| if c == '=' || c == '+' { '-' } else { c })
+            .collect();
+        for (length, width) in board.offcuts() {
+            if length >= min_size.0 && width >= min_size.1 {
+                let count = offcut_counts.entry(id.clone()).or_insert(0);
+                *count += 1;
+                let spec = format!(
+                    "{}x{}:{} offcut {}!",
+                    dimension(length),
+                    dimension(width),
+                    id,
+                    count
+                );
+                inventory += &format!(
+                    "  - \"{}\"\n",
+                    spec.replace('\\', "\\\\").replace('"', "\\\"")
+                );
+            }
+        }
+    }
+    inventory
+}
+
+/// Options controlling how `compute` searches for solutions.
+#[derive(Clone, Debug)]
+pub struct Options {
+    /// Number of shuffled attempts to make; 0 means a single pass with cuts sorted longest to shortest.
+    pub attempts: usize,
+    /// Maximum number of solutions to return.
+    pub result_count: usize,
+    /// If true, cuts sharing a name are numbered (e.g., "Shelf #1", "Shelf #2") so each piece can be tracked.
+    pub number_cuts: bool,
+    /// If true, a stack only ever holds copies of a single part, simplifying execution at the saw.
+    pub uniform_rips: bool,
+    /// If true, new boards are vended from the least used board types, distributing cuts across the available stock.
+    pub balance: bool,
+    /// If set, solutions use at most this many distinct board types.
+    pub max_board_types: Option<usize>,
+    /// If set, only boards at most this wide are used, e.g., the capacity of the jointer and planer.
+    pub max_board_width: Option<f32>,
+    /// If set, the search stops once this much time has elapsed, even if attempts remain.
+    pub timeout: Option<Duration>,
+    /// What to optimize for when ranking solutions.
+    pub objective: Objective,
+    /// Seeds the shuffling of the cutlist, so a search is reproducible.
+    pub seed: u64,
+    /// If set, cuts shorter than this are too dangerous to crosscut, and are left out of solutions.
+    pub min_crosscut_width: Option<f32>,
+    /// If true, solutions leaving one board mostly unused, as a spare for mistakes, are preferred.
+    pub reserve_board: bool,
+    /// A cut only joins an existing stack whose length differs from its own by at most this
+    /// fraction of the cut's length; otherwise it opens a new stack.
+    pub stack_fit_tolerance: f32,
+    /// If true, `attempts` is ignored and cuts are placed once, in cutlist order, after any pinned
+    /// and grouped cuts; solutions aren't tidied, so boards show cuts in the order they were placed.
+    pub preserve_order: bool,
+    /// If set, a previous solution whose cuts, where unchanged, keep their places; only the rest of
+    /// the cutlist is searched for. See `warm_start`.
+    pub warm_start: Option<Vec<Board>>,
+    /// If true, each solution returned is improved by `polish` before they're ranked for the last
+    /// time; ignored with a warm start, whose cuts keep their places.
+    pub polish: bool,
+    /// If true, `attempts` and `result_count` are ignored: cuts are placed just once, largest area
+    /// first, and `compute` returns that one solution without ranking, polished if `polish` is set.
+    /// For interactive tools, where a good layout now beats the best one later.
+    pub fast: bool,
+    /// If true, each attempt places cuts largest area first rather than in a random order, the seed
+    /// only shuffling cuts of equal area among themselves; an ensemble of deterministic layouts
+    /// which explores the ties between them
+    pub shuffle_ties: bool,
+    /// If true, every cut's copies share one orientation, as if each were named in the model's
+    /// `uniform_orientation`
+    pub uniform_orientation: bool,
+    /// If true, `Board::accept` refuses any placement which would leave the board failing
+    /// `Board::is_guillotine_valid`, so every layout can be cut edge-to-edge
+    pub strict_guillotine: bool,
+}
+
+impl Options {
+    fn is_safe_to_crosscut(&self, cut: &Cut) -> bool {
+        self.min_crosscut_width
+            .is_none_or(|min_crosscut_width| cut.length >= min_crosscut_width)
+    }
+
+    fn is_within_max_board_width(&self, board: &model::Board) -> bool {
+        self.max_board_width
+            .is_none_or(|max_board_width| board.width <= max_board_width)
+    }
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            attempts: 1024,
+            result_count: 1,
+            number_cuts: false,
+            uniform_rips: false,
+            balance: false,
+            max_board_types: None,
+            max_board_width: None,
+            timeout: None,
+            objective: Objective::Waste,
+            seed: 12345,
+            min_crosscut_width: None,
+            reserve_board: false,
+            stack_fit_tolerance: 0.5,
+            preserve_order: false,
+            warm_start: None,
+            polish: false,
+            fast: false,
+            shuffle_ties: false,
+            uniform_orientation: false,
+            strict_guillotine: false,
+        }
+    }
+}
+
+/// Expands the model's cutlist into individual cuts (one per `count`)
+fn expand_cutlist(model: &model::Input, options: &Options) -> Vec<Cut> {
+    // tally the number of pieces sharing each name, so we only number names which repeat
+    let mut name_counts: HashMap<&str, i32> = HashMap::new();
+    for cut_model in &model.cutlist {
+        *name_counts.entry(&cut_model.name).or_default() += cut_model.count.fixed().max(0);
+    }
+
+    let mut cutlist: Vec<Cut> = Vec::new();
+    let mut name_indices: HashMap<&str, i32> = HashMap::new();
+    for cut_model in &model.cutlist {
+        if let model::CountSpec::Fixed(count) = cut_model.count {
+            if count < 1 {
+                // parsing rejects these, but an Input built in code may not have been parsed
+                warn!(
+                    "Ignoring {}, whose count of {} makes no pieces",
+                    cut_model.name, count
+                );
+                continue;
+            }
+        }
+        for _ in 0..cut_model.count.fixed() {
+            let mut cut = Cut::from(cut_model, model.crosscut_kerf(), model.rip_kerf());
+            cut.isolated = model.isolate.contains(&cut_model.name);
+            cut.uniform_orientation =
+                options.uniform_orientation || model.uniform_orientation.contains(&cut_model.name);
+            if !options.is_safe_to_crosscut(&cut) {
+                // orphaned, see `orphaned_cuts`
+                continue;
+            }
+
+            if options.number_cuts && name_counts[cut_model.name.as_str()] > 1 {
+                let index = name_indices.entry(&cut_model.name).or_default();
+                *index += 1;
+                cut.id = format!("{} #{}", cut.id, index);
+            }
+            cutlist.push(cut);
+        }
+    }
+
+    cutlist
+}
+
+/// Area-based estimates of the stock required for a model, computed without solving
+#[derive(Clone, Debug, PartialEq)]
+pub struct Preview {
+    /// Total area of all cuts, including spacing
+    pub total_cut_area: f32,
+    /// Board-feet of the cuts, assuming 1" (4/4) thick stock
+    pub cut_board_feet: f32,
+    /// Lower bound on the number of boards required
+    pub estimated_boards: usize,
+}
+
+/// Returns a lower bound on the number of boards needed, assuming perfect packing onto the largest available board
+pub fn theoretical_min_boards(model: &model::Input) -> usize {
+    let cutlist = expand_cutlist(model, &Options::default());
+    let cut_area: f32 = cutlist.iter().map(|cut| cut.length * cut.width).sum();
+    let largest_board_area = model
+        .boards
+        .iter()
+        .map(|board| board.usable_length() * board.usable_width())
+        .fold(0f32, f32::max);
+    (cut_area / largest_board_area).ceil() as usize
+}
+
+/// Computes a quick estimate of the stock required for the model, without running the solver
+pub fn preview(model: &model::Input) -> Preview {
+    let cutlist = expand_cutlist(model, &Options::default());
+    let total_cut_area: f32 = cutlist.iter().map(|cut| cut.length * cut.width).sum();
+    Preview {
+        total_cut_area,
+        cut_board_feet: total_cut_area / 144f32,
+        estimated_boards: theoretical_min_boards(model),
+    }
+}
+
+/// A blank which two pieces of the same width could be cut as, then split to length
+#[derive(Clone, Debug, PartialEq)]
+pub struct CombinedCut {
+    /// Names of the pieces, longest first
+    pub names: [String; 2],
+    /// The blank's length, the pieces' lengths and the crosscut kerf between them
+    pub length: f32,
+    pub width: f32,
+}
+
+/// Advises on pairs of cuts which could be cut as one larger blank and then split, e.g., two 12x6
+/// pieces from a 24x6 blank: pieces of the same width whose lengths sum to a whole number, as a
+/// blank of a neat length is easy to measure, and which as a blank fit the longest board. Pieces
+/// are paired longest first, each at most once. This is only advice; packing is unchanged.
+pub fn suggest_combined_cuts(model: &model::Input) -> Vec<CombinedCut> {
+    let max_length = model
+        .boards
+        .iter()
+        .map(|board| board.usable_length())
+        .fold(0f32, f32::max);
+    let kerf = model.crosscut_kerf();
+
+    let mut pieces: Vec<&model::Cut> = model
+        .cutlist
+        .iter()
+        .filter(|cut_model| !cut_model.sequential)
+        .flat_map(|cut_model| (0..cut_model.count.fixed().max(0)).map(move |_| cut_model))
+        .collect();
+    pieces.sort_by(|a, b| total_cmp_f32(a.width, b.width).then(total_cmp_f32(b.length, a.length)));
+
+    let mut used = vec![false; pieces.len()];
+    let mut suggestions = Vec::new();
+    for i in 0..pieces.len() {
+        if used[i] {
+            continue;
+        }
+        let a = pieces[i];
+        let partner = (i + 1..pieces.len()).find(|&j| {
+            let b = pieces[j];
+            let sum = a.length + b.length;
+            !used[j]
+                && (a.width - b.width).abs() < CROSSCUT_EPSILON
+                && (sum - sum.round()).abs() < CROSSCUT_EPSILON
+                && sum + kerf <= max_length
+        });
+        if let Some(j) = partner {
+            used[i] = true;
+            used[j] = true;
+            suggestions.push(CombinedCut {
+                names: [a.name.clone(), pieces[j].name.clone()],
+                length: a.length + pieces[j].length + kerf,
+                width: a.width,
+            });
+        }
+    }
+    suggestions
+}
+
+/// Splits an expanded cutlist into the cuts a previous solution already placed, laid out on
+/// copies of its boards as they were, and the rest, which remain to be placed. A cut is unchanged
+/// if a placed cut shares its id and dimensions, and isn't now pinned to another board. Boards the
+/// model no longer offers at the same size are dropped, their cuts placed anew, as are all cuts of
+/// a same board group if any of its cuts changed. Previous fill cuts are dropped, as fill is made from what's left afterwards.
+pub fn warm_start(
+    model: &model::Input,
+    previous: &[Board],
+    cutlist: Vec<Cut>,
+) -> (Vec<Board>, Vec<Cut>) {
+    let mut remaining = cutlist;
+    let mut take = |placed: &Cut, board: &Board| {
+        if model
+            .pinned
+            .get(&placed.name)
+            .is_some_and(|pinned_to| !board.has_id(pinned_to))
+        {
+            return None;
+        }
+        remaining
+            .iter()
+            .position(|cut| {
+                cut.id == placed.id && cut.length == placed.length && cut.width == placed.width
+            })
+            .map(|i| Cut {
+                placement: placed.placement,
+                outcome: placed.outcome.clone(),
+                ..remaining.remove(i)
+            })
+    };
+
+    let mut kept: Vec<Board> = Vec::new();
+    for previous_board in previous {
+        let board_model = model.boards.iter().find(|board_model| {
+            board_model.id == previous_board.id
+                && board_model.usable_length() == previous_board.length
+                && board_model.usable_width() == previous_board.width
+                && Board::from(*board_model).defects == previous_board.defects
+        });
+        if let Some(board_model) = board_model {
+            let mut board = Board::from(board_model);
+            for previous_stack in &previous_board.stacks {
+                let mut stack = CutStack::new();
+                stack.cuts = previous_stack
+                    .cuts
+                    .iter()
+                    .filter_map(|cut| take(cut, &board))
+                    .collect();
+                board.stacks.push(stack);
+            }
+            kept.push(board);
+        }
+    }
+
+    // a group is placed together, so a changed cut moves its whole group
+    for group_names in &model.same_board_groups {
+        if remaining.iter().any(|cut| group_names.contains(&cut.name)) {
+            for stack in kept.iter_mut().flat_map(|board| &mut board.stacks) {
+                let (group, others): (Vec<Cut>, Vec<Cut>) = stack
+                    .cuts
+                    .drain(..)
+                    .partition(|cut| group_names.contains(&cut.name));
+                stack.cuts = others;
+                remaining.extend(group);
+            }
+        }
+    }
+
+    // renumber the kept cuts' placements from 0, in their previous order, for `generate` to follow
+    let mut kept_cuts: Vec<&mut Cut> = kept
+        .iter_mut()
+        .flat_map(|board| &mut board.stacks)
+        .flat_map(|stack| &mut stack.cuts)
+        .collect();
+    kept_cuts.sort_by_key(|cut| cut.placement);
+    for (placement, cut) in kept_cuts.into_iter().enumerate() {
+        cut.placement = placement;
+    }
+
+    for board in &mut kept {
+        board.stacks.retain(|stack| !stack.cuts.is_empty());
+    }
+    kept.retain(|board| !board.stacks.is_empty());
+    (kept, remaining)
+}
+
+/// A line of a solution's shopping list: how many of a board it uses
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct StockLine {
+    pub id: String,
+    /// The board's size as sold
+    pub length: f32,
+    pub width: f32,
+    pub count: usize,
+}
+
+impl fmt::Display for StockLine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} x {} ({} by {})",
+            self.count, self.id, self.length, self.width
+        )
+    }
+}
+
+/// Returns a line per distinct board in the solution, in order of first use
+pub fn stock_list(solution: &[Board]) -> Vec<StockLine> {
+    let mut lines: Vec<StockLine> = Vec::new();
+    for board in solution {
+        match lines.iter_mut().find(|line| line.id == board.id) {
+            Some(line) => line.count += 1,
+            None => lines.push(StockLine {
+                id: board.id.clone(),
+                length: board.nominal_length,
+                width: board.nominal_width,
+                count: 1,
+            }),
+        }
+    }
+    lines
+}
+
+/// Everything to buy for a project: the stock its solution uses, and the model's hardware
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct BillOfMaterials {
+    pub stock: Vec<StockLine>,
+    pub hardware: Vec<model::Hardware>,
+}
+
+impl fmt::Display for BillOfMaterials {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Stock:")?;
+        for line in &self.stock {
+            writeln!(f, "    {}", line)?;
+        }
+        if !self.hardware.is_empty() {
+            writeln!(f, "Hardware:")?;
+            for item in &self.hardware {
+                writeln!(f, "    {}", item)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Combines the solution's stock list with the model's hardware, which is carried over as given
+pub fn bill_of_materials(model: &model::Input, solution: &[Board]) -> BillOfMaterials {
+    BillOfMaterials {
+        stock: stock_list(solution),
+        hardware: model.hardware.clone(),
+    }
+}
+
+/// Atempts to find a best solution for computing the cutlist for the given model.
+pub fn compute(model: &model::Input, options: &Options) -> Result<Vec<Vec<Board>>> {
+    compute_with_progress(model, options, |_, _| {})
+}
+
+/// Like `compute`, but invokes `progress` with the number of attempts completed and the total after each attempt.
+pub fn compute_with_progress<F>(
+    model: &model::Input,
+    options: &Options,
+    progress: F,
+) -> Result<Vec<Vec<Board>>>
+where
+    F: FnMut(usize, usize),
+{
+    compute_with_convergence(model, options, progress).map(|(solutions, _)| solutions)
+}
+
+/// How the search's best solution improved over its attempts, to judge whether more attempts
+/// would likely find better
+#[derive(Clone, Debug, PartialEq)]
+pub struct Convergence {
+    /// The attempts run, fewer than asked for if the search timed out
+    pub attempts: usize,
+    /// The attempts, counting from 1, whose solution improved on every one before it; the last
+    /// found the best. Ranked before polishing, so a polished best may come from another attempt.
+    pub improvements: Vec<usize>,
+}
+
+impl Convergence {
+    /// The attempt, counting from 1, which found the best solution
+    pub fn best_found_at(&self) -> Option<usize> {
+        self.improvements.last().copied()
+    }
+
+    /// True if the best solution turned up in the first half of the attempts, so more attempts are
+    /// unlikely to help
+    pub fn converged_early(&self) -> bool {
+        self.best_found_at()
+            .is_some_and(|attempt| attempt * 2 <= self.attempts)
+    }
+}
+
+impl fmt::Display for Convergence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.best_found_at() {
+            Some(attempt) => write!(
+                f,
+                "Best found at attempt {} of {} after {} improvement{}; {}",
+                attempt,
+                self.attempts,
+                self.improvements.len(),
+                if self.improvements.len() == 1 {
+                    ""
+                } else {
+                    "s"
+                },
+                if self.converged_early() {
+                    "found early, more attempts are unlikely to help"
+                } else {
+                    "found late, more attempts may help"
+                }
+            ),
+            None => write!(f, "No viable solution in {} attempts", self.attempts),
+        }
+    }
+}
+
+/// Like `compute_with_progress`, but also returns how the best solution improved over the attempts
+pub fn compute_with_convergence<F>(
+    model: &model::Input,
+    options: &Options,
+    mut progress: F,
+) -> Result<(Vec<Vec<Board>>, Convergence)>
+where
+    F: FnMut(usize, usize),
+{
+    // `search` reports progress after each attempt, so a solution belongs to the attempt after
+    // those completed
+    let completed = Cell::new(0);
+    let mut results: Vec<Vec<Board>> = Vec::new();
+    let mut best = None;
+    let mut improvements = Vec::new();
+    search(
+        model,
+        options,
+        |done, total| {
+            completed.set(done);
+            progress(done, total);
+        },
+        |result| {
+            let is_better = best.is_none_or(|best: usize| {
+                compare_by_preference(&result, &results[best], model, options) == Ordering::Less
+            });
+            if is_better {
+                best = Some(results.len());
+                improvements.push(completed.get() + 1);
+            }
+            results.push(result);
+        },
+    )?;
+    let convergence = Convergence {
+        attempts: completed.get(),
+        improvements,
+    };
+
+    if options.fast {
+        let mut solution = match results.pop() {
+            Some(solution) => solution,
+            None => bail!("No viable solutions found"),
+        };
+        if options.polish && options.warm_start.is_none() {
+            polish(model, &mut solution, options);
+        }
+        if !options.preserve_order {
+            solution.iter_mut().for_each(Board::tidy);
+        }
+        return Ok((vec![solution], convergence));
+    }
+
+    Ok((best_of(results, model, options)?, convergence))
+}
+
+/// Like `compute`, but holds at most `options.result_count` solutions as the search runs, in a
+/// heap with the worst on top, rather than every solution found; memory use doesn't grow with
+/// `options.attempts`. Returns the same solutions as `compute`.
+pub fn compute_streaming(model: &model::Input, options: &Options) -> Result<Vec<Vec<Board>>> {
+    let mut best = BinaryHeap::new();
+    search(
+        model,
+        options,
+        |_, _| {},
+        |solution| {
+            best.push(Ranked::new(solution, model, options));
+            if best.len() > options.result_count {
+                best.pop();
+            }
+        },
+    )?;
+    let results = best
+        .into_sorted_vec()
+        .into_iter()
+        .map(|ranked| ranked.solution)
+        .collect();
+    best_of(results, model, options)
+}
+
+// a solution, ordered as `rank` would order it, so the greatest is the worst
+struct Ranked<'a> {
+    solution: Vec<Board>,
+    tie_breaker: (usize, usize, String),
+    model: &'a model::Input,
+    options: &'a Options,
+}
+
+impl<'a> Ranked<'a> {
+    fn new(solution: Vec<Board>, model: &'a model::Input, options: &'a Options) -> Self {
+        Ranked {
+            tie_breaker: tie_breaker(&solution),
+            solution,
+            model,
+            options,
+        }
+    }
+}
+
+impl Ord for Ranked<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        compare_by_preference(&self.solution, &other.solution, self.model, self.options)
+            .then_with(|| self.tie_breaker.cmp(&other.tie_breaker))
+    }
+}
+
+impl PartialOrd for Ranked<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Ranked<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Ranked<'_> {}
+
+// ranks the solutions found, returning the best `options.result_count` of them, tidied
+fn best_of(
+    mut results: Vec<Vec<Board>>,
+    model: &model::Input,
+    options: &Options,
+) -> Result<Vec<Vec<Board>>> {
+    if !results.is_empty() {
+        // sort results by the objective with best at front, and then return the desired count
+        rank(&mut results, model, options);
+        let result_count = options.result_count.min(results.len());
+        info!("Found {} viable solutions", result_count);
+        let mut results = results[0..result_count].to_vec();
+        if options.polish && options.warm_start.is_none() {
+            let improvements: usize = results
+                .iter_mut()
+                .map(|solution| polish(model, solution, options))
+                .sum();
+            debug!("Polishing made {} improvements", improvements);
+            rank(&mut results, model, options);
+        }
+        if !options.preserve_order {
+            for board in results.iter_mut().flatten() {
+                board.tidy();
+            }
+        }
+        Ok(results)
+    } else {
+        bail!("No viable solutions found")
+    }
+}
+
+// orders the cutlist so `generate`, placing from the back, places the largest area first; cuts of
+// equal area are shuffled among themselves, as the sort is stable
+fn sort_by_area_shuffling_ties(cutlist: &mut [Cut], rng: &mut Pcg64) {
+    cutlist.shuffle(rng);
+    cutlist.sort_by(|a, b| total_cmp_f32(a.length * a.width, b.length * b.width));
+}
+
+// validates the model, then searches for solutions per the options, passing each to `found`
+// which honors the exact board counts
+fn search<F, S>(
+    model: &model::Input,
+    options: &Options,
+    mut progress: F,
+    mut found: S,
+) -> Result<()>
+where
+    F: FnMut(usize, usize),
+    S: FnMut(Vec<Board>),
+{
+    let cutlist = validated_cutlist(model, options)?;
+    let (kept, mut cutlist) = match &options.warm_start {
+        Some(previous) => warm_start(model, previous, cutlist),
+        None => (Vec::new(), cutlist),
+    };
+
+    // discard any solutions which don't honor the exact board counts
+    let mut found = |result: Vec<Board>| {
+        if meets_exact_counts(model, &result) {
+            found(result);
+        }
+    };
+
+    if options.preserve_order {
+        // `generate` places cuts from the back of the cutlist
+        cutlist.reverse();
+        if let Some(result) = generate(model, &kept, &cutlist, options) {
+            found(result);
+        }
+        progress(1, 1);
+    } else if options.fast {
+        // placed from the back, so the largest go first
+        cutlist.sort_by(|a, b| total_cmp_f32(a.length * a.width, b.length * b.width));
+        if let Some(result) = generate(model, &kept, &cutlist, options) {
+            found(result);
+        }
+        progress(1, 1);
+    } else if options.attempts == 0 {
+        cutlist.sort_by(|a, b| total_cmp_f32(b.length, a.length));
+        if let Some(result) = generate(model, &kept, &cutlist, options) {
+            found(result);
+        }
+        progress(1, 1);
+    } else {
+        // shuffle approach, stopping when attempts are exhausted or time runs out, whichever is first
+        let mut rng = Pcg64::seed_from_u64(options.seed);
+        let deadline = options.timeout.map(|timeout| Instant::now() + timeout);
+
+        for attempt in 0..options.attempts {
+            if options.shuffle_ties {
+                sort_by_area_shuffling_ties(&mut cutlist, &mut rng);
+            } else {
+                cutlist.shuffle(&mut rng);
+            }
+            if let Some(result) = generate(model, &kept, &cutlist, options) {
+                found(result);
+            }
+            progress(attempt + 1, options.attempts);
+
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// validates the model and options, returning the expanded cutlist, or an error naming why no
+// solution is possible; this doesn't depend on the seed, so a sweep need only check it once
+fn validated_cutlist(model: &model::Input, options: &Options) -> Result<Vec<Cut>> {
+    if options.result_count == 0 && !options.fast {
+        bail!("The result count must be at least 1");
+    }
+    if model.boards.iter().any(|board| board.sheet) {
+        bail!("Sheet stock is packed with `pack_sheets` rather than in stacks");
+    }
+
+    if !is_a_solution_possible(model) {
+        bail!("No solution is possible, some cuts are larger than all of the available boards");
+    }
+    if let Some(max_board_width) = options.max_board_width {
+        let narrow_enough = model::Input {
+            boards: model
+                .boards
+                .iter()
+                .filter(|board| options.is_within_max_board_width(board))
+                .cloned()
+                .collect(),
+            ..model.clone()
+        };
+        if narrow_enough.boards.is_empty() {
+            bail!(
+                "No solution is possible, no boards are at most {} wide",
+                max_board_width
+            );
+        }
+        if !is_a_solution_possible(&narrow_enough) {
+            bail!(
+                "No solution is possible, some cuts are larger than all of the boards at most {} wide",
+                max_board_width
+            );
+        }
+    }
+
+    // Create a vector of our required Cuts
+    let cutlist = expand_cutlist(model, options);
+    if cutlist.is_empty() {
+        bail!("Every cut is too short to crosscut safely");
+    }
+
+    // Verify each same board group can fit on at least one of the available boards
+    for group_names in &model.same_board_groups {
+        let group: Vec<Cut> = cutlist
+            .iter()
+            .filter(|cut| group_names.contains(&cut.name))
+            .cloned()
+            .collect();
+        if !place_group(model, &mut Vec::new(), &group, 0, options) {
+            bail!(
+                "Same board group [{}] cannot fit on any single available board",
+                group_names.join(", ")
+            );
+        }
+    }
+
+    // Verify each sequential set fits side by side in a stack of at least one of the available boards
+    for cut_model in model
+        .cutlist
+        .iter()
+        .filter(|cut_model| cut_model.sequential)
+    {
+        let set: Vec<Cut> = cutlist
+            .iter()
+            .filter(|cut| cut.name == cut_model.name)
+            .cloned()
+            .collect();
+        if !place_sequence(model, &mut Vec::new(), &set, 0, options) {
+            bail!(
+                "Sequential set \"{}\" cannot fit side by side on any single available board",
+                cut_model.name
+            );
+        }
+    }
+
+    // Verify each pinned cut fits on the board it's pinned to
+    for (name, board_id) in &model.pinned {
+        if let Some(cut) = cutlist.iter().find(|cut| &cut.name == name) {
+            if !place_pinned(model, &mut Vec::new(), cut, board_id, options) {
+                bail!(
+                    "Cut \"{}\" is pinned to board \"{}\", which it cannot fit on",
+                    name,
+                    board_id
+                );
+            }
+        }
+    }
+
+    Ok(cutlist)
+}
+
+/// Runs `compute` with each of the seeds `0..seeds`, returning the seed whose best solution is best
+/// overall, along with its solutions. A model no seed could solve, e.g., with a cut larger than
+/// every board, is reported as `compute` would; otherwise, if no seed finds a solution, the last
+/// seed's error is.
+pub fn seed_sweep(
+    model: &model::Input,
+    options: &Options,
+    seeds: u64,
+) -> Result<(u64, Vec<Vec<Board>>)> {
+    // errors which no seed could get around are reported as they are
+    validated_cutlist(model, options)?;
+    let mut best: Option<(u64, Vec<Vec<Board>>)> = None;
+    let mut last_error = None;
+    for seed in 0..seeds {
+        let options = Options {
+            seed,
+            ..options.clone()
+        };
+        let solutions = match compute(model, &options) {
+            Ok(solutions) => solutions,
+            // some seeds may find nothing viable, e.g., with exact counts
+            Err(error) => {
+                last_error = Some(error);
+                continue;
+            }
+        };
+        let is_better = best.as_ref().is_none_or(|(_, best)| {
+            compare(&solutions[0], &best[0], model, &options) == Ordering::Less
+        });
+        if is_better {
+            best = Some((seed, solutions));
+        }
+    }
+
+    match (best, last_error) {
+        (Some(best), _) => Ok(best),
+        (None, Some(error)) => Err(error),
+        (None, None) => bail!("No viable solutions found with any of {} seeds", seeds),
+    }
+}
+
+/// Fixtures shared by the tests of the modules which export or render solutions
+#[cfg(test)]
+pub(crate) mod test_support {
+    use super::*;
+
+    /// Parses the yaml input and returns the best solution `compute` finds for it by default
+    pub(crate) fn solve(yaml: &str) -> Vec<Board> {
+        let model = model::Input::parse(yaml, model::InputFormat::Yaml).unwrap();
+        compute(&model, &Options::default()).unwrap().remove(0)
+    }
+}
+
+#[cfg(test)]
+mod solver_tests {
+    use super::*;
+
+    fn input(cutlist: &[&str]) -> model::Input {
+        model::Input {
+            spacing: 0f32,
+            boards: vec![model::Board::parse("96x8:A").unwrap()],
+            cutlist: cutlist
+                .iter()
+                .map(|c| model::Cut::parse(c).unwrap())
+                .collect(),
+            same_board_groups: Vec::new(),
+            pinned: Default::default(),
+            densities: Default::default(),
+            isolate: Default::default(),
+            uniform_orientation: Default::default(),
+            hardware: Default::default(),
+            operation_times: Default::default(),
+            max_handling: None,
+            crosscut_kerf: None,
+            rip_kerf: None,
+        }
+    }
+
+    fn cut(length: f32, width: f32, id: &str) -> Cut {
+        Cut {
+            length,
+            width,
+            id: id.to_owned(),
+            name: id.to_owned(),
+            placement: 0,
+            outcome: None,
+            shape: None,
+            double_sided: false,
+            isolated: false,
+            thickness: None,
+            uniform_orientation: false,
+            grain: false,
+            rotated: false,
+        }
+    }
+
+    // 256 empty boards of random sizes, each with randomized options and 32 cuts to offer it in
+    // turn, of whole and half inch dimensions up to and including the board's own
+    fn random_board_trials(seed: u64) -> Vec<(Board, Options, Vec<Cut>)> {
+        let mut rng = Pcg64::seed_from_u64(seed);
+        (0..256)
+            .map(|_| {
+                let length = rng.gen_range(24..=120) as f32;
+                let width = rng.gen_range(2..=12) as f32;
+                let options = Options {
+                    stack_fit_tolerance: rng.gen_range(0f32..=1f32),
+                    uniform_rips: rng.gen_bool(0.25),
+                    min_crosscut_width: if rng.gen_bool(0.5) {
+                        Some(rng.gen_range(0f32..12f32))
+                    } else {
+                        None
+                    },
+                    ..Default::default()
+                };
+                let cuts = (0..32)
+                    .map(|i| {
+                        let cut_length = rng.gen_range(1..=(length as i32 * 2)) as f32 / 2f32;
+                        let cut_width = rng.gen_range(1..=(width as i32 * 2)) as f32 / 2f32;
+                        Cut {
+                            isolated: rng.gen_bool(0.1),
+                            ..cut(cut_length, cut_width, ["A", "B", "C"][i % 3])
+                        }
+                    })
+                    .collect();
+                (board(length, width, vec![]), options, cuts)
+            })
+            .collect()
+    }
+
+    fn board(length: f32, width: f32, stacks: Vec<Vec<Cut>>) -> Board {
+        Board {
+            length,
+            nominal_length: length,
+            width,
+            nominal_width: width,
+            id: "A".to_owned(),
+            stacks: stacks.into_iter().map(|cuts| CutStack { cuts }).collect(),
+            defects: Vec::new(),
+            end_trims: (0f32, 0f32),
+            thickness: None,
+            planing_allowance: None,
+            lot: None,
+        }
+    }
+
+    fn board_containing<'a>(solution: &'a [Board], name: &str) -> Vec<&'a Board> {
+        solution
+            .iter()
+            .filter(|board| board.cuts().iter().any(|cut| cut.name == name))
+            .collect()
+    }
+
+    #[test]
+    fn expand_cutlist_numbers_repeated_names() {
+        let model = input(&["3@24x4:Shelf", "1@12x4:Shelf", "1@30x2:Leg"]);
+        let options = Options {
+            number_cuts: true,
+            ..Default::default()
+        };
+        let cutlist = expand_cutlist(&model, &options);
+        let ids: Vec<&str> = cutlist.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec!["Shelf #1", "Shelf #2", "Shelf #3", "Shelf #4", "Leg"]
+        );
+    }
+
+    #[test]
+    fn expand_cutlist_leaves_names_alone_by_default() {
+        let model = input(&["2@24x4:Shelf"]);
+        let cutlist = expand_cutlist(&model, &Options::default());
+        assert!(cutlist.iter().all(|c| c.id == "Shelf"));
+    }
+
+    #[test]
+    fn exact_count_rejects_over_and_under_use() {
+        let mut model = input(&["1@12x4:Apron"]);
+        model.boards = vec![model::Board::parse("96x8:A=2").unwrap()];
+        let board: Board = (&model.boards[0]).into();
+
+        assert!(!meets_exact_counts(&model, std::slice::from_ref(&board)));
+        assert!(meets_exact_counts(&model, &[board.clone(), board.clone()]));
+        assert!(!meets_exact_counts(
+            &model,
+            &[board.clone(), board.clone(), board]
+        ));
+    }
+
+    #[test]
+    fn board_lots_use_each_board_at_most_once() {
+        let mut model = input(&["12@20x2:Rail"]);
+        model.boards = model::BoardLot::parse("4@48~6x6:Rough")
+            .unwrap()
+            .expand(0)
+            .unwrap();
+        let options = Options {
+            attempts: 16,
+            result_count: 4,
+            ..Default::default()
+        };
+        for solution in compute(&model, &options).unwrap() {
+            let mut ids: Vec<&str> = solution.iter().map(|board| board.id.as_str()).collect();
+            ids.sort_unstable();
+            let used = ids.len();
+            ids.dedup();
+            assert_eq!(ids.len(), used);
+        }
+
+        // more than the lot holds can't be cut from it
+        model.cutlist = input(&["40@20x2:Rail"]).cutlist;
+        assert!(compute(&model, &options).is_err());
+    }
+
+    #[test]
+    fn exact_count_filters_solutions() {
+        let mut model = input(&["4@90x7:Slat"]);
+        model.boards = vec![model::Board::parse("96x8:A=4").unwrap()];
+        let solutions = compute(&model, &Options::default()).expect("Expected a solution");
+        assert!(solutions.iter().all(|solution| solution.len() == 4));
+
+        // the slats need 4 boards, so requiring 3 can't be honored
+        model.boards = vec![model::Board::parse("96x8:A=3").unwrap()];
+        assert!(compute(&model, &Options::default()).is_err());
+    }
+
+    #[test]
+    fn same_board_group_is_placed_on_one_board() {
+        let mut model = input(&["4@40x7:Filler", "3@20x2:Drawer Front"]);
+        model.same_board_groups = vec![vec!["Drawer Front".to_owned()]];
+        let options = Options {
+            attempts: 32,
+            result_count: 32,
+            ..Default::default()
+        };
+
+        let solutions = compute(&model, &options).expect("Expected a solution");
+        for solution in &solutions {
+            assert_eq!(board_containing(solution, "Drawer Front").len(), 1);
+        }
+    }
+
+    #[test]
+    fn oversized_same_board_group_is_unsatisfiable() {
+        let mut model = input(&["3@40x7:Drawer Front", "1@10x2:Pull"]);
+        model.same_board_groups = vec![vec!["Drawer Front".to_owned()]];
+
+        let error =
+            compute(&model, &Options::default()).expect_err("Expected group to be rejected");
+        assert!(error.to_string().contains("Drawer Front"));
+    }
+
+    #[test]
+    fn uniform_rips_never_mix_parts_in_a_stack() {
+        let model = input(&["4@20x2:Leg", "4@20x3:Rail", "6@12x1.5:Cleat"]);
+        let options = Options {
+            attempts: 64,
+            result_count: 64,
+            uniform_rips: true,
+            ..Default::default()
+        };
+
+        let solutions = compute(&model, &options).expect("Expected a solution");
+        for stack in solutions.iter().flatten().flat_map(|board| &board.stacks) {
+            let name = &stack.cuts[0].name;
+            assert!(stack.cuts.iter().all(|cut| &cut.name == name));
+        }
+    }
+
+    #[test]
+    fn balance_distributes_cuts_across_board_types() {
+        let mut model = input(&["8@90x5:Slat"]);
+        model.boards = vec![
+            model::Board::parse("96x8:A").unwrap(),
+            model::Board::parse("96x6:B").unwrap(),
+        ];
+        let count_of =
+            |solution: &[Board], id: &str| solution.iter().filter(|b| b.id == id).count();
+
+        // by default, the narrowest board which fits is always vended
+        let unbalanced = &compute(&model, &Options::default()).unwrap()[0];
+        assert_eq!(count_of(unbalanced, "A"), 0);
+        assert_eq!(count_of(unbalanced, "B"), 8);
+
+        let options = Options {
+            balance: true,
+            ..Default::default()
+        };
+        let balanced = &compute(&model, &options).unwrap()[0];
+        assert_eq!(count_of(balanced, "A"), 4);
+        assert_eq!(count_of(balanced, "B"), 4);
+    }
+
+    #[test]
+    fn progress_reports_reach_completion() {
+        let model = input(&["4@25x2:Leg", "2@12x4:Apron"]);
+        let options = Options {
+            attempts: 50,
+            ..Default::default()
+        };
+
+        let mut reports = Vec::new();
+        compute_with_progress(&model, &options, |completed, total| {
+            reports.push((completed, total))
+        })
+        .unwrap();
+        assert_eq!(reports.len(), 50);
+        assert_eq!(reports.last(), Some(&(50, 50)));
+        assert!(reports.windows(2).all(|w| w[0].0 < w[1].0));
+
+        // the single sorted pass reports as one attempt
+        let options = Options {
+            attempts: 0,
+            ..Default::default()
+        };
+        let mut reports = Vec::new();
+        compute_with_progress(&model, &options, |completed, total| {
+            reports.push((completed, total))
+        })
+        .unwrap();
+        assert_eq!(reports, vec![(1, 1)]);
+    }
+
+    #[test]
+    fn used_bounds_covers_the_furthest_cut_edges() {
+        assert_eq!(board(96f32, 8f32, Vec::new()).used_bounds(), (0f32, 0f32));
+
+        let partial = board(
+            96f32,
+            8f32,
+            vec![
+                vec![cut(40f32, 4f32, "A"), cut(30f32, 2f32, "B")],
+                vec![cut(20f32, 3f32, "C")],
+            ],
+        );
+        assert_eq!(partial.used_bounds(), (60f32, 6f32));
+
+        // a stack's short cuts don't reach its end, but the longest does
+        let ragged = board(
+            96f32,
+            8f32,
+            vec![vec![cut(10f32, 2f32, "A"), cut(25f32, 6f32, "B")]],
+        );
+        assert_eq!(ragged.used_bounds(), (25f32, 8f32));
+    }
+
+    #[test]
+    fn largest_offcut_spans_stacks_and_tail() {
+        let concentrated = board(96f32, 8f32, vec![vec![cut(40f32, 8f32, "A")]]);
+        assert_eq!(concentrated.largest_offcut(), (56f32, 8f32));
+
+        let scattered = board(
+            96f32,
+            8f32,
+            vec![vec![cut(40f32, 4f32, "A")], vec![cut(20f32, 8f32, "B")]],
+        );
+        assert_eq!(scattered.largest_offcut(), (36f32, 8f32));
+
+        // free space beside consecutive stacks merges into one offcut with the tail
+        let skyline = board(
+            96f32,
+            8f32,
+            vec![vec![cut(48f32, 4f32, "A")], vec![cut(40f32, 4f32, "B")]],
+        );
+        assert_eq!(skyline.largest_offcut(), (96f32, 4f32));
+
+        // the end of a short cut in a stack is an offcut too
+        let full = board(
+            40f32,
+            8f32,
+            vec![vec![cut(40f32, 4f32, "A"), cut(10f32, 4f32, "B")]],
+        );
+        assert_eq!(full.largest_offcut(), (30f32, 4f32));
+    }
+
+    #[test]
+    fn offcuts_are_counted_per_loose_piece() {
+        let full = board(96f32, 8f32, vec![vec![cut(96f32, 8f32, "A")]]);
+        assert!(full.offcuts().is_empty());
+
+        let stack = board(
+            96f32,
+            8f32,
+            vec![vec![cut(40f32, 4f32, "A"), cut(30f32, 3f32, "B")]],
+        );
+        assert_eq!(
+            stack.offcuts(),
+            vec![(10f32, 3f32), (40f32, 1f32), (56f32, 8f32)]
+        );
+    }
+
+    #[test]
+    fn offcuts_objective_prefers_fewer_pieces_at_equal_waste() {
+        // both leave 48x8 of waste, but the first leaves it as one piece
+        let tidy = vec![board(96f32, 8f32, vec![vec![cut(48f32, 8f32, "A")]])];
+        let scattered = vec![board(
+            96f32,
+            8f32,
+            vec![vec![cut(48f32, 4f32, "A")], vec![cut(48f32, 4f32, "B")]],
+        )];
+        assert!((tidy[0].scrap_area() - scattered[0].scrap_area()).abs() < 1e-4);
+        assert_eq!(offcut_count(&tidy), 1);
+        assert_eq!(offcut_count(&scattered), 2);
+
+        let mut solutions = vec![scattered, tidy.clone()];
+        let options = Options {
+            objective: Objective::Offcuts,
+            ..Default::default()
+        };
+        rank(&mut solutions, &input(&[]), &options);
+        assert_eq!(offcut_count(&solutions[0]), 1);
+
+        assert_eq!("offcuts".parse::<Objective>().unwrap(), Objective::Offcuts);
+        assert!("area".parse::<Objective>().is_err());
+    }
+
+    #[test]
+    fn total_cmp_f32_sorts_problematic_values() {
+        let mut values = [
+            (3f32, 0),
+            (f32::NAN, 1),
+            (-0f32, 2),
+            (f32::INFINITY, 3),
+            (0f32, 4),
+            (3f32, 5),
+            (f32::NEG_INFINITY, 6),
+            (f32::NAN, 7),
+        ];
+        values.sort_by(|a, b| total_cmp_f32(a.0, b.0));
+        let order: Vec<usize> = values.iter().map(|(_, index)| *index).collect();
+        // equal values keep their order; NaN sorts last
+        assert_eq!(order, vec![6, 2, 4, 0, 5, 3, 1, 7]);
+
+        // a board whose score is NaN, e.g., from a zero length, is ranked rather than panicking
+        let mut solutions = vec![
+            vec![board(0f32, 0f32, vec![vec![cut(0f32, 0f32, "A")]])],
+            vec![board(96f32, 8f32, vec![vec![cut(40f32, 4f32, "A")]])],
+        ];
+        rank(&mut solutions, &input(&[]), &Options::default());
+        let mut boards = solutions.remove(0);
+        boards[0].tidy();
+    }
+
+    #[test]
+    fn cutting_time_counts_each_operation() {
+        // a crosscut after each stack, freeing it from the board, a rip per cut, and a trim for B,
+        // which is short of its stack
+        let solution = vec![board(
+            96f32,
+            8f32,
+            vec![
+                vec![cut(40f32, 4f32, "A"), cut(30f32, 3f32, "B")],
+                vec![cut(20f32, 8f32, "C")],
+            ],
+        )];
+        assert_eq!(operation_counts(&solution), (3, 3));
+        let times = model::OperationTimes {
+            crosscut: 20f32,
+            rip: 45f32,
+        };
+        assert_eq!(cutting_time(&solution, &times), 3f32 * 20f32 + 3f32 * 45f32);
+
+        // the same cuts in one stack filling its board need no crosscut, only two trims
+        let stacked = vec![board(
+            40f32,
+            16f32,
+            vec![vec![
+                cut(40f32, 4f32, "A"),
+                cut(30f32, 3f32, "B"),
+                cut(20f32, 8f32, "C"),
+            ]],
+        )];
+        assert_eq!(operation_counts(&stacked), (2, 3));
+
+        let mut model = input(&[]);
+        model.operation_times = times;
+        let options = Options {
+            objective: Objective::Time,
+            ..Default::default()
+        };
+        let mut solutions = vec![solution, stacked];
+        rank(&mut solutions, &model, &options);
+        assert_eq!(solutions[0][0].stacks.len(), 1);
+        assert_eq!("time".parse::<Objective>().unwrap(), Objective::Time);
+    }
+
+    #[test]
+    fn schedule_groups_operations_by_phase() {
+        let solution = vec![board(
+            96f32,
+            8f32,
+            vec![
+                vec![cut(40f32, 4f32, "A"), cut(30f32, 3f32, "B")],
+                vec![cut(20f32, 8f32, "C")],
+            ],
+        )];
+        let times = model::OperationTimes {
+            crosscut: 20f32,
+            rip: 45f32,
+        };
+        let schedule = build_schedule(&solution, &times);
+        let phases: Vec<(Operation, usize, f32, f32)> = schedule
+            .phases
+            .iter()
+            .map(|phase| {
+                (
+                    phase.operation,
+                    phase.steps.len(),
+                    phase.start,
+                    phase.duration,
+                )
+            })
+            .collect();
+        assert_eq!(
+            phases,
+            vec![
+                (Operation::Crosscut, 2, 0f32, 40f32),
+                (Operation::Rip, 3, 40f32, 135f32),
+                (Operation::Trim, 1, 175f32, 20f32),
+            ]
+        );
+        assert_eq!(schedule.total(), cutting_time(&solution, &times));
+
+        let text = schedule.to_string();
+        assert!(text.starts_with("Schedule, estimated 3:15"));
+        assert_eq!(text.lines().count(), 4);
+        assert!(text.contains("Rough cut (2 operations)"));
+
+        // a board needing no crosscuts or trims is only ripped
+        let ripped = vec![board(40f32, 8f32, vec![vec![cut(40f32, 4f32, "A")]])];
+        assert_eq!(build_schedule(&ripped, &times).phases.len(), 1);
+        assert_eq!(build_schedule(&[], &times).total(), 0f32);
+    }
+
+    #[test]
+    fn cluster_objective_prefers_parts_on_fewer_boards() {
+        // the same cuts on two boards each, but the second scatters both parts across both boards
+        let cut_24 = |id| cut(24f32, 4f32, id);
+        let clustered = vec![
+            board(96f32, 8f32, vec![vec![cut_24("Shelf"), cut_24("Shelf")]]),
+            board(96f32, 8f32, vec![vec![cut_24("Rail"), cut_24("Rail")]]),
+        ];
+        let scattered = vec![
+            board(96f32, 8f32, vec![vec![cut_24("Shelf"), cut_24("Rail")]]),
+            board(96f32, 8f32, vec![vec![cut_24("Rail"), cut_24("Shelf")]]),
+        ];
+        assert_eq!(part_spread(&clustered), 0f32);
+        assert_eq!(part_spread(&scattered), 1f32);
+        assert_eq!(score(&clustered), score(&scattered));
+
+        let options = Options {
+            objective: Objective::Cluster,
+            ..Default::default()
+        };
+        let mut solutions = vec![scattered, clustered];
+        rank(&mut solutions, &input(&[]), &options);
+        assert_eq!(part_spread(&solutions[0]), 0f32);
+        assert_eq!("cluster".parse::<Objective>().unwrap(), Objective::Cluster);
+    }
+
+    #[test]
+    fn effort_objective_prefers_smaller_boards() {
+        // two boards each, holding the same cuts, but the first solution's boards are longer
+        let cut_24 = |id| cut(24f32, 4f32, id);
+        let long = vec![
+            board(96f32, 8f32, vec![vec![cut_24("Shelf"), cut_24("Shelf")]]),
+            board(96f32, 8f32, vec![vec![cut_24("Rail"), cut_24("Rail")]]),
+        ];
+        let short = vec![
+            board(48f32, 8f32, vec![vec![cut_24("Shelf"), cut_24("Shelf")]]),
+            board(32f32, 8f32, vec![vec![cut_24("Rail"), cut_24("Rail")]]),
+        ];
+        assert_eq!(handling_effort(&long), 2f32 * 96f32 * 8f32);
+        assert_eq!(handling_effort(&short), 80f32 * 8f32);
+
+        let options = Options {
+            objective: Objective::Effort,
+            ..Default::default()
+        };
+        let mut solutions = vec![long, short];
+        rank(&mut solutions, &input(&[]), &options);
+        assert_eq!(solutions[0][0].length, 48f32);
+        assert_eq!("effort".parse::<Objective>().unwrap(), Objective::Effort);
+    }
+
+    #[test]
+    fn max_waste_objective_prefers_balanced_boards() {
+        // both solutions waste the same area, but the second wastes a third of one board
+        let balanced = vec![
+            board(48f32, 8f32, vec![vec![cut(40f32, 8f32, "A")]]),
+            board(48f32, 8f32, vec![vec![cut(40f32, 8f32, "B")]]),
+        ];
+        let lopsided = vec![
+            board(48f32, 8f32, vec![vec![cut(48f32, 8f32, "A")]]),
+            board(48f32, 8f32, vec![vec![cut(32f32, 8f32, "B")]]),
+        ];
+        assert!((max_board_waste(&balanced) - 1f32 / 6f32).abs() < 1e-6);
+        assert!((max_board_waste(&lopsided) - 1f32 / 3f32).abs() < 1e-6);
+        assert_eq!(max_board_waste(&[]), 0f32);
+
+        let options = Options {
+            objective: Objective::MaxWaste,
+            ..Default::default()
+        };
+        let mut solutions = vec![lopsided, balanced];
+        rank(&mut solutions, &input(&[]), &options);
+        assert_eq!(solutions[0][0].cuts()[0].length, 40f32);
+        assert_eq!(
+            "max-waste".parse::<Objective>().unwrap(),
+            Objective::MaxWaste
+        );
+    }
+
+    #[test]
+    fn bill_of_materials_carries_hardware_unchanged() {
+        let model = model::Input::parse(
+            "
+            boards: [96x8:A, 48x6:B]
+            cutlist: [2@90x8:Side, 1@40x6:Shelf]
+            hardware:
+              - { name: Hinge, quantity: 4, note: brass }
+              - { name: Screw, quantity: 24 }
+            ",
+            model::InputFormat::Yaml,
+        )
+        .unwrap();
+        let solution = compute(&model, &Options::default()).unwrap().remove(0);
+        let bill = bill_of_materials(&model, &solution);
+        assert_eq!(bill.hardware, model.hardware);
+        let counted: usize = bill.stock.iter().map(|line| line.count).sum();
+        assert_eq!(counted, solution.len());
+        assert_eq!(bill.stock[0].to_string(), "2 x A (96 by 8)");
+
+        let json: serde_json::Value = serde_json::to_value(&bill).unwrap();
+        assert_eq!(json["hardware"][0]["name"], "Hinge");
+        assert_eq!(json["hardware"][0]["quantity"], 4);
+        assert_eq!(json["hardware"][0]["note"], "brass");
+        assert!(json["hardware"][1]["note"].is_null());
+        let text = bill.to_string();
+        assert!(text.contains("Hardware:\n    4 x Hinge (brass)\n    24 x Screw\n"));
+    }
+
+    #[test]
+    fn reserve_board_prefers_a_spare() {
+        // both solutions use the same area, but the first leaves the second board nearly empty
+        let concentrated = vec![
+            board(96f32, 8f32, vec![vec![cut(80f32, 8f32, "A")]]),
+            board(96f32, 8f32, vec![vec![cut(16f32, 8f32, "B")]]),
+        ];
+        let spread = vec![
+            board(96f32, 8f32, vec![vec![cut(48f32, 8f32, "A")]]),
+            board(96f32, 8f32, vec![vec![cut(48f32, 8f32, "B")]]),
+        ];
+        assert!(reserve_score(&concentrated) > 0f32);
+        assert_eq!(reserve_score(&spread), 0f32);
+
+        let options = Options {
+            reserve_board: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            compare(&concentrated, &spread, &input(&[]), &options),
+            Ordering::Less
+        );
+        let mut solutions = vec![spread.clone(), concentrated];
+        rank(&mut solutions, &input(&[]), &options);
+        assert_eq!(solutions[0][1].cuts()[0].id, "B");
+        assert!((solutions[0][1].fill() - 1f32 / 6f32).abs() < 1e-6);
+    }
+
+    #[test]
+    fn score_breakdown_matches_board_score() {
+        let board = board(
+            96f32,
+            8f32,
+            vec![
+                vec![cut(40f32, 4f32, "A"), cut(30f32, 3f32, "B")],
+                vec![cut(20f32, 8f32, "C")],
+            ],
+        );
+        let breakdown = board.score_breakdown().unwrap();
+        assert_eq!(Some(breakdown.score), board.score());
+        assert_eq!(breakdown.concentration, board.offcut_concentration());
+        assert_eq!(breakdown.scrap_area, board.scrap_area());
+        let density: f32 = board.stacks.iter().map(|stack| stack.score()).product();
+        assert!((breakdown.density - density).abs() < 1e-6);
+        let shape = 1f32 - OFFCUT_CONCENTRATION_WEIGHT * (1f32 - breakdown.concentration);
+        assert!((breakdown.score - density * shape).abs() < 1e-6);
+
+        assert!(Board::from(&model::Board::parse("96x8:A").unwrap())
+            .score_breakdown()
+            .is_none());
+    }
+
+    #[test]
+    fn score_favors_concentrated_offcuts() {
+        // both boards use the same area with perfectly dense stacks, but the
+        // second scatters its waste between a strip and the tail
+        let concentrated = board(96f32, 8f32, vec![vec![cut(40f32, 8f32, "A")]]);
+        let scattered = board(
+            96f32,
+            8f32,
+            vec![vec![cut(40f32, 4f32, "A")], vec![cut(20f32, 8f32, "B")]],
+        );
+        assert!((concentrated.scrap_area() - scattered.scrap_area()).abs() < 1e-4);
+        assert!(concentrated.score().unwrap() > scattered.score().unwrap());
+    }
+
+    #[test]
+    fn preview_estimates_from_area() {
+        let mut model = input(&["4@24x6:Shelf", "2@48x3:Rail"]);
+        model.boards = vec![
+            model::Board::parse("96x8:A").unwrap(),
+            model::Board::parse("48x6:B").unwrap(),
+        ];
+
+        // 4 * 144 + 2 * 144 = 864 sq in, which needs at least 2 of the 768 sq in boards
+        let preview = preview(&model);
+        assert_eq!(preview.total_cut_area, 864f32);
+        assert_eq!(preview.cut_board_feet, 6f32);
+        assert_eq!(preview.estimated_boards, 2);
+        assert_eq!(theoretical_min_boards(&model), 2);
+
+        // spacing is included in the area
+        model.spacing = 1f32;
+        assert_eq!(
+            super::preview(&model).total_cut_area,
+            4f32 * 25f32 * 7f32 + 2f32 * 49f32 * 4f32
+        );
+    }
+
+    #[test]
+    fn max_board_width_leaves_wider_boards_unused() {
+        let mut model = input(&["4@30x3:Rail"]);
+        model
+            .boards
+            .push(model::Board::parse("96x5:Narrow").unwrap());
+        let options = Options {
+            max_board_width: Some(6f32),
+            ..Default::default()
+        };
+        let solution = compute(&model, &options).unwrap().remove(0);
+        assert!(solution.iter().all(|board| board.id == "Narrow"));
+
+        // none of the boards narrow enough can take the wide cut
+        model.cutlist.push(model::Cut::parse("1@30x7:Top").unwrap());
+        let error = compute(&model, &options).unwrap_err().to_string();
+        assert!(error.contains("at most 6 wide"), "{}", error);
+
+        model.boards.pop();
+        let error = compute(&model, &options).unwrap_err().to_string();
+        assert!(error.contains("no boards are at most 6 wide"), "{}", error);
+    }
+
+    #[test]
+    fn max_board_types_caps_distinct_board_types() {
+        let mut model = input(&["2@90x7:Top", "4@90x3.5:Rail"]);
+        model.boards = vec![
+            model::Board::parse("96x8:A").unwrap(),
+            model::Board::parse("96x4:B").unwrap(),
+        ];
+        let board_types = |solution: &[Board]| {
+            let mut ids: Vec<&str> = solution.iter().map(|b| b.id.as_str()).collect();
+            ids.sort_unstable();
+            ids.dedup();
+            ids.len()
+        };
+
+        // the rails pack tighter on the narrow B boards
+        let uncapped = &compute(&model, &Options::default()).unwrap()[0];
+        assert_eq!(board_types(uncapped), 2);
+
+        let options = Options {
+            result_count: 16,
+            max_board_types: Some(1),
+            ..Default::default()
+        };
+        let capped = compute(&model, &options).unwrap();
+        for solution in &capped {
+            assert_eq!(board_types(solution), 1);
+            assert!(solution.iter().all(|board| board.id == "A"));
+        }
+    }
+
+    #[test]
+    fn placement_order_has_one_entry_per_cut() {
+        let mut model = input(&["4@25x2:Leg", "3@14x5:Slat", "2@12x4:Apron"]);
+        model.same_board_groups = vec![vec!["Apron".to_owned()]];
+        let options = Options {
+            result_count: 8,
+            ..Default::default()
+        };
+
+        for solution in compute(&model, &options).unwrap() {
+            let mut order: Vec<usize> = solution
+                .iter()
+                .flat_map(|board| board.cuts())
+                .map(|cut| cut.placement)
+                .collect();
+            order.sort_unstable();
+            assert_eq!(order, (0..9).collect::<Vec<usize>>());
+        }
+    }
+
+    // asserts no two cuts of the board overlap, per the offsets of the flat cut table, and that
+    // each lies within the board
+    fn assert_no_overlaps(board: &Board) {
+        let rects: Vec<Rect> = flat_cut_table(std::slice::from_ref(board))
+            .iter()
+            .map(|row| Rect {
+                x: row.x,
+                y: row.y,
+                length: row.length,
+                width: row.width,
+            })
+            .collect();
+        for (i, a) in rects.iter().enumerate() {
+            assert!(
+                a.x + a.length <= board.length + CROSSCUT_EPSILON
+                    && a.y + a.width <= board.width + CROSSCUT_EPSILON,
+                "{:?} overruns {:?}",
+                a,
+                board
+            );
+            for b in &rects[i + 1..] {
+                assert!(!a.overlaps(b), "{:?} overlaps {:?} on {:?}", a, b, board);
+            }
+        }
+        assert!(board.is_guillotine_valid());
+    }
+
+    #[test]
+    fn accepted_cuts_never_overlap() {
+        for (mut board, options, cuts) in random_board_trials(0) {
+            for cut in &cuts {
+                board.accept(cut, &options);
+                assert_no_overlaps(&board);
+            }
+        }
+
+        for seed in 0..32 {
+            let model = model::Input::parse(
+                &crate::generate::random_input(8, seed),
+                model::InputFormat::Yaml,
+            )
+            .unwrap();
+            let options = Options {
+                attempts: 16,
+                result_count: 4,
+                seed,
+                ..Default::default()
+            };
+            for board in compute(&model, &options).unwrap().iter().flatten() {
+                assert_no_overlaps(board);
+            }
+        }
+    }
+
+    #[test]
+    fn warm_start_keeps_unchanged_cuts_in_place() {
+        let mut model = input(&["4@25x2:Leg", "2@12x4:Apron", "2@40x6:Top"]);
+        model.boards.push(model::Board::parse("48x6:B").unwrap());
+        let previous = compute(&model, &Options::default()).unwrap().remove(0);
+        let board_of = |solution: &[Board]| -> Vec<(String, String, usize)> {
+            let mut assignments: Vec<(String, String, usize)> = solution
+                .iter()
+                .enumerate()
+                .flat_map(|(i, board)| {
+                    board
+                        .cuts()
+                        .into_iter()
+                        .map(move |cut| (cut.id.clone(), board.id.clone(), i))
+                })
+                .collect();
+            assignments.sort();
+            assignments
+        };
+
+        // the apron grows, so only it is placed anew
+        model.cutlist[1] = model::Cut::parse("2@14x4:Apron").unwrap();
+        let (kept, delta) = warm_start(
+            &model,
+            &previous,
+            expand_cutlist(&model, &Options::default()),
+        );
+        assert_eq!(delta.len(), 2);
+        assert!(delta.iter().all(|cut| cut.name == "Apron"));
+        assert_eq!(
+            kept.iter().map(|board| board.cuts().len()).sum::<usize>(),
+            6
+        );
+
+        let options = Options {
+            warm_start: Some(previous.clone()),
+            ..Default::default()
+        };
+        let solution = compute(&model, &options).unwrap().remove(0);
+        let unchanged =
+            |assignments: Vec<(String, String, usize)>| -> Vec<(String, String, usize)> {
+                assignments
+                    .into_iter()
+                    .filter(|(id, _, _)| id != "Apron")
+                    .collect()
+            };
+        assert_eq!(
+            unchanged(board_of(&solution)),
+            unchanged(board_of(&previous))
+        );
+        assert_eq!(
+            solution
+                .iter()
+                .flat_map(|board| board.cuts())
+                .filter(|cut| cut.name == "Apron" && cut.length == 14f32)
+                .count(),
+            2
+        );
+
+        // the same cutlist keeps every cut in place
+        model.cutlist[1] = model::Cut::parse("2@12x4:Apron").unwrap();
+        let (_, delta) = warm_start(
+            &model,
+            &previous,
+            expand_cutlist(&model, &Options::default()),
+        );
+        assert!(delta.is_empty());
+    }
+
+    #[test]
+    fn can_accept_agrees_with_accept() {
+        for (mut board, options, cuts) in random_board_trials(1) {
+            for cut in &cuts {
+                let accepted = board.clone().accept(cut, &options).is_placed();
+                assert_eq!(board.can_accept(cut, &options), accepted, "{:?}", cut);
+                board.accept(cut, &options);
+            }
+        }
+    }
+
+    #[test]
+    fn offcut_inventory_reparses_as_scrap_stock() {
+        let solution = vec![board(
+            96f32,
+            8f32,
+            vec![
+                vec![cut(40f32, 5f32, "A"), cut(30f32, 3f32, "B")],
+                vec![cut(40f32, 8f32, "C")],
+            ],
+        )];
+        let inventory = offcut_inventory(&solution, MIN_USABLE_OFFCUT);
+        let input = model::Input::parse(
+            &format!("boards: [96x8:Oak]\ncutlist: [1@12x2:Cleat]\n{}", inventory),
+            model::InputFormat::Yaml,
+        )
+        .unwrap();
+
+        // the 10x3 end of B is too short to keep by default; nothing is left beside either stack
+        let scrap: Vec<_> = input.boards[1..]
+            .iter()
+            .map(|board| (board.length, board.width, board.id.as_str()))
+            .collect();
+        assert_eq!(scrap, [(16f32, 8f32, "A offcut 1")]);
+        assert!(input.boards[1..]
+            .iter()
+            .all(|board| board.dressed && board.max_count == Some(1)));
+
+        let kept = offcut_inventory(&solution, (8f32, 2f32));
+        assert_eq!(kept.lines().count(), 3);
+        assert!(kept.contains("\"10x3:A offcut 1!\""), "{}", kept);
+        assert!(kept.contains("\"16x8:A offcut 2!\""), "{}", kept);
+
+        // offcuts of one board are each used, as each has an id of its own
+        let solution = vec![board(
+            96f32,
+            8f32,
+            vec![vec![cut(30f32, 4f32, "A")], vec![cut(30f32, 4f32, "B")]],
+        )];
+        let inventory = offcut_inventory(&solution, MIN_USABLE_OFFCUT);
+        let model = model::Input::parse(
+            &format!("boards: [96x8:Oak]\ncutlist: [2@29x4:Part]\n{}", inventory),
+            model::InputFormat::Yaml,
+        )
+        .unwrap();
+        // the two 30x4 offcuts beside the stacks, and the 36x8 one past them
+        assert_eq!(model.boards.len(), 4, "{}", inventory);
+        let resolved = compute(&model, &Options::default()).unwrap().remove(0);
+        assert_eq!(resolved.len(), 2);
+        assert!(resolved
+            .iter()
+            .all(|board| board.id.starts_with("A offcut") && board.width == 4f32));
+        assert_ne!(resolved[0].id, resolved[1].id);
+    }
+
+    #[test]
+    fn preserve_order_places_cuts_in_cutlist_order() {
+        let model = input(&["1@12x4:Apron", "2@25x2:Leg", "1@40x6:Top", "2@14x5:Slat"]);
+        let options = Options {
+            preserve_order: true,
+            result_count: 8,
+            ..Default::default()
+        };
+        let solutions = compute(&model, &options).unwrap();
+        assert_eq!(solutions.len(), 1);
+
+        let mut cuts: Vec<&Cut> = solutions[0].iter().flat_map(|board| board.cuts()).collect();
+        cuts.sort_by_key(|cut| cut.placement);
+        let names: Vec<&str> = cuts.iter().map(|cut| cut.name.as_str()).collect();
+        assert_eq!(names, vec!["Apron", "Leg", "Leg", "Top", "Slat", "Slat"]);
+
+        // untidied, each stack lists its cuts in the order they were placed
+        for stack in solutions[0].iter().flat_map(|board| &board.stacks) {
+            assert!(stack
+                .cuts
+                .windows(2)
+                .all(|pair| pair[0].placement < pair[1].placement));
+        }
+
+        // deterministic, regardless of attempts and seed
+        let other = compute(
+            &model,
+            &Options {
+                attempts: 3,
+                seed: 99,
+                ..options
+            },
+        )
+        .unwrap();
+        assert_eq!(format!("{:?}", other), format!("{:?}", solutions));
+    }
+
+    #[test]
+    fn search_stops_at_timeout_or_attempts_whichever_is_first() {
+        let model = input(&["4@25x2:Leg", "2@12x4:Apron"]);
+        let attempts_made = |options: &Options| {
+            let mut completed = 0;
+            compute_with_progress(&model, options, |c, _| completed = c).unwrap();
+            completed
+        };
+
+        // the timeout passes immediately, long before the attempts are exhausted
+        let options = Options {
+            attempts: 1_000_000,
+            timeout: Some(Duration::ZERO),
+            ..Default::default()
+        };
+        assert_eq!(attempts_made(&options), 1);
+
+        // the attempts are exhausted long before the timeout
+        let options = Options {
+            attempts: 10,
+            timeout: Some(Duration::from_secs(3600)),
+            ..Default::default()
+        };
+        assert_eq!(attempts_made(&options), 10);
+    }
+
+    #[test]
+    fn full_length_cut_needs_no_crosscut() {
+        let full_length = board(96f32, 8f32, vec![vec![cut(96f32, 4f32, "A")]]);
+        assert!(full_length.crosscuts().is_empty());
+        assert!(!full_length.requires_crosscut_at(96f32));
+
+        let partial = board(
+            96f32,
+            8f32,
+            vec![vec![cut(50f32, 4f32, "A")], vec![cut(46f32, 4f32, "B")]],
+        );
+        assert_eq!(partial.crosscuts(), vec![50f32]);
+
+        let short = board(96f32, 8f32, vec![vec![cut(50f32, 4f32, "A")]]);
+        assert_eq!(short.crosscuts(), vec![50f32]);
+
+        // the solver can vend a board for a cut spanning its full length
+        let model = input(&["1@96x4:Rail"]);
+        let solution = &compute(&model, &Options::default()).unwrap()[0];
+        assert_eq!(solution.len(), 1);
+        assert!(solution[0].crosscuts().is_empty());
+    }
+
+    #[test]
+    fn board_cuts_flattens_accepted_cuts() {
+        let options = Options::default();
+        let mut board: Board = (&model::Board::parse("96x8:A").unwrap()).into();
+        let accepted = vec![
+            cut(40f32, 4f32, "A"),
+            cut(38f32, 3f32, "B"),
+            cut(30f32, 6f32, "C"),
+            cut(10f32, 1f32, "D"),
+        ];
+        for c in &accepted {
+            assert!(board.accept(c, &options).is_placed());
+        }
+        assert!(board.stacks.len() > 1);
+
+        let mut ids: Vec<&str> = board.cuts().iter().map(|c| c.id.as_str()).collect();
+        ids.sort_unstable();
+        assert_eq!(ids, vec!["A", "B", "C", "D"]);
+    }
+
+    #[test]
+    fn find_board_selects_by_id_and_index() {
+        let mut oak_1 = board(96f32, 8f32, vec![vec![cut(40f32, 4f32, "Leg")]]);
+        oak_1.id = "Oak".to_owned();
+        let mut oak_2 = board(96f32, 8f32, vec![vec![cut(40f32, 4f32, "Rail")]]);
+        oak_2.id = "Oak".to_owned();
+        let mut walnut = board(96f32, 6f32, vec![vec![cut(20f32, 4f32, "Pull")]]);
+        walnut.id = "Walnut".to_owned();
+        let solution = vec![oak_1, walnut, oak_2];
+
+        assert_eq!(find_board(&solution, "Oak").unwrap().cuts()[0].id, "Leg");
+        assert_eq!(find_board(&solution, "Oak#1").unwrap().cuts()[0].id, "Leg");
+        assert_eq!(find_board(&solution, "Oak#2").unwrap().cuts()[0].id, "Rail");
+        assert_eq!(
+            find_board(&solution, "Walnut").unwrap().cuts()[0].id,
+            "Pull"
+        );
+        assert!(find_board(&solution, "Oak#3").is_none());
+        assert!(find_board(&solution, "Oak#0").is_none());
+        assert!(find_board(&solution, "Cherry").is_none());
+    }
+
+    #[test]
+    fn operation_order_groups_identical_settings() {
+        let solution = vec![
+            board(
+                96f32,
+                8f32,
+                vec![
+                    vec![cut(30f32, 4f32, "A"), cut(20f32, 3f32, "B")],
+                    vec![cut(24f32, 3f32, "C")],
+                ],
+            ),
+            board(
+                96f32,
+                6f32,
+                vec![
+                    vec![cut(24f32, 4f32, "D")],
+                    vec![cut(30f32, 2f32, "E"), cut(30f32, 3f32, "F")],
+                ],
+            ),
+        ];
+        let steps = optimize_operation_order(&solution);
+
+        // every stack is crosscut, every cut ripped, and only B is shorter than its stack
+        let count = |operation| steps.iter().filter(|s| s.operation == operation).count();
+        assert_eq!(count(Operation::Crosscut), 4);
+        assert_eq!(count(Operation::Rip), 6);
+        assert_eq!(count(Operation::Trim), 1);
+
+        // phases are in dependency order
+        let phases: Vec<Operation> = steps.iter().map(|s| s.operation).collect();
+        let mut sorted_phases = phases.clone();
+        sorted_phases.sort_by_key(|o| *o as usize);
+        assert_eq!(phases, sorted_phases);
+
+        // each (operation, setting) pair appears as a single contiguous run
+        let mut seen: Vec<(Operation, f32)> = Vec::new();
+        for (i, step) in steps.iter().enumerate() {
+            let key = (step.operation, step.setting);
+            if i > 0 && (steps[i - 1].operation, steps[i - 1].setting) == key {
+                continue;
+            }
+            assert!(!seen.contains(&key), "{:?} is not contiguous", key);
+            seen.push(key);
+        }
+    }
+
+    #[test]
+    fn isolated_cuts_never_share_a_stack() {
+        // without isolation, the rails would stack beside the stiles
+        let mut model = input(&["4@30x2:Stile", "4@28x2:Rail"]);
+        model.isolate = vec!["Rail".to_owned()];
+        let options = Options {
+            attempts: 32,
+            result_count: 32,
+            ..Default::default()
+        };
+        for solution in compute(&model, &options).unwrap() {
+            for stack in solution.iter().flat_map(|board| &board.stacks) {
+                if stack.cuts.iter().any(|cut| cut.name == "Rail") {
+                    assert_eq!(stack.cuts.len(), 1);
+                }
+            }
+        }
+
+        let error = model::Input::parse(
+            "boards: [96x8:A]\ncutlist: [1@12x4:Apron]\nisolate: [Rail]",
+            model::InputFormat::Yaml,
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("unknown cut"));
+    }
+
+    #[test]
+    fn longest_board_and_stack_span_the_solution() {
+        let solution = vec![
+            board(
+                72f32,
+                8f32,
+                vec![vec![cut(40f32, 4f32, "A")], vec![cut(20f32, 4f32, "B")]],
+            ),
+            board(
+                96f32,
+                6f32,
+                vec![
+                    vec![cut(30f32, 3f32, "C"), cut(10f32, 3f32, "D")],
+                    vec![cut(45f32, 2f32, "E")],
+                ],
+            ),
+        ];
+        assert_eq!(longest_board(&solution).unwrap().nominal_length, 96f32);
+        assert_eq!(longest_stack(&solution), 45f32);
+
+        assert!(longest_board(&[]).is_none());
+        assert_eq!(longest_stack(&[]), 0f32);
+    }
+
+    #[test]
+    fn snap_rounds_to_the_increment() {
+        assert_eq!(snap(23.87, 1f32 / 16f32), 23.875);
+        assert_eq!(snap(23.9, 1f32 / 16f32), 23.875);
+        assert_eq!(snap(23.87, 0.5), 24f32);
+        assert_eq!(snap(23.7, 0.5), 23.5);
+        assert_eq!(snap(0.2, 0.5), 0f32);
+
+        // the cuts abut, so rounding B's offset down to 10 overlaps it with A, while rounding
+        // it up to 10.125 leaves a gap
+        let solution = vec![board(
+            96f32,
+            8f32,
+            vec![vec![cut(10.1, 4f32, "A")], vec![cut(10f32, 4f32, "B")]],
+        )];
+        assert_eq!(snap_overlaps(&solution, 0.5), vec!["A", "B"]);
+        assert!(snap_overlaps(&solution, 1f32 / 16f32).is_empty());
+    }
+
+    #[test]
+    fn zero_count_cuts_are_ignored() {
+        assert!(model::Cut::parse("0@12x4:Apron").is_err());
+
+        // only reachable by building the input in code
+        let mut model = input(&["2@20x3:Rail"]);
+        model.cutlist.push(model::Cut {
+            count: model::CountSpec::Fixed(0),
+            ..model::Cut::parse("1@12x4:Apron").unwrap()
+        });
+        let cutlist = expand_cutlist(&model, &Options::default());
+        assert_eq!(cutlist.len(), 2);
+        assert!(cutlist.iter().all(|cut| cut.name == "Rail"));
+
+        let solution = compute(&model, &Options::default()).unwrap().remove(0);
+        let placed: usize = solution.iter().map(|board| board.cuts().len()).sum();
+        assert_eq!(placed, 2);
+    }
+
+    #[test]
+    fn stack_fit_tolerance_limits_stack_reuse() {
+        let place = |tolerance: f32| {
+            let options = Options {
+                stack_fit_tolerance: tolerance,
+                ..Default::default()
+            };
+            let mut board: Board = (&model::Board::parse("96x8:A").unwrap()).into();
+            board.accept(&cut(40f32, 2f32, "A"), &options);
+            // a quarter shorter than the stack
+            board.accept(&cut(30f32, 2f32, "B"), &options)
+        };
+
+        // tighter than the difference forces a new stack
+        assert_eq!(place(0.1), PlacementOutcome::NewStack);
+        // looser reuses the existing one
+        assert_eq!(
+            place(0.5),
+            PlacementOutcome::ExistingStack {
+                length_difference: 10f32
+            }
+        );
+        assert_eq!(Options::default().stack_fit_tolerance, 0.5);
+    }
+
+    #[test]
+    fn accept_explains_each_outcome() {
+        let options = Options::default();
+        let mut board: Board = (&model::Board::parse("96x8:A").unwrap()).into();
+
+        let outcome = board.accept(&cut(40f32, 4f32, "A"), &options);
+        assert_eq!(outcome, PlacementOutcome::NewBoard);
+        assert_eq!(outcome.reason(), "vended new board (no fit)");
+
+        let outcome = board.accept(&cut(38f32, 3f32, "B"), &options);
+        assert_eq!(
+            outcome,
+            PlacementOutcome::ExistingStack {
+                length_difference: 2f32
+            }
+        );
+        assert_eq!(
+            outcome.reason(),
+            "placed in existing stack (best fit, \u{394}len 2.00)"
+        );
+
+        let outcome = board.accept(&cut(30f32, 6f32, "C"), &options);
+        assert_eq!(outcome, PlacementOutcome::NewStack);
+        assert_eq!(outcome.reason(), "opened new stack");
+
+        let outcome = board.accept(&cut(60f32, 6f32, "D"), &options);
+        assert_eq!(outcome, PlacementOutcome::Rejected);
+        assert_eq!(outcome.reason(), "rejected");
+
+        // placed cuts carry their outcome
+        let outcomes: Vec<Option<PlacementOutcome>> =
+            board.cuts().iter().map(|c| c.outcome.clone()).collect();
+        assert_eq!(outcomes.len(), 3);
+        assert!(outcomes
+            .iter()
+            .all(|o| o.as_ref().is_some_and(|o| o.is_placed())));
+    }
+
+    #[test]
+    fn overage_rescues_a_cut_just_over_nominal_length() {
+        let mut model = input(&["1@96.5x4:Rail"]);
+        assert!(compute(&model, &Options::default()).is_err());
+
+        model.boards = vec![model::Board::parse("96x8:A+1").unwrap()];
+        let solutions = compute(&model, &Options::default()).unwrap();
+        assert_eq!(solutions[0].len(), 1);
+        assert_eq!(solutions[0][0].length, 97f32);
+        assert_eq!(solutions[0][0].nominal_length, 96f32);
+    }
+
+    #[test]
+    fn pinned_cuts_stay_on_their_board() {
+        let mut model = input(&["2@24x3:Rail", "4@30x4:Shelf"]);
+        model.boards.push(model::Board::parse("96x6:B").unwrap());
+        // the narrower B would otherwise be vended first for the rails
+        model.pinned.insert("Rail".to_owned(), "A".to_owned());
+        let options = Options {
+            attempts: 64,
+            result_count: 64,
+            ..Default::default()
+        };
+
+        let solutions = compute(&model, &options).unwrap();
+        assert!(solutions.len() > 1);
+        for solution in &solutions {
+            let rail_boards = board_containing(solution, "Rail");
+            assert!(!rail_boards.is_empty());
+            assert!(rail_boards.iter().all(|board| board.id == "A"));
+        }
+    }
+
+    #[test]
+    fn cuts_pin_to_a_lot() {
+        // the narrower Pine would otherwise be vended first for the legs
+        let yaml = "boards: [3@48~6x6:Oak, 96x4:Pine]\ncutlist: [4@20x2:Leg, 2@30x3:Rail]";
+        let model = model::Input::parse(
+            &format!("{}\npinned: {{ Leg: Oak }}", yaml),
+            model::InputFormat::Yaml,
+        )
+        .unwrap();
+        let solution = compute(&model, &Options::default()).unwrap().remove(0);
+        let leg_boards = board_containing(&solution, "Leg");
+        assert!(!leg_boards.is_empty());
+        assert!(leg_boards
+            .iter()
+            .all(|board| board.id.starts_with("Oak #") && board.has_id("Oak")));
+
+        // as does a group's material
+        let grouped = yaml.replace(
+            "cutlist: [4@20x2:Leg, 2@30x3:Rail]",
+            "cutlist:\n  legs: { material: Oak, parts: [4@20x2:Leg] }\n  rails: { parts: [2@30x3:Rail] }",
+        );
+        let model = model::Input::parse(&grouped, model::InputFormat::Yaml).unwrap();
+        assert_eq!(model.pinned["Leg"], "Oak");
+        assert!(compute(&model, &Options::default()).is_ok());
+    }
+
+    #[test]
+    fn infeasible_pin_is_reported() {
+        let mut model = input(&["1@24x7:Panel"]);
+        model.boards.push(model::Board::parse("96x6:B").unwrap());
+        model.pinned.insert("Panel".to_owned(), "B".to_owned());
+        let error = compute(&model, &Options::default()).unwrap_err();
+        assert!(error.to_string().contains("pinned"));
+    }
+
+    #[test]
+    fn pins_respect_max_count() {
+        let mut model = input(&["2@60x4:Shelf"]);
+        model.pinned.insert("Shelf".to_owned(), "A".to_owned());
+        assert!(compute(&model, &Options::default()).is_ok());
+
+        // each shelf needs a board of its own, but only one A is in stock
+        model.boards[0].max_count = Some(1);
+        assert!(compute(&model, &Options::default()).is_err());
+    }
+
+    #[test]
+    fn pins_respect_max_board_types() {
+        let mut model = input(&["1@24x3:Rail", "1@30x4:Shelf"]);
+        model.boards.push(model::Board::parse("96x6:B").unwrap());
+        model.pinned.insert("Rail".to_owned(), "A".to_owned());
+        model.pinned.insert("Shelf".to_owned(), "B".to_owned());
+        assert!(compute(&model, &Options::default()).is_ok());
+
+        // the pins need two board types, but only one may be vended
+        let options = Options {
+            max_board_types: Some(1),
+            ..Default::default()
+        };
+        assert!(compute(&model, &options).is_err());
+    }
+
+    #[test]
+    fn shape_survives_solving() {
+        let model = input(&["1@12x6:Bracket#curved", "2@30x4:Rail"]);
+        let solutions = compute(&model, &Options::default()).unwrap();
+        let cuts: Vec<&Cut> = solutions[0].iter().flat_map(|board| board.cuts()).collect();
+        let bracket = cuts.iter().find(|cut| cut.name == "Bracket").unwrap();
+        assert_eq!(bracket.shape.as_deref(), Some("curved"));
+        assert_eq!((bracket.length, bracket.width), (12f32, 6f32));
+        assert!(cuts
+            .iter()
+            .filter(|cut| cut.name == "Rail")
+            .all(|cut| cut.shape.is_none()));
+
+        let json = serde_json::to_string(&solutions[0]).unwrap();
+        assert!(json.contains("\"shape\":\"curved\""));
+    }
+
+    #[test]
+    fn tidy_preserves_cuts_in_a_deterministic_order() {
+        let cuts = |board: &Board| -> Vec<(String, f32, f32)> {
+            board
+                .cuts()
+                .iter()
+                .map(|cut| (cut.id.clone(), cut.length, cut.width))
+                .collect()
+        };
+        let sorted = |mut cuts: Vec<(String, f32, f32)>| {
+            cuts.sort_by(|a, b| a.0.cmp(&b.0));
+            cuts
+        };
+
+        let mut a = board(
+            96f32,
+            8f32,
+            vec![
+                vec![cut(20f32, 2f32, "B"), cut(30f32, 3f32, "C")],
+                vec![cut(40f32, 4f32, "A"), cut(10f32, 1f32, "D")],
+            ],
+        );
+        let mut b = board(
+            96f32,
+            8f32,
+            vec![
+                vec![cut(10f32, 1f32, "D"), cut(40f32, 4f32, "A")],
+                vec![cut(30f32, 3f32, "C"), cut(20f32, 2f32, "B")],
+            ],
+        );
+        let before = cuts(&a);
+        a.tidy();
+        b.tidy();
+        assert_eq!(sorted(cuts(&a)), sorted(before));
+        assert_eq!(cuts(&a), cuts(&b));
+
+        // longest stack first, and longest cut first within each stack
+        let ids: Vec<Vec<&str>> = a
+            .stacks
+            .iter()
+            .map(|stack| stack.cuts.iter().map(|cut| cut.id.as_str()).collect())
+            .collect();
+        assert_eq!(ids, vec![vec!["A", "D"], vec!["C", "B"]]);
+    }
+
+    #[test]
+    fn sheet_packer_places_cuts_without_overlap() {
+        let mut sheet = SheetPacker::new(96f32, 48f32, "Ply");
+        let cuts = vec![
+            cut(48f32, 24f32, "Side"),
+            cut(48f32, 24f32, "Side"),
+            cut(30f32, 20f32, "Shelf"),
+            cut(30f32, 20f32, "Shelf"),
+            cut(20f32, 10f32, "Drawer"),
+            cut(20f32, 10f32, "Drawer"),
+            cut(8f32, 4f32, "Cleat"),
+            cut(8f32, 4f32, "Cleat"),
+        ];
+        for cut in &cuts {
+            assert!(sheet.insert(cut), "Expected {} to fit", cut.id);
+        }
+        assert!(!sheet.insert(&cut(100f32, 10f32, "Too Long")));
+        assert_eq!(sheet.placements.len(), cuts.len());
+
+        let bounds = Rect {
+            x: 0f32,
+            y: 0f32,
+            length: sheet.length,
+            width: sheet.width,
+        };
+        for (i, a) in sheet.placements.iter().enumerate() {
+            let rect = a.rect();
+            assert!(rect.x >= bounds.x && rect.x + rect.length <= bounds.length);
+            assert!(rect.y >= bounds.y && rect.y + rect.width <= bounds.width);
+            for b in &sheet.placements[i + 1..] {
+                assert!(
+                    !rect.overlaps(&b.rect()),
+                    "{} overlaps {}",
+                    a.cut.id,
+                    b.cut.id
+                );
+            }
+        }
+        let total_area: f32 = cuts.iter().map(|cut| cut.length * cut.width).sum();
+        assert_eq!(sheet.used_area(), total_area);
+        // too wide to fit across the sheet, but fits turned along its length
+        let mut sheet = SheetPacker::new(96f32, 48f32, "Ply");
+        assert!(sheet.insert(&cut(40f32, 60f32, "Divider")));
+        assert!(sheet.placements[0].rotated);
+        assert_eq!(sheet.placements[0].rect().length, 60f32);
+
+        // unless it's grained, which it can only be laid out along the sheet's length
+        let mut sheet = SheetPacker::new(96f32, 48f32, "Ply");
+        let grained = |length, width, id| Cut {
+            grain: true,
+            ..cut(length, width, id)
+        };
+        assert!(!sheet.insert(&grained(40f32, 60f32, "Divider")));
+        assert!(!sheet.insert_oriented(&grained(60f32, 40f32, "Door"), Some(true)));
+        assert!(sheet.insert(&grained(60f32, 40f32, "Door")));
+        assert!(!sheet.placements[0].rotated);
+        assert!(sheet
+            .placements
+            .iter()
+            .all(|placement| !placement.cut.rotated));
+    }
+
+    #[test]
+    fn cuts_overlapping_a_defect_are_rejected() {
+        let options = Options::default();
+        let knot = Rect {
+            x: 0f32,
+            y: 5f32,
+            length: 4f32,
+            width: 3f32,
+        };
+        let mut board = Board {
+            defects: vec![knot],
+            ..board(96f32, 8f32, vec![])
+        };
+        assert!(!board
+            .accept(&cut(40f32, 6f32, "Apron"), &options)
+            .is_placed());
+        assert!(board
+            .accept(&cut(10f32, 4f32, "Block"), &options)
+            .is_placed());
+        // the first stack would carry this cut over the knot, so it opens a stack of its own
+        assert_eq!(
+            board.accept(&cut(10f32, 2f32, "Strip"), &options),
+            PlacementOutcome::NewStack
+        );
+        assert!(board
+            .accept(&cut(40f32, 8f32, "Panel"), &options)
+            .is_placed());
+        assert_eq!(board.stacks.len(), 3);
+        assert!(board.cut_rects().iter().all(|rect| !rect.overlaps(&knot)));
+
+        // tidying would put the panel over the knot, so leaves the board be
+        board.tidy();
+        assert_eq!(board.stacks[0].cuts[0].id, "Block");
+
+        // sheets are packed around their defects
+        let mut sheet = SheetPacker::new(96f32, 48f32, "Ply").with_defects(&[Rect {
+            x: 40f32,
+            y: 0f32,
+            length: 8f32,
+            width: 48f32,
+        }]);
+        assert!(!sheet.insert(&cut(60f32, 40f32, "Top")));
+        assert!(sheet.insert(&cut(40f32, 40f32, "Side")));
+        assert!(sheet.insert(&cut(48f32, 40f32, "Side")));
+        for placement in &sheet.placements {
+            assert!(!placement.rect().overlaps(&sheet.defects[0]));
+        }
+    }
+
+    #[test]
+    fn end_trim_leaves_less_of_each_board_for_cuts() {
+        let model = model::Input::parse(
+            "end_trim: 1\nboards: [96x8:A]\ncutlist: [1@95x4:Top]",
+            model::InputFormat::Yaml,
+        )
+        .unwrap();
+        let solution = compute(&model, &Options::default()).unwrap().remove(0);
+        assert_eq!(solution[0].length, 95f32);
+        assert_eq!(solution[0].end_trims, (1f32, 0f32));
+
+        // a cut needing the trimmed length no longer fits
+        let mut board = Board::from(&model.boards[0]);
+        assert!(!board
+            .accept(&cut(96f32, 4f32, "Top"), &Options::default())
+            .is_placed());
+        let model = model::Input::parse(
+            "end_trim: 1\nboards: [96x8:A]\ncutlist: [1@96x4:Top]",
+            model::InputFormat::Yaml,
+        )
+        .unwrap();
+        assert!(compute(&model, &Options::default()).is_err());
+    }
+
+    #[test]
+    fn cuts_go_on_boards_thick_enough_for_them() {
+        let solve = |boards: &str, cutlist: &str| {
+            let model = model::Input::parse(
+                &format!("boards: [{}]\ncutlist: [{}]", boards, cutlist),
+                model::InputFormat::Yaml,
+            )
+            .unwrap();
+            compute(&model, &Options::default())
+        };
+
+        // a thicker board accepts the cut, a thinner one never does
+        let solution = solve("96x8x0.5:Thin, 96x8x1:Thick", "2@30x4x0.75:Apron")
+            .unwrap()
+            .remove(0);
+        assert!(solution.iter().all(|board| board.id == "Thick"));
+        assert!(solve("96x8x0.5:Thin", "2@30x4x0.75:Apron").is_err());
+
+        // cuts without a thickness still go anywhere
+        let solution = solve("96x8x0.5:Thin", "2@30x4:Apron").unwrap().remove(0);
+        assert_eq!(solution[0].id, "Thin");
+
+        let mut board = board(96f32, 8f32, Vec::new());
+        board.thickness = Some(1f32);
+        board.planing_allowance = Some(0.125);
+        let mut thin = cut(30f32, 4f32, "Apron");
+        thin.thickness = Some(0.75);
+        assert!(!board.accept(&thin, &Options::default()).is_placed());
+        thin.thickness = Some(0.875);
+        assert!(board.accept(&thin, &Options::default()).is_placed());
+    }
+
+    #[test]
+    fn convergence_reports_the_attempt_finding_the_best() {
+        let model = model::Input::parse(
+            &crate::generate::random_input(12, 3),
+            model::InputFormat::Yaml,
+        )
+        .unwrap();
+        let options = Options {
+            attempts: 200,
+            ..Default::default()
+        };
+        let (solutions, convergence) =
+            compute_with_convergence(&model, &options, |_, _| {}).unwrap();
+        assert_eq!(convergence.attempts, 200);
+        assert!(convergence.improvements.windows(2).all(|w| w[0] < w[1]));
+        let best_at = convergence.best_found_at().unwrap();
+
+        // the shuffles are seeded, so the first N attempts of any run are the same: stopping at the
+        // reported attempt finds the best, and stopping before it doesn't
+        let best_of_first = |attempts: usize| {
+            compute(
+                &model,
+                &Options {
+                    attempts,
+                    ..options.clone()
+                },
+            )
+            .unwrap()
+            .remove(0)
+        };
+        let best = best_of_first(best_at);
+        assert_eq!(score(&best), score(&solutions[0]));
+        assert_eq!(best.len(), solutions[0].len());
+        if best_at > 1 {
+            assert_eq!(
+                compare_by_preference(&best, &best_of_first(best_at - 1), &model, &options),
+                Ordering::Less
+            );
+        }
+        assert_eq!(convergence.converged_early(), best_at <= 100);
+        assert!(convergence
+            .to_string()
+            .starts_with(&format!("Best found at attempt {} of 200", best_at)));
+    }
+
+    #[test]
+    fn shuffling_ties_keeps_cuts_in_area_order() {
+        // 12x4 and 24x2, 6x8 and 8x6 tie in area
+        let mut cutlist = vec![
+            cut(12f32, 4f32, "Rail"),
+            cut(24f32, 2f32, "Slat"),
+            cut(6f32, 8f32, "Block"),
+            cut(8f32, 6f32, "Pad"),
+            cut(30f32, 4f32, "Top"),
+            cut(2f32, 2f32, "Peg"),
+        ];
+        let mut rng = Pcg64::seed_from_u64(1);
+        let mut orders = std::collections::HashSet::new();
+        for _ in 0..16 {
+            sort_by_area_shuffling_ties(&mut cutlist, &mut rng);
+            let areas: Vec<f32> = cutlist.iter().map(|cut| cut.length * cut.width).collect();
+            assert_eq!(areas, vec![4f32, 48f32, 48f32, 48f32, 48f32, 120f32]);
+            orders.insert(cutlist.iter().map(|cut| cut.id.clone()).collect::<Vec<_>>());
+        }
+        // only the ties move
+        assert!(orders.len() > 1);
+        assert!(orders
+            .iter()
+            .all(|order| order[0] == "Peg" && order[5] == "Top"));
+
+        let model = input(&["2@12x4:Rail", "2@24x2:Slat", "1@40x6:Top"]);
+        let solutions = compute(
+            &model,
+            &Options {
+                attempts: 16,
+                shuffle_ties: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        for solution in &solutions {
+            let first = solution
+                .iter()
+                .flat_map(|board| board.cuts())
+                .min_by_key(|cut| cut.placement)
+                .unwrap();
+            assert_eq!(first.name, "Top");
+        }
+    }
+
+    #[test]
+    fn placements_yield_every_cut_once_at_its_offset() {
+        let solution = vec![
+            board(
+                96f32,
+                8f32,
+                vec![
+                    vec![cut(30f32, 4f32, "A1"), cut(20f32, 3f32, "A2")],
+                    vec![cut(40f32, 2f32, "A3")],
+                ],
+            ),
+            board(96f32, 8f32, vec![vec![cut(10f32, 8f32, "B1")]]),
+        ];
+        let found: Vec<(&str, &str, f32, f32)> = placements(&solution)
+            .map(|placement| {
+                (
+                    placement.board.id.as_str(),
+                    placement.cut.id.as_str(),
+                    placement.x,
+                    placement.y,
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("A", "A1", 0f32, 0f32),
+                ("A", "A2", 0f32, 4f32),
+                ("A", "A3", 30f32, 0f32),
+                ("A", "B1", 0f32, 0f32),
+            ]
+        );
+        // each placement's board is the one holding its cut
+        assert!(std::ptr::eq(
+            placements(&solution).last().unwrap().board,
+            &solution[1]
+        ));
+        assert_eq!(solution[0].used_bounds(), (70f32, 7f32));
+        assert!(placements(&[]).next().is_none());
+    }
+
+    #[test]
+    fn a_result_count_of_zero_is_refused() {
+        // rather than returning no solutions for the headless output to index into
+        let model = input(&["2@24x4:Shelf"]);
+        let options = Options {
+            result_count: 0,
+            ..Default::default()
+        };
+        let error = compute(&model, &options).unwrap_err();
+        assert!(error.to_string().contains("at least 1"));
+        assert!(compute_streaming(&model, &options).is_err());
+
+        // fast mode ignores the count, returning its one solution
+        let fast = Options {
+            fast: true,
+            ..options
+        };
+        assert_eq!(compute(&model, &fast).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn fast_mode_places_every_cut_in_a_single_pass() {
+        let model = model::Input::parse(
+            &crate::generate::random_input(12, 7),
+            model::InputFormat::Yaml,
+        )
+        .unwrap();
+        let cutlist = expand_cutlist(&model, &Options::default());
+        for polish in [false, true] {
+            let options = Options {
+                fast: true,
+                polish,
+                // ignored when fast
+                attempts: 1_000_000,
+                result_count: 8,
+                ..Default::default()
+            };
+            let start = Instant::now();
+            let solutions = compute(&model, &options).unwrap();
+            assert!(start.elapsed() < Duration::from_secs(1));
+
+            assert_eq!(solutions.len(), 1);
+            let solution = &solutions[0];
+            let placed: usize = solution.iter().map(|board| board.cuts().len()).sum();
+            assert_eq!(placed, cutlist.len());
+            assert!(meets_exact_counts(&model, solution));
+            solution.iter().for_each(assert_no_overlaps);
+        }
+    }
+
+    #[test]
+    fn probes_find_the_boards_with_room_left() {
+        let model = input(&[]);
+        let options = Options::default();
+        let solution = vec![
+            board(96f32, 8f32, vec![vec![cut(40f32, 6f32, "Apron")]]),
+            board(96f32, 8f32, vec![vec![cut(96f32, 8f32, "Top")]]),
+        ];
+        // a stack of its own in the first board's tail, or beside the apron
+        assert_eq!(
+            boards_with_room_for(&model, &solution, (24f32, 4f32), &options),
+            vec![0]
+        );
+        assert_eq!(
+            boards_with_room_for(&model, &solution, (50f32, 1f32), &options),
+            vec![0]
+        );
+        // too long for the tail, and too wide to go beside the apron
+        assert!(boards_with_room_for(&model, &solution, (60f32, 4f32), &options).is_empty());
+
+        // the kerf counts against the room left
+        let model = model::Input {
+            spacing: 1f32,
+            ..model
+        };
+        assert!(boards_with_room_for(&model, &solution, (56f32, 4f32), &options).is_empty());
+        assert_eq!(
+            boards_with_room_for(&model, &solution, (55f32, 4f32), &options),
+            vec![0]
+        );
+    }
+
+    #[test]
+    fn pack_sheets_opens_sheets_as_needed() {
+        let mut model = input(&["3@48x30:Side", "4@20x10:Drawer"]);
+        model.boards = vec![model::Board::parse("96x48:Ply#sheet").unwrap()];
+        assert!(compute(&model, &Options::default()).is_err());
+
+        // two sides fill most of a sheet, so the third needs another
+        let sheets = pack_sheets(&model, &Options::default()).unwrap();
+        assert_eq!(sheets.len(), 2);
+        let placed: usize = sheets.iter().map(|sheet| sheet.placements.len()).sum();
+        assert_eq!(placed, 7);
+
+        model.boards = vec![model::Board::parse("96x48:Ply#sheet=1").unwrap()];
+        assert!(pack_sheets(&model, &Options::default()).is_err());
+    }
+
+    #[test]
+    fn pack_sheets_meets_exact_counts() {
+        // both tops fit one sheet, so a count of exactly two can't be met
+        let mut model = input(&["2@40x40:Top"]);
+        model.boards = vec![model::Board::parse("96x48:Ply#sheet=2").unwrap()];
+        let error = pack_sheets(&model, &Options::default()).unwrap_err();
+        assert!(error.to_string().contains("exactly 2"), "{}", error);
+
+        model.boards = vec![model::Board::parse("96x48:Ply#sheet=1").unwrap()];
+        assert_eq!(pack_sheets(&model, &Options::default()).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn pack_sheets_refuses_fill_cuts() {
+        // rather than silently leaving the slats out
+        let mut model = input(&["fill@24x12:Slat", "1@40x40:Top"]);
+        model.boards = vec![model::Board::parse("96x48:Ply#sheet").unwrap()];
+        let error = pack_sheets(&model, &Options::default()).unwrap_err();
+        assert!(error.to_string().contains("Slat"), "{}", error);
+    }
+
+    #[test]
+    fn uniformly_oriented_cuts_share_one_orientation() {
+        let orientations = |sheets: &[SheetPacker], name: &str| -> Vec<bool> {
+            sheets
+                .iter()
+                .flat_map(|sheet| &sheet.placements)
+                .filter(|placement| placement.cut.name == name)
+                .map(|placement| placement.rotated)
+                .collect()
+        };
+        let yaml = "boards: [96x48:Ply#sheet]\ncutlist: [5@40x18:Side, 4@40x18:Shelf]";
+
+        // left to pack as tightly as they can, copies turn whichever way fits best
+        let model = model::Input::parse(yaml, model::InputFormat::Yaml).unwrap();
+        let sheets = pack_sheets(&model, &Options::default()).unwrap();
+        let sides = orientations(&sheets, "Side");
+        assert!(sides.contains(&true) && sides.contains(&false));
+
+        let model = model::Input::parse(
+            &format!("{}\nuniform_orientation: [Side]", yaml),
+            model::InputFormat::Yaml,
+        )
+        .unwrap();
+        let sheets = pack_sheets(&model, &Options::default()).unwrap();
+        let sides = orientations(&sheets, "Side");
+        assert_eq!(sides.len(), 5);
+        assert!(sides.iter().all(|rotated| *rotated == sides[0]));
+
+        // or every cut's, with the option
+        let model = model::Input::parse(yaml, model::InputFormat::Yaml).unwrap();
+        let options = Options {
+            uniform_orientation: true,
+            ..Default::default()
+        };
+        let sheets = pack_sheets(&model, &options).unwrap();
+        for name in ["Side", "Shelf"] {
+            let rotations = orientations(&sheets, name);
+            assert!(rotations.iter().all(|rotated| *rotated == rotations[0]));
+        }
+
+        assert!(model::Input::parse(
+            &format!("{}\nuniform_orientation: [Door]", yaml),
+            model::InputFormat::Yaml,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn grained_cuts_are_refused_cross_grain() {
+        let options = Options::default();
+        let rail = Cut {
+            grain: true,
+            ..cut(7f32, 3f32, "Rail")
+        };
+        let mut board = board(96f32, 8f32, vec![]);
+        assert!(!board.accept(&rail.clone().rotate(), &options).is_placed());
+        assert!(board.accept(&rail, &options).is_placed());
+        // an ungrained cut may go either way
+        assert!(board
+            .accept(&cut(7f32, 3f32, "Slat").rotate(), &options)
+            .is_placed());
+        assert!(validate_solution(&[board.clone()]).is_ok());
+
+        // a cross-grain placement slipped in around accept is caught
+        board.stacks[0].cuts.push(rail.rotate());
+        let error = validate_solution(&[board]).unwrap_err();
+        assert!(error.to_string().contains("Rail"));
+    }
+
+    #[test]
+    fn cuts_are_never_placed_cross_grain() {
+        // without rotation, each cut's length runs along its board's length
+        let mut model = input(&["2@30x6:Panel", "4@7x2:Block", "3@20x4:Rail#grain"]);
+        model.spacing = 0.25;
+        let solutions = compute(
+            &model,
+            &Options {
+                result_count: 16,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        for board in solutions.iter().flatten() {
+            for cut in board.cuts() {
+                let model_cut = model.cutlist.iter().find(|c| c.name == cut.name).unwrap();
+                assert_eq!(cut.length, model_cut.length + model.spacing);
+                assert_eq!(cut.width, model_cut.width + model.spacing);
+            }
+        }
+        assert!(solutions
+            .iter()
+            .all(|solution| validate_solution(solution).is_ok()));
+    }
+
+    #[test]
+    fn each_blade_takes_its_kerf_in_its_direction() {
+        let mut model = input(&["2@30x2:Rail", "2@20x3:Stile"]);
+        model.spacing = 0.25;
+        model.crosscut_kerf = Some(0.125);
+        model.rip_kerf = Some(0.5);
+        let options = Options {
+            uniform_rips: true,
+            ..Default::default()
+        };
+        let solution = compute(&model, &options).unwrap().remove(0);
+        assert_eq!(solution.len(), 1);
+        for cut in solution[0].cuts() {
+            let model_cut = model.cutlist.iter().find(|c| c.name == cut.name).unwrap();
+            assert_eq!(cut.length, model_cut.length + 0.125);
+            assert_eq!(cut.width, model_cut.width + 0.5);
+        }
+        // the rip kerf separates the pieces of a stack, the crosscut kerf the stacks
+        let stacks = &solution[0].stacks;
+        let mut widths: Vec<_> = stacks.iter().map(|stack| stack.width()).collect();
+        widths.sort_by(|a, b| total_cmp_f32(*a, *b));
+        assert_eq!(widths, [5f32, 7f32]);
+        assert_eq!(solution[0].allocated_length(), 50.25);
+
+        // a blade without its own kerf falls back to the spacing
+        model.rip_kerf = None;
+        let solution = compute(&model, &options).unwrap().remove(0);
+        assert!(solution[0]
+            .cuts()
+            .iter()
+            .all(|cut| cut.width.fract() == 0.25));
+    }
+
+    #[test]
+    fn suggests_the_stock_which_would_place_all_cuts() {
+        // two 90x3 pieces fit each 96x8 board, of which there's only one
+        let mut model = input(&["6@90x3:Rail", "1@100x3:Beam"]);
+        model.boards[0].max_count = Some(1);
+        let options = Options::default();
+        let suggestions = suggest_additional_stock(&model, &options);
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].0.id, "A");
+        assert_eq!(suggestions[0].1, 2);
+
+        // the beam is longer than any board, so more stock wouldn't help it
+        model.cutlist.pop();
+        assert!(compute(&model, &options).is_err());
+        model.boards[0].max_count = Some(3);
+        assert!(compute(&model, &options).is_ok());
+        assert!(suggest_additional_stock(&model, &options).is_empty());
+    }
+
+    // records each message logged by the capturing thread with its level; other tests run in
+    // parallel, and their messages are ignored
+    struct CapturingLogger;
+
+    static CAPTURING_THREAD: std::sync::OnceLock<std::thread::ThreadId> =
+        std::sync::OnceLock::new();
+    static CAPTURED: std::sync::Mutex<Vec<(log::Level, String)>> =
+        std::sync::Mutex::new(Vec::new());
+
+    impl log::Log for CapturingLogger {
+        fn enabled(&self, _: &log::Metadata) -> bool {
+            CAPTURING_THREAD.get() == Some(&std::thread::current().id())
+        }
+
+        fn log(&self, record: &log::Record) {
+            if self.enabled(record.metadata()) {
+                CAPTURED
+                    .lock()
+                    .unwrap()
+                    .push((record.level(), record.args().to_string()));
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    #[test]
+    fn compute_logs_at_each_level() {
+        CAPTURING_THREAD.set(std::thread::current().id()).unwrap();
+        log::set_logger(&CapturingLogger).unwrap();
+        log::set_max_level(log::LevelFilter::Debug);
+
+        let mut model = input(&["2@24x4:Shelf"]);
+        model.cutlist.push(model::Cut {
+            count: model::CountSpec::Fixed(0),
+            ..model::Cut::parse("1@12x2:Cleat").unwrap()
+        });
+        compute(
+            &model,
+            &Options {
+                attempts: 0,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let logged = CAPTURED.lock().unwrap().clone();
+        let at = |level: log::Level| -> Vec<&str> {
+            logged
+                .iter()
+                .filter(|(l, _)| *l == level)
+                .map(|(_, message)| message.as_str())
+                .collect()
+        };
+        assert_eq!(
+            at(log::Level::Warn),
+            ["Ignoring Cleat, whose count of 0 makes no pieces"]
+        );
+        assert_eq!(at(log::Level::Info), ["Found 1 viable solutions"]);
+        // the placement of each shelf, which RUST_LOG=debug shows
+        let placements = at(log::Level::Debug);
+        assert!(placements[0].starts_with("Placed Shelf on new board 0 (A)"));
+        assert!(placements[1].starts_with("Placed Shelf on board 0 (A)"));
+    }
+
+    #[test]
+    fn equal_scores_rank_in_a_determined_order() {
+        let full = |name: &str| board(96f32, 8f32, vec![vec![cut(96f32, 8f32, name)]]);
+        let one_a = vec![full("A")];
+        let one_b = vec![full("B")];
+        let two = vec![full("A"), full("B")];
+        assert_eq!(score(&one_a), score(&two));
+
+        let model = input(&[]);
+        let expected: Vec<String> = [&one_a, &one_b, &two]
+            .iter()
+            .map(|solution| fingerprint(solution))
+            .collect();
+        for mut solutions in [
+            vec![two.clone(), one_b.clone(), one_a.clone()],
+            vec![one_b.clone(), one_a.clone(), two.clone()],
+            vec![one_a.clone(), two.clone(), one_b.clone()],
+        ] {
+            rank(&mut solutions, &model, &Options::default());
+            let ranked: Vec<String> = solutions.iter().map(|s| fingerprint(s)).collect();
+            assert_eq!(ranked, expected);
+        }
+        assert_eq!(fingerprint(&two), "A 96x8: [A 96x8]; A 96x8: [B 96x8]");
+    }
+
+    #[test]
+    fn sequential_sets_stay_side_by_side_in_one_stack() {
+        let mut model = input(&["3@30x2:Panel#sequential", "4@40x3:Rail", "6@20x1:Slat"]);
+        let options = Options {
+            number_cuts: true,
+            ..Default::default()
+        };
+        for solution in compute(&model, &options).unwrap() {
+            let stacks: Vec<&CutStack> = solution
+                .iter()
+                .flat_map(|board| &board.stacks)
+                .filter(|stack| stack.cuts.iter().any(|cut| cut.name == "Panel"))
+                .collect();
+            assert_eq!(stacks.len(), 1);
+            let names: Vec<&str> = stacks[0].cuts.iter().map(|cut| cut.name.as_str()).collect();
+            let first = names.iter().position(|name| *name == "Panel").unwrap();
+            assert_eq!(names[first..first + 3], ["Panel"; 3]);
+        }
+
+        // a board takes the set only if a single stack can hold all of it
+        let set: Vec<Cut> = (0..3).map(|_| cut(30f32, 2f32, "Panel")).collect();
+        let mut board = board(96f32, 5f32, vec![]);
+        assert_eq!(
+            board.accept_sequence(&set, &options),
+            PlacementOutcome::Rejected
+        );
+        assert!(board.stacks.is_empty());
+
+        // and no board at all may
+        model.cutlist[0] = model::Cut::parse("5@30x2:Panel#sequential").unwrap();
+        assert!(compute(&model, &options).is_err());
+    }
+
+    #[test]
+    fn suggests_combining_cuts_which_abut_neatly() {
+        let mut model = input(&[
+            "2@12x6:Door",
+            "1@11.5x4:Rail",
+            "1@12.5x4:Stile",
+            "1@7.3x4:Cleat",
+        ]);
+        model.spacing = 0.125;
+        assert_eq!(
+            suggest_combined_cuts(&model),
+            [
+                CombinedCut {
+                    names: ["Stile".to_owned(), "Rail".to_owned()],
+                    length: 24.125,
+                    width: 4f32,
+                },
+                CombinedCut {
+                    names: ["Door".to_owned(), "Door".to_owned()],
+                    length: 24.125,
+                    width: 6f32,
+                },
+            ]
+        );
+
+        // a blank longer than the longest board is no help
+        let model = input(&["2@50x6:Side"]);
+        assert!(suggest_combined_cuts(&model).is_empty());
+    }
+
+    #[test]
+    fn streaming_keeps_the_same_best_solutions_as_compute() {
+        let model = input(&["3@30x3:Rail", "4@20x2:Stile", "2@45x4:Top", "6@11x1.5:Slat"]);
+        for objective in [Objective::Waste, Objective::Offcuts, Objective::Time] {
+            let options = Options {
+                attempts: 256,
+                result_count: 8,
+                objective,
+                seed: 7,
+                ..Default::default()
+            };
+            let fingerprints = |solutions: Vec<Vec<Board>>| -> Vec<String> {
+                solutions.iter().map(|s| fingerprint(s)).collect()
+            };
+            let batch = fingerprints(compute(&model, &options).unwrap());
+            assert_eq!(batch.len(), 8);
+            assert_eq!(
+                fingerprints(compute_streaming(&model, &options).unwrap()),
+                batch
+            );
+        }
+    }
+
+    #[test]
+    fn fill_cuts_consume_leftover_space() {
+        // the fixed cut uses one 96x8 board, leaving 56 of its length for two stacks of 24x3 slats
+        let model = input(&["1@40x8:Top", "fill@24x3:Slat"]);
+        let solutions = compute(&model, &Options::default()).unwrap();
+        let solution = &solutions[0];
+        assert_eq!(solution.len(), 1);
+        assert_eq!(fill_counts(&model, solution), vec![("Slat".to_owned(), 4)]);
+
+        // nothing more fits once the slats are in
+        let slat = cut(24f32, 3f32, "Slat");
+        assert!(solution
+            .iter()
+            .all(|board| !board.clone().accept(&slat, &Options::default()).is_placed()));
+    }
+
+    #[test]
+    fn layouts_are_guillotine_valid() {
+        let model = input(&["4@25x2:Leg", "3@14x5:Slat", "4@4x0.75:Mount", "2@48x3:Rail"]);
+        let solutions = compute(
+            &model,
+            &Options {
+                result_count: 16,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(solutions
+            .iter()
+            .flatten()
+            .all(|board| board.is_guillotine_valid()));
+
+        // stacks too long or wide for the board can't be cut from it edge-to-edge
+        let too_long = board(
+            96f32,
+            8f32,
+            vec![vec![cut(60f32, 4f32, "A")], vec![cut(40f32, 4f32, "B")]],
+        );
+        assert!(!too_long.is_guillotine_valid());
+        let too_wide = board(
+            96f32,
+            8f32,
+            vec![vec![cut(60f32, 5f32, "A"), cut(40f32, 4f32, "B")]],
+        );
+        assert!(!too_wide.is_guillotine_valid());
+    }
+
+    #[test]
+    fn strict_guillotine_refuses_non_guillotine_placements() {
+        // a stack edited wider than its board leaves any layout on it uncuttable edge-to-edge
+        let too_wide = board(
+            96f32,
+            8f32,
+            vec![vec![cut(30f32, 5f32, "A"), cut(30f32, 4f32, "B")]],
+        );
+        let shelf = cut(30f32, 4f32, "Shelf");
+        assert!(too_wide
+            .clone()
+            .accept(&shelf, &Options::default())
+            .is_placed());
+
+        let strict = Options {
+            strict_guillotine: true,
+            ..Default::default()
+        };
+        assert!(!too_wide.can_accept(&shelf, &strict));
+        assert!(!too_wide.clone().accept(&shelf, &strict).is_placed());
+        let mut valid = board(96f32, 8f32, vec![vec![cut(30f32, 4f32, "A")]]);
+        assert!(valid.accept(&shelf, &strict).is_placed());
+        assert!(valid.is_guillotine_valid());
+
+        let model = input(&["2@30x6:Panel", "4@7x2:Block", "3@20x4:Rail"]);
+        let solutions = compute(
+            &model,
+            &Options {
+                result_count: 16,
+                ..strict
+            },
+        )
+        .unwrap();
+        assert!(solutions
+            .iter()
+            .flatten()
+            .all(|board| board.is_guillotine_valid()));
+    }
+
+    #[test]
+    fn weight_is_board_feet_times_density() {
+        let mut model = input(&["2@90x7:Panel"]);
+        assert_eq!(solution_weight(&model, &[]), None);
+
+        // each panel needs its own 96x8 board, 5.33 board-feet apiece
+        model.densities.insert("A".to_owned(), 3f32);
+        let solutions = compute(&model, &Options::default()).unwrap();
+        assert_eq!(solutions[0].len(), 2);
+        let weight = solution_weight(&model, &solutions[0]).unwrap();
+        assert!((weight - 2f32 * 96f32 * 8f32 / 144f32 * 3f32).abs() < 1e-3);
+    }
+
+    #[test]
+    fn seed_sweep_reports_a_reproducible_best_seed() {
+        let model = input(&["4@25x2:Leg", "3@14x5:Slat", "4@20x3:Edge", "2@12x4:Apron"]);
+        let options = Options {
+            attempts: 8,
+            ..Default::default()
+        };
+        let (seed, solutions) = seed_sweep(&model, &options, 8).unwrap();
+        assert!(seed < 8);
+
+        // no seed in the sweep does better
+        for other in 0..8 {
+            let other = compute(
+                &model,
+                &Options {
+                    seed: other,
+                    ..options.clone()
+                },
+            )
+            .unwrap();
+            assert!(score(&other[0]) <= score(&solutions[0]));
+        }
+
+        let reproduced = compute(&model, &Options { seed, ..options }).unwrap();
+        assert_eq!(score(&reproduced[0]), score(&solutions[0]));
+        let layout = |solution: &[Board]| -> Vec<Vec<String>> {
+            solution
+                .iter()
+                .map(|board| board.cuts().iter().map(|cut| cut.id.clone()).collect())
+                .collect()
+        };
+        assert_eq!(layout(&reproduced[0]), layout(&solutions[0]));
+    }
+
+    #[test]
+    fn seed_sweep_reports_why_no_solution_is_possible() {
+        let mut model = input(&["1@24x7:Panel"]);
+        model.boards = vec![model::Board::parse("96x6:B").unwrap()];
+        let error = seed_sweep(&model, &Options::default(), 4).unwrap_err();
+        assert!(error.to_string().contains("larger than all"), "{}", error);
+
+        let options = Options {
+            result_count: 0,
+            ..Default::default()
+        };
+        assert!(seed_sweep(&input(&["2@24x4:Shelf"]), &options, 3).is_err());
+
+        // with no seed finding a solution, the last failure is reported
+        let mut model = input(&["2@60x8:Top"]);
+        model.boards = vec![model::Board::parse("96x8:A=1").unwrap()];
+        let error = seed_sweep(&model, &Options::default(), 4).unwrap_err();
+        assert!(
+            error.to_string().contains("No viable solutions"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn cuts_too_short_to_crosscut_are_orphaned() {
+        let options = Options {
+            min_crosscut_width: Some(6f32),
+            ..Default::default()
+        };
+        let mut board: Board = (&model::Board::parse("96x8:A").unwrap()).into();
+        assert_eq!(
+            board.accept(&cut(4f32, 2f32, "Block"), &options),
+            PlacementOutcome::Rejected
+        );
+        assert!(board
+            .accept(&cut(6f32, 2f32, "Block"), &options)
+            .is_placed());
+        assert!(board
+            .accept(&cut(4f32, 2f32, "Block"), &Options::default())
+            .is_placed());
+
+        let model = input(&["2@30x4:Rail", "4@4x0.75:Mount"]);
+        assert_eq!(orphaned_cuts(&model, &options), vec!["Mount".to_owned()]);
+        let solutions = compute(&model, &options).unwrap();
+        assert!(board_containing(&solutions[0], "Mount").is_empty());
+        assert_eq!(board_containing(&solutions[0], "Rail").len(), 1);
+    }
+
+    #[test]
+    fn flat_cut_table_orders_by_board_then_position() {
+        let solution = vec![
+            board(
+                96f32,
+                8f32,
+                vec![
+                    vec![cut(40f32, 4f32, "A"), cut(30f32, 3f32, "B")],
+                    vec![cut(20f32, 6f32, "C")],
+                ],
+            ),
+            board(96f32, 8f32, vec![vec![cut(50f32, 2f32, "D")]]),
+        ];
+        let rows = flat_cut_table(&solution);
+        let cut_count: usize = solution.iter().map(|board| board.cuts().len()).sum();
+        assert_eq!(rows.len(), cut_count);
+
+        let order: Vec<(usize, &str, f32, f32)> = rows
+            .iter()
+            .map(|row| (row.board, row.cut.as_str(), row.x, row.y))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                (0, "A", 0f32, 0f32),
+                (0, "C", 40f32, 0f32),
+                (0, "B", 0f32, 4f32),
+                (1, "D", 0f32, 0f32),
+            ]
+        );
+        assert!(rows.iter().all(|row| !row.rotated));
+    }
+
+    #[test]
+    fn double_sided_cuts_are_flagged_in_the_plan() {
+        let model = input(&["2@30x4:Door#double-sided", "2@20x3:Rail"]);
+        let solution = compute(&model, &Options::default()).unwrap().remove(0);
+        for cut in solution.iter().flat_map(|board| board.cuts()) {
+            assert_eq!(cut.double_sided, cut.name == "Door");
+        }
+
+        let rows = flat_cut_table(&solution);
+        assert_eq!(rows.len(), 4);
+        for row in &rows {
+            assert_eq!(row.double_sided, row.cut.starts_with("Door"));
+        }
+    }
+
+    #[test]
+    fn sparsely_used_boards_are_eliminated() {
+        let model = input(&["1@40x4:Rail", "1@10x2:Block"]);
+        let mut boards = vec![
+            board(96f32, 8f32, vec![vec![cut(40f32, 4f32, "Rail")]]),
+            board(96f32, 8f32, vec![vec![cut(10f32, 2f32, "Block")]]),
+        ];
+        assert_eq!(
+            try_eliminate_boards(&model, &mut boards, &Options::default()),
+            1
+        );
+        assert_eq!(boards.len(), 1);
+        let mut names: Vec<&str> = boards[0].cuts().iter().map(|c| c.name.as_str()).collect();
+        names.sort_unstable();
+        assert_eq!(names, vec!["Block", "Rail"]);
+
+        // a board whose cuts don't fit elsewhere stays
+        let mut boards = vec![
+            board(96f32, 8f32, vec![vec![cut(90f32, 7f32, "Rail")]]),
+            board(96f32, 8f32, vec![vec![cut(90f32, 7f32, "Block")]]),
+        ];
+        assert_eq!(
+            try_eliminate_boards(&model, &mut boards, &Options::default()),
+            0
+        );
+        assert_eq!(boards.len(), 2);
+    }
+
+    #[test]
+    fn polishing_never_worsens_a_solution() {
+        let mut improved = 0;
+        for seed in 0..16 {
+            let model = model::Input::parse(
+                &crate::generate::random_input(8, seed),
+                model::InputFormat::Yaml,
+            )
+            .unwrap();
+            // a single shuffled attempt leaves room for improvement
+            let options = Options {
+                attempts: 1,
+                seed,
+                ..Default::default()
+            };
+            let mut solution = compute(&model, &options).unwrap().remove(0);
+            let before = score(&solution);
+            let cut_count = solution
+                .iter()
+                .map(|board| board.cuts().len())
+                .sum::<usize>();
+
+            if polish(&model, &mut solution, &options) > 0 {
+                improved += 1;
+                assert!(score(&solution) > before);
+            }
+            assert!(score(&solution) >= before);
+            let polished_cut_count: usize = solution.iter().map(|board| board.cuts().len()).sum();
+            assert_eq!(polished_cut_count, cut_count);
+            assert!(solution.iter().all(|board| !board.stacks.is_empty()));
+            solution.iter().for_each(assert_no_overlaps);
+
+            // polished solutions are what compute returns when asked
+            let polished = compute(
+                &model,
+                &Options {
+                    polish: true,
+                    ..options
+                },
+            )
+            .unwrap()
+            .remove(0);
+            assert!(score(&polished) >= before);
+        }
+        assert!(improved > 8, "Only {} of 16 solutions improved", improved);
     }
 }
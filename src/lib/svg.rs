@@ -0,0 +1,180 @@
+use super::solver;
+
+/// Model units between boards, and around the drawing
+const PADDING: f32 = 4f32;
+
+/// Height of a text line, in model units
+const TEXT_SIZE: f32 = 2f32;
+const LABEL_SIZE: f32 = 1.25;
+
+const BOARD_FILL: &str = "#e6e6e6";
+const CUT_FILL: &str = "#808080";
+const CUT_STROKE: &str = "#404040";
+const CROSSCUT_STROKE: &str = "#ff0000";
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// draws the solution's boards one above the other, from `y` down, returning the elements and the
+// height they take
+fn boards(solution: &[solver::Board], mut y: f32) -> (Vec<String>, f32) {
+    let top = y;
+    let mut elements = Vec::new();
+    for board in solution {
+        elements.push(format!(
+            r#"<text x="0" y="{}" font-size="{}">{} ({} by {})</text>"#,
+            y + TEXT_SIZE,
+            TEXT_SIZE,
+            escape(&board.id),
+            board.nominal_length,
+            board.nominal_width
+        ));
+        y += TEXT_SIZE * 1.5;
+        elements.push(format!(
+            r#"<rect x="0" y="{}" width="{}" height="{}" fill="{}"/>"#,
+            y, board.length, board.width, BOARD_FILL
+        ));
+
+        for solver::Placement {
+            cut, x, y: cut_y, ..
+        } in board.placements()
+        {
+            elements.push(format!(
+                r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}" stroke="{}" stroke-width="0.1"/>"#,
+                x, y + cut_y, cut.length, cut.width, CUT_FILL, CUT_STROKE
+            ));
+            elements.push(format!(
+                r#"<text x="{}" y="{}" font-size="{}" text-anchor="middle" dominant-baseline="middle">{}</text>"#,
+                x + cut.length / 2f32,
+                y + cut_y + cut.width / 2f32,
+                LABEL_SIZE,
+                escape(&cut.id)
+            ));
+        }
+        // over the cuts, as the stack following each would cover it
+        for x in board.crosscuts() {
+            elements.push(format!(
+                r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-width="0.1"/>"#,
+                x,
+                y,
+                x,
+                y + board.width,
+                CROSSCUT_STROKE
+            ));
+        }
+        y += board.width + PADDING;
+    }
+    (elements, y - top)
+}
+
+// wraps the elements in an svg document `length` by `height` model units, plus padding
+fn document(elements: &[String], length: f32, height: f32) -> String {
+    let width = length + PADDING * 2f32;
+    let height = height + PADDING * 2f32;
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}in\" height=\"{h}in\" viewBox=\"{x} {x} {w} {h}\" font-family=\"sans-serif\">\n{}\n</svg>\n",
+        elements.join("\n"),
+        w = width,
+        h = height,
+        x = -PADDING
+    )
+}
+
+fn longest(solution: &[solver::Board]) -> f32 {
+    solution
+        .iter()
+        .map(|board| board.length)
+        .fold(0f32, f32::max)
+}
+
+/// Renders the solution as an svg document, in inches: each board drawn to scale below the last,
+/// labeled, with its cuts labeled and its crosscuts marked.
+pub fn svg(solution: &[solver::Board]) -> String {
+    let (elements, height) = boards(solution, 0f32);
+    document(&elements, longest(solution), height)
+}
+
+/// Renders the solutions into one svg document for comparing them, each drawn as by `svg` in its
+/// own group below the last, headed by its rank and score.
+pub fn svg_all(solutions: &[Vec<solver::Board>]) -> String {
+    let mut elements = Vec::new();
+    let mut y = 0f32;
+    for (i, solution) in solutions.iter().enumerate() {
+        elements.push(format!(r#"<g class="solution" id="solution-{}">"#, i + 1));
+        elements.push(format!(
+            r#"<text x="0" y="{}" font-size="{}" font-weight="bold">Solution {}, score {:.3}</text>"#,
+            y + TEXT_SIZE * 1.5,
+            TEXT_SIZE * 1.5,
+            i + 1,
+            solver::score(solution)
+        ));
+        y += TEXT_SIZE * 2.5;
+        let (mut solution_elements, height) = boards(solution, y);
+        elements.append(&mut solution_elements);
+        elements.push("</g>".to_owned());
+        y += height + PADDING;
+    }
+    let length = solutions
+        .iter()
+        .map(|solution| longest(solution))
+        .fold(0f32, f32::max);
+    document(&elements, length, y)
+}
+
+#[cfg(test)]
+mod svg_tests {
+    use super::*;
+    use crate::model;
+
+    #[test]
+    fn svg_all_groups_each_solution() {
+        let model = model::Input::parse(
+            "
+            boards:
+              - 96x8:A
+              - 96x6:B
+            cutlist:
+              - 4@25x2:Leg & Foot
+              - 2@12x4:Apron
+              - 4@20x3:Top Table Edges
+            ",
+            model::InputFormat::Yaml,
+        )
+        .unwrap();
+        let options = solver::Options {
+            result_count: 3,
+            ..Default::default()
+        };
+        let solutions = solver::compute(&model, &options).unwrap();
+        let combined = svg_all(&solutions);
+
+        assert!(combined.starts_with("<svg"));
+        assert_eq!(
+            combined.matches(r#"<g class="solution""#).count(),
+            solutions.len()
+        );
+        assert_eq!(combined.matches("</g>").count(), solutions.len());
+        for i in 1..=solutions.len() {
+            assert!(combined.contains(&format!("Solution {}, score", i)));
+        }
+        let cut_count: usize = solutions
+            .iter()
+            .flatten()
+            .map(|board| board.cuts().len())
+            .sum();
+        assert_eq!(
+            combined.matches(&format!(r#"fill="{}""#, CUT_FILL)).count(),
+            cut_count
+        );
+        assert!(combined.contains("Leg &amp; Foot") && !combined.contains("Leg & Foot"));
+
+        // a single solution's svg draws the same boards, without the grouping
+        let single = svg(&solutions[0]);
+        assert!(!single.contains("<g"));
+        assert!(single.contains(&solutions[0][0].id));
+    }
+}
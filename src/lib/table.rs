@@ -0,0 +1,82 @@
+use std::{io, path::Path};
+
+use anyhow::Result;
+use csv::{QuoteStyle, Writer, WriterBuilder};
+
+use super::solver;
+
+const HEADER: [&str; 7] = [
+    "board_id", "cut_name", "x", "y", "length", "width", "rotated",
+];
+
+/// Writes the solution's flat cut table to `path` as csv, a row per placed piece, for import into
+/// spreadsheets or shop software. Positions are as `solver::flat_cut_table` gives them, the same
+/// as the visualizer draws.
+pub fn csv<P: AsRef<Path>>(solution: &[solver::Board], path: P) -> Result<()> {
+    write_csv(solution, writer().from_path(path)?)
+}
+
+// text fields, e.g., names, are quoted; numbers aren't
+fn writer() -> WriterBuilder {
+    let mut builder = WriterBuilder::new();
+    builder.quote_style(QuoteStyle::NonNumeric);
+    builder
+}
+
+fn write_csv<W: io::Write>(solution: &[solver::Board], mut writer: Writer<W>) -> Result<()> {
+    writer.write_record(HEADER)?;
+    for row in solver::flat_cut_table(solution) {
+        writer.write_record([
+            row.board_id,
+            row.cut,
+            row.x.to_string(),
+            row.y.to_string(),
+            row.length.to_string(),
+            row.width.to_string(),
+            row.rotated.to_string(),
+        ])?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod table_tests {
+    use super::*;
+
+    #[test]
+    fn csv_reads_back_a_row_per_piece() {
+        let solution = solver::test_support::solve(
+            "
+            boards:
+              - 96x8:A
+            cutlist:
+              - 2@40x4:Rail
+              - 3@20x3:Stile, Left
+            ",
+        );
+
+        let mut bytes = Vec::new();
+        write_csv(&solution, writer().from_writer(&mut bytes)).unwrap();
+        let text = String::from_utf8(bytes).unwrap();
+        assert!(text.contains(r#""Stile, Left"#));
+
+        let mut reader = csv::Reader::from_reader(text.as_bytes());
+        assert_eq!(reader.headers().unwrap(), HEADER.as_slice());
+        let records: Vec<csv::StringRecord> = reader.records().map(|r| r.unwrap()).collect();
+        assert_eq!(records.len(), 5);
+        let table = solver::flat_cut_table(&solution);
+        for (record, row) in records.iter().zip(&table) {
+            assert_eq!(&record[0], row.board_id);
+            assert_eq!(&record[1], row.cut);
+            assert_eq!(record[2].parse::<f32>().unwrap(), row.x);
+            assert_eq!(record[3].parse::<f32>().unwrap(), row.y);
+            assert_eq!(&record[6], "false");
+        }
+
+        let path = std::env::temp_dir().join(format!("cutlist-{}.csv", std::process::id()));
+        csv(&solution, &path).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), text);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,6 +1,15 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs,
+};
+
+use anyhow::{bail, Context, Result};
+use log::{error, warn};
 use macroquad::prelude::*;
+use serde::Serialize;
+use yaml_rust::{Yaml, YamlLoader};
 
-use super::solver;
+use super::{model, solver};
 
 const PADDING: f32 = 10f32;
 const FONT_SIZE: f32 = 16f32;
@@ -12,6 +21,121 @@ const CUT_STROKE_COLOR: Color = Color::new(0.25f32, 0.25f32, 0.25f32, 1f32);
 
 const CROSSCUT_LINE_COLOR: Color = Color::new(1f32, 0f32, 0f32, 0.5);
 
+const DEFECT_COLOR: Color = Color::new(1f32, 0f32, 0f32, 0.35);
+const DEFECT_STROKE_COLOR: Color = Color::new(0.75f32, 0f32, 0f32, 1f32);
+
+const HIGHLIGHT_COLOR: Color = Color::new(0f32, 0.6, 0f32, 0.25);
+
+/// Seconds between each cut revealed during placement playback
+const PLAYBACK_INTERVAL: f64 = 0.25;
+
+/// Model units between the hatch lines drawn over cuts which need further shaping
+const HATCH_SPACING: f32 = 1f32;
+
+/// Model units from the corner of a double sided cut to the dot marking its up face, and the dot's radius
+const DOUBLE_SIDED_DOT_INSET: f32 = 0.75;
+const DOUBLE_SIDED_DOT_RADIUS: f32 = 0.25;
+
+/// Cuts shorter or narrower than these, in model units, are too small to show a grain arrow
+const GRAIN_ARROW_MIN_LENGTH: f32 = 6f32;
+const GRAIN_ARROW_MIN_WIDTH: f32 = 1.5;
+
+/// The colors used by the visualizer
+#[derive(Clone, Debug, PartialEq)]
+pub struct Theme {
+    pub background: Color,
+    pub board: Color,
+    pub board_stroke: Color,
+    pub board_label: Color,
+    pub cut: Color,
+    pub cut_stroke: Color,
+    pub cut_label: Color,
+    pub crosscut_line: Color,
+    pub defect: Color,
+    pub defect_stroke: Color,
+    pub highlight: Color,
+    pub axis: Color,
+    pub status: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            background: WHITE,
+            board: BOARD_COLOR,
+            board_stroke: BOARD_STROKE_COLOR,
+            board_label: BLACK,
+            cut: CUT_COLOR,
+            cut_stroke: CUT_STROKE_COLOR,
+            cut_label: WHITE,
+            crosscut_line: CROSSCUT_LINE_COLOR,
+            defect: DEFECT_COLOR,
+            defect_stroke: DEFECT_STROKE_COLOR,
+            highlight: HIGHLIGHT_COLOR,
+            axis: GREEN,
+            status: DARKGRAY,
+        }
+    }
+}
+
+impl Theme {
+    /// Parses a theme from a yaml (or json) mapping of color names to hex colors, e.g.:
+    /// { background: "#1e1e1e", cut: "#808080ff" }
+    /// Any colors not specified keep their default values.
+    pub fn parse(theme: &str) -> Result<Theme> {
+        let docs = YamlLoader::load_from_str(theme)?;
+        let mut result = Theme::default();
+        if let Some(Yaml::Hash(doc)) = docs.first() {
+            for (name, color) in doc {
+                let name = name.as_str().unwrap_or_default();
+                let color = match color.as_str() {
+                    Some(color) => {
+                        parse_color(color).with_context(|| format!("Invalid color for {}", name))?
+                    }
+                    None => bail!("Expected a hex color string for {}", name),
+                };
+                match name {
+                    "background" => result.background = color,
+                    "board" => result.board = color,
+                    "board_stroke" => result.board_stroke = color,
+                    "board_label" => result.board_label = color,
+                    "cut" => result.cut = color,
+                    "cut_stroke" => result.cut_stroke = color,
+                    "cut_label" => result.cut_label = color,
+                    "crosscut_line" => result.crosscut_line = color,
+                    "defect" => result.defect = color,
+                    "defect_stroke" => result.defect_stroke = color,
+                    "highlight" => result.highlight = color,
+                    "axis" => result.axis = color,
+                    "status" => result.status = color,
+                    _ => bail!("Unrecognized theme color \"{}\"", name),
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// Parses a color in the form #RRGGBB or #RRGGBBAA
+fn parse_color(color: &str) -> Result<Color> {
+    let hex = color.trim_start_matches('#');
+    if (hex.len() != 6 && hex.len() != 8) || !hex.is_ascii() {
+        bail!(
+            "Expected a color in form #RRGGBB or #RRGGBBAA, got \"{}\"",
+            color
+        );
+    }
+    let component =
+        |i: usize| -> Result<f32> { Ok(u8::from_str_radix(&hex[i..i + 2], 16)? as f32 / 255f32) };
+    let alpha = if hex.len() == 8 { component(6)? } else { 1f32 };
+    Ok(Color::new(
+        component(0)?,
+        component(2)?,
+        component(4)?,
+        alpha,
+    ))
+}
+
 #[derive(Clone, Copy)]
 enum LabelAnchor {
     Left,
@@ -19,6 +143,46 @@ enum LabelAnchor {
     Right,
 }
 
+/// Maps model units to screen pixels. When rotated the x and y axes are swapped,
+/// so a board's length runs down the screen rather than across it.
+#[derive(Clone, Copy)]
+struct Transform {
+    scale: f32,
+    rotated: bool,
+}
+
+impl Transform {
+    fn swap(point: Vec2, rotated: bool) -> Vec2 {
+        if rotated {
+            Vec2::new(point.y, point.x)
+        } else {
+            point
+        }
+    }
+
+    /// Converts a point (or size) in model units to screen pixels
+    fn apply(&self, point: Vec2) -> Vec2 {
+        Self::swap(point, self.rotated) * self.scale
+    }
+
+    /// Converts a point (or size) in screen pixels to model units
+    fn invert(&self, point: Vec2) -> Vec2 {
+        Self::swap(point / self.scale, self.rotated)
+    }
+
+    /// Converts a position on screen to a position in the model, where the model is drawn at `origin`
+    fn screen_to_model(&self, position: Vec2, origin: Vec2) -> Vec2 {
+        self.invert(position) - origin
+    }
+}
+
+/// Returns the real-world distance between two positions on screen, where the model is drawn at `origin`
+fn measure_distance(start: Vec2, end: Vec2, origin: Vec2, transform: Transform) -> f32 {
+    transform
+        .screen_to_model(start, origin)
+        .distance(transform.screen_to_model(end, origin))
+}
+
 #[derive(Clone)]
 struct Label {
     text: String,
@@ -30,97 +194,244 @@ struct Label {
 fn draw_rectangle_scaled(
     top_left: Vec2,
     size: Vec2,
-    scale: f32,
+    transform: Transform,
     fill_color: Color,
     stroke_color: Color,
 ) {
-    draw_rectangle(
-        top_left.x * scale,
-        top_left.y * scale,
-        size.x * scale,
-        size.y * scale,
-        fill_color,
-    );
-    draw_rectangle_lines(
-        top_left.x * scale,
-        top_left.y * scale,
-        size.x * scale,
-        size.y * scale,
-        1f32,
-        stroke_color,
-    );
+    let top_left = transform.apply(top_left);
+    let size = transform.apply(size);
+    draw_rectangle(top_left.x, top_left.y, size.x, size.y, fill_color);
+    draw_rectangle_lines(top_left.x, top_left.y, size.x, size.y, 1f32, stroke_color);
 }
 
-fn draw_line_scaled(start: Vec2, end: Vec2, scale: f32, color: Color) {
-    draw_line(
-        start.x * scale,
-        start.y * scale,
-        end.x * scale,
-        end.y * scale,
-        1f32,
-        color,
-    );
+fn draw_line_scaled(start: Vec2, end: Vec2, transform: Transform, color: Color) {
+    let start = transform.apply(start);
+    let end = transform.apply(end);
+    draw_line(start.x, start.y, end.x, end.y, 1f32, color);
+}
+
+/// Draws diagonal hatching across a rectangle, marking a blank which needs further shaping
+fn draw_hatching_scaled(top_left: Vec2, size: Vec2, transform: Transform, color: Color) {
+    // each hatch line is x + y = t in the rectangle's space, clipped to its bounds
+    let mut t = HATCH_SPACING;
+    while t < size.x + size.y {
+        let start = Vec2::new((t - size.y).max(0f32), t.min(size.y));
+        let end = Vec2::new(t.min(size.x), (t - size.x).max(0f32));
+        draw_line_scaled(top_left + start, top_left + end, transform, color);
+        t += HATCH_SPACING;
+    }
+}
+
+/// The optional annotations drawn over boards, each toggled by a key
+#[derive(Clone, Copy, Default)]
+struct Overlays {
+    /// Each board is labeled with the components of its score
+    scores: bool,
+    /// Each cut large enough gets an arrow along its grain
+    grain: bool,
+    /// Each board's unused tail is hatched and labeled with its length
+    tail: bool,
+}
+
+/// Returns the line segments of an arrow, a shaft and the two barbs of its head, pointing along the
+/// grain of a cut at `top_left` of `size`, in model units, or None if the cut's too small to show one.
+/// The solver doesn't rotate cuts, so the grain always runs along a cut's length. The arrow spans the
+/// middle three fifths of the cut, three quarters of the way down, clear of its centered label.
+fn grain_arrow(top_left: Vec2, size: Vec2) -> Option<[(Vec2, Vec2); 3]> {
+    if size.x < GRAIN_ARROW_MIN_LENGTH || size.y < GRAIN_ARROW_MIN_WIDTH {
+        return None;
+    }
+    let y = top_left.y + size.y * 0.75;
+    let tail = Vec2::new(top_left.x + size.x / 5f32, y);
+    let tip = Vec2::new(top_left.x + size.x * 4f32 / 5f32, y);
+    let head = (size.y / 5f32).min((tip.x - tail.x) / 4f32);
+    Some([
+        (tail, tip),
+        (tip, Vec2::new(tip.x - head, y - head / 2f32)),
+        (tip, Vec2::new(tip.x - head, y + head / 2f32)),
+    ])
 }
 
-fn render_board(board: &solver::Board, top_left: Vec2, scale: f32) -> Vec<Label> {
+/// Returns the (top left, size) of the board's unused tail, from the end of its last stack to the
+/// end of the board, in model units relative to the board's top left; None if there's none left.
+fn tail_region(board: &solver::Board) -> Option<(Vec2, Vec2)> {
+    let allocated_length = board.allocated_length();
+    let tail_length = board.length - allocated_length;
+    if tail_length > 0f32 {
+        Some((
+            Vec2::new(allocated_length, 0f32),
+            Vec2::new(tail_length, board.width),
+        ))
+    } else {
+        None
+    }
+}
+
+/// Returns the (top left, size) of each of the board's trimmed ends, in model units relative to the
+/// board's top left: its start's trim before the board's length, its far end's after
+fn trim_regions(board: &solver::Board) -> Vec<(Vec2, Vec2)> {
+    let (start, end) = board.end_trims;
+    let mut regions = Vec::new();
+    if start > 0f32 {
+        regions.push((Vec2::new(-start, 0f32), Vec2::new(start, board.width)));
+    }
+    if end > 0f32 {
+        regions.push((Vec2::new(board.length, 0f32), Vec2::new(end, board.width)));
+    }
+    regions
+}
+
+/// Renders the board, and the cuts on it. If `revealed` is set, only cuts placed before that placement index are drawn.
+fn render_board(
+    board: &solver::Board,
+    top_left: Vec2,
+    transform: Transform,
+    revealed: Option<usize>,
+    overlays: Overlays,
+    theme: &Theme,
+) -> Vec<Label> {
     let mut labels = Vec::new();
 
     // Draw the board
     draw_rectangle_scaled(
         top_left,
         Vec2::new(board.length, board.width),
-        scale,
-        BOARD_COLOR,
-        BOARD_STROKE_COLOR,
+        transform,
+        theme.board,
+        theme.board_stroke,
     );
+    for (trim_offset, trim_size) in trim_regions(board) {
+        draw_rectangle_scaled(
+            top_left + trim_offset,
+            trim_size,
+            transform,
+            theme.board,
+            theme.board_stroke,
+        );
+        draw_hatching_scaled(
+            top_left + trim_offset,
+            trim_size,
+            transform,
+            theme.board_stroke,
+        );
+    }
     labels.push(Label {
-        text: format!("{} ({} by {})", board.id, board.length, board.width),
+        text: format!(
+            "{} ({} by {})",
+            board.id, board.nominal_length, board.nominal_width
+        ),
         position: top_left,
-        color: BLACK,
+        color: theme.board_label,
         anchor: LabelAnchor::Left,
     });
+    for defect in &board.defects {
+        draw_rectangle_scaled(
+            top_left + Vec2::new(defect.x, defect.y),
+            Vec2::new(defect.length, defect.width),
+            transform,
+            theme.defect,
+            theme.defect_stroke,
+        );
+    }
+    if overlays.scores {
+        if let Some(breakdown) = board.score_breakdown() {
+            labels.push(Label {
+                text: format!(
+                    "score {:.3} (density {:.3}, concentration {:.3}, scrap {:.1})",
+                    breakdown.score,
+                    breakdown.density,
+                    breakdown.concentration,
+                    breakdown.scrap_area
+                ),
+                position: top_left + Vec2::new(board.length, 0f32),
+                color: theme.board_label,
+                anchor: LabelAnchor::Right,
+            });
+        }
+    }
 
-    // Draw the cut stacks
-    let mut stack_origin = top_left;
-    for stack in &board.stacks {
-        let mut cut_y = 0f32;
-        for cut in &stack.cuts {
-            draw_rectangle_scaled(
-                Vec2::new(stack_origin.x, stack_origin.y + cut_y),
-                Vec2::new(cut.length, cut.width),
-                scale,
-                CUT_COLOR,
-                CUT_STROKE_COLOR,
+    if overlays.tail {
+        if let Some((tail_offset, tail_size)) = tail_region(board) {
+            draw_hatching_scaled(
+                top_left + tail_offset,
+                tail_size,
+                transform,
+                theme.board_stroke,
             );
-
             labels.push(Label {
-                text: cut.id.clone(),
-                position: Vec2::new(
-                    stack_origin.x + cut.length / 2f32,
-                    stack_origin.y + cut_y + cut.width / 2f32,
-                ),
-                color: WHITE,
+                text: format!("{} available", tail_size.x),
+                position: top_left + tail_offset + tail_size / 2f32,
+                color: theme.board_label,
                 anchor: LabelAnchor::Center,
             });
+        }
+    }
 
-            cut_y += cut.width;
+    // Draw the cuts
+    let is_revealed = |cut: &solver::Cut| revealed.is_none_or(|revealed| cut.placement < revealed);
+    for solver::Placement { cut, x, y, .. } in board.placements() {
+        if !is_revealed(cut) {
+            continue;
         }
+        let cut_origin = top_left + Vec2::new(x, y);
+        let cut_size = Vec2::new(cut.length, cut.width);
 
-        // draw the crosscut
-        draw_line_scaled(
-            Vec2::new(
-                stack_origin.x + stack.length(),
-                top_left.y - (PADDING / 8f32),
-            ),
-            Vec2::new(
-                stack_origin.x + stack.length(),
-                top_left.y + board.width + (PADDING / 8f32),
-            ),
-            scale,
-            CROSSCUT_LINE_COLOR,
-        );
+        draw_rectangle_scaled(cut_origin, cut_size, transform, theme.cut, theme.cut_stroke);
+        if cut.shape.is_some() {
+            draw_hatching_scaled(cut_origin, cut_size, transform, theme.cut_stroke);
+        }
+
+        if overlays.grain {
+            let arrow = grain_arrow(cut_origin, cut_size);
+            for (start, end) in arrow.iter().flatten() {
+                draw_line_scaled(*start, *end, transform, theme.cut_stroke);
+            }
+        }
 
-        stack_origin.x += stack.length();
+        // double sided parts get a dot in the corner of the face to mark first
+        if cut.double_sided {
+            let dot = transform.apply(cut_origin + Vec2::splat(DOUBLE_SIDED_DOT_INSET));
+            draw_circle(
+                dot.x,
+                dot.y,
+                DOUBLE_SIDED_DOT_RADIUS * transform.scale,
+                theme.cut_stroke,
+            );
+        }
+
+        let notes: Vec<&str> = cut
+            .shape
+            .as_deref()
+            .into_iter()
+            .chain(cut.double_sided.then_some("face up"))
+            .collect();
+        labels.push(Label {
+            text: if notes.is_empty() {
+                cut.id.clone()
+            } else {
+                format!("{} ({})", cut.id, notes.join(", "))
+            },
+            position: cut_origin + cut_size / 2f32,
+            color: theme.cut_label,
+            anchor: LabelAnchor::Center,
+        });
+    }
+
+    // draw the crosscut after each stack with a cut revealed, unless it would land on the board's end
+    let mut stack_end = 0f32;
+    for stack in &board.stacks {
+        stack_end += stack.length();
+        if stack.cuts.iter().any(is_revealed) && board.requires_crosscut_at(stack_end) {
+            draw_line_scaled(
+                Vec2::new(top_left.x + stack_end, top_left.y - (PADDING / 8f32)),
+                Vec2::new(
+                    top_left.x + stack_end,
+                    top_left.y + board.width + (PADDING / 8f32),
+                ),
+                transform,
+                theme.crosscut_line,
+            );
+        }
     }
 
     labels
@@ -131,56 +442,329 @@ fn draw_axis(at: Vec2, size: f32, color: Color) {
     draw_line(at.x - size, at.y, at.x + size, at.y, 1f32, color);
 }
 
-pub async fn show(solutions: &[Vec<solver::Board>]) {
-    let mut scale = 16f32;
+#[derive(Serialize)]
+struct Favorite<'a> {
+    /// 1-based, as shown in the status line
+    solution: usize,
+    boards: &'a [solver::Board],
+}
+
+/// Serializes the favorited solutions, in order, as json
+fn favorites_json(solutions: &[Vec<solver::Board>], favorites: &HashSet<usize>) -> Result<String> {
+    let mut indices: Vec<usize> = favorites.iter().copied().collect();
+    indices.sort_unstable();
+    let favorites: Vec<Favorite> = indices
+        .into_iter()
+        .map(|index| Favorite {
+            solution: index + 1,
+            boards: &solutions[index],
+        })
+        .collect();
+    Ok(serde_json::to_string_pretty(&favorites)?)
+}
+
+// parses a probe's trial cut, e.g., 24x4, as its length and width
+fn parse_probe(text: &str) -> Option<(f32, f32)> {
+    let (length, width) = text.split_once('x')?;
+    match (length.parse::<f32>(), width.parse::<f32>()) {
+        (Ok(length), Ok(width)) if length > 0f32 && width > 0f32 => Some((length, width)),
+        _ => None,
+    }
+}
+
+// buckets the solutions' indices by their board count, fewest boards first; each bucket keeps the
+// solutions' ranked order
+fn board_count_groups(solutions: &[Vec<solver::Board>]) -> BTreeMap<usize, Vec<usize>> {
+    let mut groups: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for (index, solution) in solutions.iter().enumerate() {
+        groups.entry(solution.len()).or_default().push(index);
+    }
+    groups
+}
+
+// the next or previous solution in the same board count group as `current`, staying put at
+// either end of the group
+fn step_within_group(groups: &BTreeMap<usize, Vec<usize>>, current: usize, forward: bool) -> usize {
+    let group = match groups.values().find(|group| group.contains(&current)) {
+        Some(group) => group,
+        None => return current,
+    };
+    let position = group.iter().position(|index| *index == current).unwrap();
+    let position = if forward {
+        (position + 1).min(group.len() - 1)
+    } else {
+        position.saturating_sub(1)
+    };
+    group[position]
+}
+
+// the best solution of the next or previous board count group from `current`'s, staying put at
+// either end
+fn step_between_groups(
+    groups: &BTreeMap<usize, Vec<usize>>,
+    current: usize,
+    forward: bool,
+) -> usize {
+    let keys: Vec<usize> = groups.keys().copied().collect();
+    let position = match groups.values().position(|group| group.contains(&current)) {
+        Some(position) => position,
+        None => return current,
+    };
+    let target = if forward {
+        (position + 1).min(keys.len() - 1)
+    } else {
+        position.saturating_sub(1)
+    };
+    if target == position {
+        current
+    } else {
+        groups[&keys[target]][0]
+    }
+}
+
+// re-runs the search with the seed after `options.seed`, returning options with that seed along
+// with the solutions found, or None if none were, so the caller can keep the ones it has
+fn resolve(
+    model: &model::Input,
+    options: &solver::Options,
+) -> Option<(solver::Options, Vec<Vec<solver::Board>>)> {
+    let options = solver::Options {
+        seed: options.seed.wrapping_add(1),
+        ..options.clone()
+    };
+    solver::compute(model, &options)
+        .ok()
+        .map(|solutions| (options, solutions))
+}
+
+/// Shows the solutions of `model`, which were found with `options`. N re-solves with the next seed,
+/// replacing the solutions unless none are found. C probes for room: type a trial cut, e.g.,
+/// 24x4, and press enter to highlight the boards which could still take it, or escape to cancel.
+/// B groups the solutions by board count: J and K then move within the current group, and H and L
+/// jump to the best solution with the next fewer or more boards.
+/// If `favorites_path` is set, F toggles the
+/// current solution as a favorite, and the favorites are written there as json each time they
+/// change; re-solving starts a new set of favorites.
+pub async fn show(
+    model: &model::Input,
+    options: &solver::Options,
+    mut solutions: Vec<Vec<solver::Board>>,
+    theme: &Theme,
+    favorites_path: Option<&str>,
+) {
+    let mut options = options.clone();
+    let mut transform = Transform {
+        scale: 16f32,
+        rotated: false,
+    };
     let mut origin = Vec2::new(0f32, 0f32);
     let mut mouse_down_position: Option<Vec2> = None;
     let mut current_solution_index: usize = 0;
 
+    // when set, only cuts placed before this index are shown
+    let mut revealed: Option<usize> = None;
+    let mut playing = false;
+    let mut last_reveal_time = get_time();
+
+    // measurement endpoints in model units, so they stay put as the view pans and zooms
+    let mut measurement: Vec<Vec2> = Vec::new();
+
+    let mut overlays = Overlays::default();
+
+    let mut favorites: HashSet<usize> = HashSet::new();
+
+    // set when a re-solve is requested, so the indicator is drawn before the search blocks
+    let mut solving = false;
+
+    // the trial cut being typed, while probing, and the last one entered
+    let mut probe_input: Option<String> = None;
+    let mut probe: Option<(f32, f32)> = None;
+
+    // when grouping, J and K move within the current board count's solutions
+    let mut grouped = false;
+    let mut groups = board_count_groups(&solutions);
+
     loop {
-        clear_background(WHITE);
+        // while typing a probe, keys type rather than act as shortcuts
+        let typing = probe_input.is_some();
+        let shortcut_pressed = |key| !typing && is_key_pressed(key);
+
+        if shortcut_pressed(KeyCode::N) {
+            solving = true;
+        }
+
+        clear_background(theme.background);
+
+        if solving {
+            draw_text(
+                "Solving...",
+                20.0,
+                screen_height() - 60.,
+                16.0,
+                theme.status,
+            );
+        }
 
-        draw_text(&format!("Solution {} of {}", current_solution_index + 1, solutions.len()), 20.0, screen_height() - 20., 16.0, DARKGRAY);
-        draw_axis(origin * scale, 10f32, GREEN);
+        let group = if grouped {
+            let board_count = solutions[current_solution_index].len();
+            let group = &groups[&board_count];
+            let position = group
+                .iter()
+                .position(|index| *index == current_solution_index)
+                .unwrap();
+            format!(
+                " ({} board{}: {} of {})",
+                board_count,
+                if board_count == 1 { "" } else { "s" },
+                position + 1,
+                group.len()
+            )
+        } else {
+            String::new()
+        };
+        draw_text(
+            &format!(
+                "Solution {} of {}{}{}",
+                current_solution_index + 1,
+                solutions.len(),
+                group,
+                if favorites.contains(&current_solution_index) {
+                    " (favorite)"
+                } else {
+                    ""
+                }
+            ),
+            20.0,
+            screen_height() - 20.,
+            16.0,
+            theme.status,
+        );
+        draw_axis(transform.apply(origin), 10f32, theme.axis);
 
         let cutlist = &solutions[current_solution_index];
+        let cut_count: usize = cutlist.iter().map(|board| board.cuts().len()).sum();
+
+        let with_room = probe
+            .map(|size| solver::boards_with_room_for(model, cutlist, size, &options))
+            .unwrap_or_default();
+        if let Some(text) = &probe_input {
+            draw_text(
+                &format!("Probe for room: {}_", text),
+                20.0,
+                screen_height() - 80.,
+                16.0,
+                theme.status,
+            );
+        } else if let Some((length, width)) = probe {
+            draw_text(
+                &format!(
+                    "{}x{} fits {} of {} boards",
+                    length,
+                    width,
+                    with_room.len(),
+                    cutlist.len()
+                ),
+                20.0,
+                screen_height() - 80.,
+                16.0,
+                theme.status,
+            );
+        }
+
+        if playing && get_time() - last_reveal_time > PLAYBACK_INTERVAL {
+            revealed = revealed.map(|revealed| (revealed + 1).min(cut_count));
+            last_reveal_time = get_time();
+            playing = revealed != Some(cut_count);
+        }
+
+        if let Some(revealed) = revealed {
+            draw_text(
+                &format!("Placed {} of {} cuts", revealed, cut_count),
+                20.0,
+                screen_height() - 40.,
+                16.0,
+                theme.status,
+            );
+        }
+
         let mut all_labels = Vec::new();
         let mut board_y_offset = 0f32;
-        for board in cutlist {
-            let mut board_labels =
-                render_board(board, origin + Vec2::new(0f32, board_y_offset), scale);
+        for (i, board) in cutlist.iter().enumerate() {
+            if with_room.contains(&i) {
+                draw_rectangle_scaled(
+                    origin + Vec2::new(0f32, board_y_offset),
+                    Vec2::new(board.length, board.width),
+                    transform,
+                    theme.highlight,
+                    theme.highlight,
+                );
+            }
+            let mut board_labels = render_board(
+                board,
+                origin + Vec2::new(0f32, board_y_offset),
+                transform,
+                revealed,
+                overlays,
+                theme,
+            );
             all_labels.append(&mut board_labels);
             board_y_offset += board.width + PADDING;
         }
 
         for label in &all_labels {
             let measure = measure_text(&label.text, None, FONT_SIZE as u16, 1f32);
+            let position = transform.apply(label.position);
             match label.anchor {
                 LabelAnchor::Left => draw_text(
                     &label.text,
-                    (label.position.x * scale).floor(),
-                    ((label.position.y * scale) - measure.height * 0.25).floor(),
+                    position.x.floor(),
+                    (position.y - measure.height * 0.25).floor(),
                     FONT_SIZE,
                     label.color,
                 ),
                 LabelAnchor::Center => draw_text(
                     &label.text,
-                    ((label.position.x * scale) - measure.width * 0.5).floor(),
-                    ((label.position.y * scale) + (measure.height - measure.offset_y) * 0.5)
-                        .floor(),
+                    (position.x - measure.width * 0.5).floor(),
+                    (position.y + (measure.height - measure.offset_y) * 0.5).floor(),
                     FONT_SIZE,
                     label.color,
                 ),
                 LabelAnchor::Right => draw_text(
                     &label.text,
-                    ((label.position.x * scale) - measure.width).floor(),
-                    ((label.position.y * scale) - measure.height * 0.25).floor(),
+                    (position.x - measure.width).floor(),
+                    (position.y - measure.height * 0.25).floor(),
                     FONT_SIZE,
                     label.color,
                 ),
             };
         }
 
+        // Measurement overlay: right click two points to measure between them
+        if let Some(start) = measurement.first() {
+            let start_screen = transform.apply(*start + origin);
+            let end_screen = match measurement.get(1) {
+                Some(end) => transform.apply(*end + origin),
+                None => Vec2::from(mouse_position()),
+            };
+            draw_line(
+                start_screen.x,
+                start_screen.y,
+                end_screen.x,
+                end_screen.y,
+                1f32,
+                theme.status,
+            );
+            let distance = measure_distance(start_screen, end_screen, origin, transform);
+            let midpoint = (start_screen + end_screen) * 0.5;
+            draw_text(
+                &format!("{:.2}", distance),
+                midpoint.x.floor(),
+                (midpoint.y - 4f32).floor(),
+                FONT_SIZE,
+                theme.status,
+            );
+        }
+
         // Input
 
         let (_, mouse_wheel_y) = mouse_wheel();
@@ -191,39 +775,418 @@ pub async fn show(solutions: &[Vec<solver::Board>]) {
         let left_mouse_down = is_mouse_button_down(MouseButton::Left);
 
         if mouse_wheel_y.abs() > 0f32 {
-            let new_scale = (scale + (mouse_wheel_y * 2f32)).clamp(1f32, 64f32);
-            let old_origin = origin * scale;
+            let new_scale = (transform.scale + (mouse_wheel_y * 2f32)).clamp(1f32, 64f32);
+            let old_origin = transform.apply(origin);
             let old_offset_to_cursor = old_origin - mouse_position;
-            let new_offset_to_cursor = old_offset_to_cursor * new_scale / scale;
+            let new_offset_to_cursor = old_offset_to_cursor * new_scale / transform.scale;
             let new_origin = mouse_position + new_offset_to_cursor;
 
-            scale = new_scale;
-            origin = new_origin / scale;
+            transform.scale = new_scale;
+            origin = transform.invert(new_origin);
         }
 
         if left_mouse_down {
             if let Some(mouse_down_position) = mouse_down_position {
                 let mouse_movement = mouse_position - mouse_down_position;
-                origin += mouse_movement / scale;
+                origin += transform.invert(mouse_movement);
             }
             mouse_down_position = Some(mouse_position);
         } else {
             mouse_down_position = None;
         }
 
-        if is_key_pressed(KeyCode::Space) {
+        if is_mouse_button_pressed(MouseButton::Right) {
+            if measurement.len() == 2 {
+                measurement.clear();
+            }
+            measurement.push(transform.screen_to_model(mouse_position, origin));
+        }
+
+        // the queue holds the characters typed since it was last drained, newest last
+        let typed: Vec<char> = std::iter::from_fn(get_char_pressed).collect();
+        if let Some(text) = &mut probe_input {
+            text.extend(
+                typed
+                    .iter()
+                    .rev()
+                    .filter(|c| c.is_ascii_digit() || **c == '.' || **c == 'x'),
+            );
+            if is_key_pressed(KeyCode::Backspace) {
+                text.pop();
+            }
+            if is_key_pressed(KeyCode::Enter) || is_key_pressed(KeyCode::KpEnter) {
+                probe = parse_probe(text);
+                if probe.is_none() {
+                    warn!("Expected a trial cut, e.g., 24x4, not \"{}\"", text);
+                }
+                probe_input = None;
+            } else if is_key_pressed(KeyCode::Escape) {
+                probe_input = None;
+            }
+        } else if shortcut_pressed(KeyCode::C) {
+            probe_input = Some(String::new());
+            probe = None;
+        }
+
+        if shortcut_pressed(KeyCode::S) {
+            overlays.scores = !overlays.scores;
+        }
+
+        if shortcut_pressed(KeyCode::G) {
+            overlays.grain = !overlays.grain;
+        }
+
+        if shortcut_pressed(KeyCode::T) {
+            overlays.tail = !overlays.tail;
+        }
+
+        if let Some(favorites_path) = favorites_path {
+            if shortcut_pressed(KeyCode::F) {
+                if !favorites.remove(&current_solution_index) {
+                    favorites.insert(current_solution_index);
+                }
+                // written as they change, since the window may be closed at any time
+                if let Err(e) = favorites_json(&solutions, &favorites)
+                    .and_then(|json| Ok(fs::write(favorites_path, json)?))
+                {
+                    error!("Unable to write favorites to {}: {}", favorites_path, e);
+                }
+            }
+        }
+
+        if shortcut_pressed(KeyCode::M) {
+            measurement.clear();
+        }
+
+        if shortcut_pressed(KeyCode::Space) {
             origin = Vec2::new(0f32, 0f32);
-            scale = 16f32;
+            transform.scale = 16f32;
+        }
+
+        if shortcut_pressed(KeyCode::R) {
+            transform.rotated = !transform.rotated;
         }
 
-        if is_key_pressed(KeyCode::J) {
-            current_solution_index = (current_solution_index + 1).min(solutions.len() - 1);
+        if shortcut_pressed(KeyCode::B) {
+            grouped = !grouped;
         }
 
-        if is_key_pressed(KeyCode::K) && current_solution_index > 0 {
-            current_solution_index -= 1;
+        if shortcut_pressed(KeyCode::J) {
+            current_solution_index = if grouped {
+                step_within_group(&groups, current_solution_index, true)
+            } else {
+                (current_solution_index + 1).min(solutions.len() - 1)
+            };
+            revealed = revealed.map(|_| 0);
+        }
+
+        if shortcut_pressed(KeyCode::K) && current_solution_index > 0 {
+            current_solution_index = if grouped {
+                step_within_group(&groups, current_solution_index, false)
+            } else {
+                current_solution_index - 1
+            };
+            revealed = revealed.map(|_| 0);
         }
 
-        next_frame().await
+        if grouped {
+            for (key, forward) in [(KeyCode::H, false), (KeyCode::L, true)] {
+                if shortcut_pressed(key) {
+                    current_solution_index =
+                        step_between_groups(&groups, current_solution_index, forward);
+                    revealed = revealed.map(|_| 0);
+                }
+            }
+        }
+
+        // Placement playback: P plays from the first cut, period and comma step forward and back, escape shows all
+        if shortcut_pressed(KeyCode::P) {
+            playing = !playing;
+            if playing && revealed.is_none_or(|revealed| revealed >= cut_count) {
+                revealed = Some(0);
+            }
+            last_reveal_time = get_time();
+        }
+
+        if shortcut_pressed(KeyCode::Period) {
+            playing = false;
+            revealed = Some(revealed.map_or(0, |revealed| (revealed + 1).min(cut_count)));
+        }
+
+        if shortcut_pressed(KeyCode::Comma) {
+            playing = false;
+            revealed = Some(revealed.map_or(cut_count, |revealed| revealed.saturating_sub(1)));
+        }
+
+        if shortcut_pressed(KeyCode::Escape) {
+            playing = false;
+            revealed = None;
+            probe = None;
+        }
+
+        next_frame().await;
+
+        if solving {
+            solving = false;
+            match resolve(model, &options) {
+                Some((new_options, new_solutions)) => {
+                    options = new_options;
+                    solutions = new_solutions;
+                    groups = board_count_groups(&solutions);
+                    current_solution_index = 0;
+                    revealed = None;
+                    playing = false;
+                    favorites.clear();
+                }
+                None => warn!("No solutions found with seed {}", options.seed + 1),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod visualizer_tests {
+    use super::*;
+
+    #[test]
+    fn resolve_uses_the_next_seed() {
+        let mut model = model::Input::parse(
+            "
+            boards:
+              - 96x8:A
+              - 96x6:B
+            cutlist:
+              - 4@25x2:Leg
+              - 2@12x4:Apron
+              - 4@20x3:Top Table Edges
+            ",
+            model::InputFormat::Yaml,
+        )
+        .unwrap();
+        let options = solver::Options {
+            attempts: 8,
+            ..Default::default()
+        };
+
+        // a new seed shuffles differently, so the solutions may differ, but are still solutions
+        let (resolved_options, solutions) = resolve(&model, &options).unwrap();
+        assert_eq!(resolved_options.seed, options.seed + 1);
+        assert!(!solutions.is_empty());
+        let expected = solver::compute(&model, &resolved_options).unwrap();
+        assert_eq!(solver::score(&solutions[0]), solver::score(&expected[0]));
+
+        // when the search finds nothing, there's nothing to replace the current solutions with
+        model.boards = vec![model::Board::parse("96x8:A=3").unwrap()];
+        assert!(resolve(&model, &options).is_none());
+    }
+
+    #[test]
+    fn transform_scales_without_rotation() {
+        let transform = Transform {
+            scale: 4f32,
+            rotated: false,
+        };
+        assert_eq!(
+            transform.apply(Vec2::new(2f32, 3f32)),
+            Vec2::new(8f32, 12f32)
+        );
+        assert_eq!(
+            transform.invert(Vec2::new(8f32, 12f32)),
+            Vec2::new(2f32, 3f32)
+        );
+    }
+
+    #[test]
+    fn transform_swaps_axes_when_rotated() {
+        let transform = Transform {
+            scale: 4f32,
+            rotated: true,
+        };
+        assert_eq!(
+            transform.apply(Vec2::new(2f32, 3f32)),
+            Vec2::new(12f32, 8f32)
+        );
+        assert_eq!(
+            transform.invert(Vec2::new(12f32, 8f32)),
+            Vec2::new(2f32, 3f32)
+        );
+
+        // a board's length should run down the screen
+        let size = transform.apply(Vec2::new(96f32, 6f32));
+        assert!(size.y > size.x);
+    }
+
+    #[test]
+    fn grain_arrow_runs_along_the_cut() {
+        let [shaft, upper_barb, lower_barb] =
+            grain_arrow(Vec2::new(10f32, 0f32), Vec2::new(20f32, 5f32)).unwrap();
+        assert_eq!(shaft, (Vec2::new(14f32, 3.75), Vec2::new(26f32, 3.75)));
+        assert_eq!(upper_barb, (Vec2::new(26f32, 3.75), Vec2::new(25f32, 3.25)));
+        assert_eq!(lower_barb, (Vec2::new(26f32, 3.75), Vec2::new(25f32, 4.25)));
+
+        // the head shrinks with a narrow cut
+        let [_, upper_barb, _] = grain_arrow(Vec2::ZERO, Vec2::new(20f32, 2f32)).unwrap();
+        assert_eq!(upper_barb.1, Vec2::new(15.6, 1.3));
+
+        assert!(grain_arrow(Vec2::ZERO, Vec2::new(4f32, 5f32)).is_none());
+        assert!(grain_arrow(Vec2::ZERO, Vec2::new(20f32, 1f32)).is_none());
+    }
+
+    #[test]
+    fn trim_regions_lie_beyond_the_usable_length() {
+        let model = model::Input::parse(
+            "end_trim: 1\ntrim_both_ends: true\nboards: [96x8:A]\ncutlist: [1@20x8:Top]",
+            model::InputFormat::Yaml,
+        )
+        .unwrap();
+        let board = solver::Board::from(&model.boards[0]);
+        assert_eq!(board.length, 94f32);
+        assert_eq!(
+            trim_regions(&board),
+            vec![
+                (Vec2::new(-1f32, 0f32), Vec2::new(1f32, 8f32)),
+                (Vec2::new(94f32, 0f32), Vec2::new(1f32, 8f32)),
+            ]
+        );
+        assert!(trim_regions(&solver::Board::from(
+            &model::Board::parse("96x8:A").unwrap()
+        ))
+        .is_empty());
+    }
+
+    #[test]
+    fn tail_region_spans_the_unused_end_of_the_board() {
+        let solution = solver::test_support::solve(
+            "
+            boards:
+              - 96x8:A
+            cutlist:
+              - 2@30x3:Rail
+              - 1@20x8:Top
+            ",
+        );
+        assert_eq!(solution.len(), 1);
+        assert_eq!(
+            tail_region(&solution[0]),
+            Some((Vec2::new(50f32, 0f32), Vec2::new(46f32, 8f32)))
+        );
+
+        let solution = solver::test_support::solve("boards: [48x8:A]\ncutlist: [1@48x8:Top]");
+        assert_eq!(tail_region(&solution[0]), None);
+    }
+
+    #[test]
+    fn measurement_converts_pixels_to_model_distance() {
+        let origin = Vec2::new(2f32, 1f32);
+        let mut transform = Transform {
+            scale: 4f32,
+            rotated: false,
+        };
+        assert_eq!(
+            transform.screen_to_model(Vec2::new(8f32, 12f32), origin),
+            Vec2::new(0f32, 2f32)
+        );
+
+        // 3-4-5 triangle at 4 pixels per unit
+        let start = Vec2::new(0f32, 0f32);
+        let end = Vec2::new(12f32, 16f32);
+        assert!((measure_distance(start, end, origin, transform) - 5f32).abs() < 1e-4);
+
+        // the origin pans the view but doesn't change distances; rotation doesn't either
+        transform.rotated = true;
+        assert!(
+            (measure_distance(start, end, Vec2::new(-7f32, 3f32), transform) - 5f32).abs() < 1e-4
+        );
+    }
+
+    #[test]
+    fn favorites_serialize_in_order() {
+        let board = |id: &str| {
+            solver::Board::from(&crate::model::Board::parse(&format!("96x8:{}", id)).unwrap())
+        };
+        let solutions = vec![vec![board("A")], vec![board("B")], vec![board("C")]];
+        let favorites: HashSet<usize> = vec![2, 0].into_iter().collect();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&favorites_json(&solutions, &favorites).unwrap()).unwrap();
+        let favorites = json.as_array().unwrap();
+        assert_eq!(favorites.len(), 2);
+        assert_eq!(favorites[0]["solution"], 1);
+        assert_eq!(favorites[0]["boards"][0]["id"], "A");
+        assert_eq!(favorites[1]["solution"], 3);
+        assert_eq!(favorites[1]["boards"][0]["id"], "C");
+
+        assert_eq!(favorites_json(&solutions, &HashSet::new()).unwrap(), "[]");
+    }
+
+    #[test]
+    fn theme_parses_colors_over_defaults() {
+        let theme = Theme::parse(
+            "
+            background: '#1e1e1e'
+            cut: '#ff000080'
+            ",
+        )
+        .expect("Expected theme to parse");
+        assert_eq!(
+            theme.background,
+            Color::new(30f32 / 255f32, 30f32 / 255f32, 30f32 / 255f32, 1f32)
+        );
+        assert_eq!(theme.cut, Color::new(1f32, 0f32, 0f32, 128f32 / 255f32));
+        assert_eq!(theme.board, Theme::default().board);
+
+        // json works too
+        let theme = Theme::parse(r##"{ "axis": "#00FF00" }"##).expect("Expected theme to parse");
+        assert_eq!(theme.axis, Color::new(0f32, 1f32, 0f32, 1f32));
+    }
+
+    #[test]
+    fn theme_rejects_bad_colors() {
+        assert!(Theme::parse("background: '#12345'").is_err());
+        assert!(Theme::parse("background: 'white'").is_err());
+        assert!(Theme::parse("background: 12").is_err());
+        assert!(Theme::parse("backdrop: '#000000'").is_err());
+    }
+
+    #[test]
+    fn solutions_group_by_board_count() {
+        let board = solver::Board::from(&crate::model::Board::parse("96x8:A").unwrap());
+        let solution = |board_count: usize| vec![board.clone(); board_count];
+        let solutions = vec![
+            solution(4),
+            solution(5),
+            solution(4),
+            solution(6),
+            solution(5),
+        ];
+
+        let groups = board_count_groups(&solutions);
+        assert_eq!(groups.keys().copied().collect::<Vec<_>>(), vec![4, 5, 6]);
+        assert_eq!(groups[&4], vec![0, 2]);
+        assert_eq!(groups[&5], vec![1, 4]);
+        assert_eq!(groups[&6], vec![3]);
+        assert!(board_count_groups(&[]).is_empty());
+
+        // within a group, stopping at its ends
+        assert_eq!(step_within_group(&groups, 0, true), 2);
+        assert_eq!(step_within_group(&groups, 2, true), 2);
+        assert_eq!(step_within_group(&groups, 4, false), 1);
+        assert_eq!(step_within_group(&groups, 1, false), 1);
+
+        // between groups, landing on the best of each
+        assert_eq!(step_between_groups(&groups, 2, true), 1);
+        assert_eq!(step_between_groups(&groups, 4, true), 3);
+        assert_eq!(step_between_groups(&groups, 3, true), 3);
+        assert_eq!(step_between_groups(&groups, 4, false), 0);
+        assert_eq!(step_between_groups(&groups, 2, false), 2);
+    }
+
+    #[test]
+    fn probes_parse_as_trial_cuts() {
+        assert_eq!(parse_probe("24x4"), Some((24f32, 4f32)));
+        assert_eq!(parse_probe("30.5x3.25"), Some((30.5, 3.25)));
+        assert_eq!(parse_probe("24"), None);
+        assert_eq!(parse_probe("24x"), None);
+        assert_eq!(parse_probe("0x4"), None);
     }
 }
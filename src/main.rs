@@ -1,19 +1,41 @@
 #![allow(dead_code)]
 #![allow(unused_variables)]
 
-mod lib;
-
-use lib::{model, solver, visualizer};
+use cutlist::{ascii, cache, db, export, generate, images, model, solver, svg, table, visualizer};
+use indicatif::{ProgressBar, ProgressStyle};
+use log::{info, warn};
 use macroquad::prelude::*;
-use std::{error::Error, fs};
+use std::{
+    error::Error,
+    fs,
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 use structopt::StructOpt;
-use yaml_rust::YamlLoader;
 
 #[derive(StructOpt, Debug)]
 pub struct Options {
     #[structopt(short, long, default_value = "input.yaml")]
     pub input: String,
 
+    /// Format of the input file, yaml or json; detected from the file extension if not specified
+    #[structopt(long)]
+    pub input_format: Option<model::InputFormat>,
+
+    /// Solve each `---` separated document of a yaml input as a project of its own, reporting each
+    /// in turn, rather than just the first; headless only. With more than one project, file
+    /// exports are numbered after their project, e.g., --pdf cuts.pdf writes cuts-1.pdf, cuts-2.pdf.
+    #[structopt(long)]
+    pub all_documents: bool,
+
+    /// Read numbers in board and cut specs with a comma as the decimal separator, e.g., 2@12,5x4:Apron
+    #[structopt(long)]
+    pub decimal_comma: bool,
+
+    /// Treat questionable input as an error rather than a warning, e.g., a board wider than it is long
+    #[structopt(long)]
+    pub strict: bool,
+
     #[structopt(short, long)]
     pub visualize: bool,
 
@@ -22,6 +44,211 @@ pub struct Options {
 
     #[structopt(short, long, default_value = "1")]
     pub count: usize,
+
+    /// Number cuts which share a name, e.g., "Shelf #1", "Shelf #2"
+    #[structopt(short, long)]
+    pub number_cuts: bool,
+
+    /// Only stack copies of the same part together, at the expense of using more stock
+    #[structopt(short, long)]
+    pub uniform_rips: bool,
+
+    /// Distribute cuts across all available board types rather than filling one type first
+    #[structopt(short, long)]
+    pub balance: bool,
+
+    /// Stop searching after this many seconds, returning the best solutions found so far
+    #[structopt(long)]
+    pub timeout: Option<f64>,
+
+    /// Use at most this many distinct board types, even if more would pack tighter
+    #[structopt(long)]
+    pub max_board_types: Option<usize>,
+
+    /// Use only boards at most this wide, e.g., the capacity of your jointer or planer
+    #[structopt(long)]
+    pub max_board_width: Option<f32>,
+
+    /// What to minimize when ranking solutions: waste, offcuts for the fewest loose pieces,
+    /// cluster for copies of each part on the fewest boards, time for the fewest saw operations,
+    /// weighted by the input's `operation_times`, effort for the least board area to handle, or
+    /// max-waste for the least waste on any one board
+    #[structopt(long, default_value = "waste")]
+    pub minimize: solver::Objective,
+
+    /// Treat board widths as nominal lumber sizes (e.g., a 4 is 3.5 wide), solving with their actual widths
+    #[structopt(long)]
+    pub nominal: bool,
+
+    /// Leave out cuts shorter than this, which are unsafe to crosscut
+    #[structopt(long)]
+    pub min_crosscut_width: Option<f32>,
+
+    /// Prefer solutions leaving one board mostly unused, as a spare for mistakes
+    #[structopt(long)]
+    pub reserve_board: bool,
+
+    /// Place cuts once, in the order they're listed, rather than searching shuffled attempts
+    #[structopt(long)]
+    pub preserve_order: bool,
+
+    /// A cut only joins a stack whose length is within this fraction of its own, e.g., 0.5 for 50%
+    #[structopt(long, default_value = "0.5")]
+    pub stack_fit_tolerance: f32,
+
+    /// In headless mode, print cut positions rounded to this increment, e.g., 0.0625 for 1/16, for
+    /// marking by hand; solutions keep their exact positions
+    #[structopt(long)]
+    pub snap: Option<f32>,
+
+    /// Improve each solution found by moving and swapping single cuts between its boards while
+    /// that improves on it
+    #[structopt(long)]
+    pub polish: bool,
+
+    /// Place cuts just once, largest first, returning that layout (polished, with --polish) rather
+    /// than searching many attempts for the best
+    #[structopt(long)]
+    pub fast: bool,
+
+    /// Each attempt places cuts largest first, shuffling only those of equal area, rather than
+    /// placing them in a random order
+    #[structopt(long)]
+    pub shuffle_ties: bool,
+
+    /// Lay out every copy of a cut the same way, all rotated or none, as if each cut were listed
+    /// in the input's `uniform_orientation`; only sheet stock rotates cuts
+    #[structopt(long)]
+    pub uniform_orientation: bool,
+
+    /// Refuse any placement which would leave a board that can't be cut edge-to-edge, checking
+    /// each board's layout as cuts are placed on it
+    #[structopt(long)]
+    pub strict_guillotine: bool,
+
+    /// Print the best solution to the terminal rather than showing the visualizer
+    #[structopt(long)]
+    pub headless: bool,
+
+    /// Also advise on pairs of cuts which could be cut as a single blank and then split
+    #[structopt(long)]
+    pub advise: bool,
+
+    /// Print an area-based estimate of the stock required, without solving
+    #[structopt(long)]
+    pub preview: bool,
+
+    /// A yaml or json file of visualizer colors, e.g. { background: "#1e1e1e" }
+    #[structopt(long)]
+    pub theme: Option<String>,
+
+    /// In headless mode, also print the saw operations in an order minimizing fence changes
+    #[structopt(long)]
+    pub operations: bool,
+
+    /// In headless mode, also print a timeline of the saw operations by phase, estimating their
+    /// durations from the input's `operation_times` in seconds
+    #[structopt(long)]
+    pub schedule: bool,
+
+    /// In headless mode, print the best solution as a flat table of cuts by board and position instead
+    #[structopt(long)]
+    pub table_flat: bool,
+
+    /// In headless mode, print the best solution as ascii drawings of its boards instead, e.g. for a report
+    #[structopt(long)]
+    pub ascii: bool,
+
+    /// In headless mode, also explain why each cut was placed where it was
+    #[structopt(long)]
+    pub explain: bool,
+
+    /// In headless mode, also write a printable shopping list and cut sheet of the best solution to this pdf
+    #[structopt(long)]
+    pub pdf: Option<String>,
+
+    /// In headless mode, also write the best solution's flat cut table to this csv, a row per piece
+    #[structopt(long)]
+    pub csv: Option<String>,
+
+    /// In headless mode, also write a bill of materials to this json file: the best solution's
+    /// boards, counted by id, and the input's `hardware` list
+    #[structopt(long)]
+    pub bom: Option<String>,
+
+    /// In headless mode, also write a pdf of sticker labels, one per piece of the best solution, to this path
+    #[structopt(long)]
+    pub labels: Option<String>,
+
+    /// The label sheet --labels prints on: avery-5160 (30 per page) or avery-5163 (10 per page)
+    #[structopt(long, default_value = "avery-5160")]
+    pub label_template: export::LabelTemplate,
+
+    /// In headless mode, also write the solutions found (see --count) to this svg, one below the
+    /// next, labeled with their scores
+    #[structopt(long)]
+    pub svg_all: Option<String>,
+
+    /// In headless mode, also write each board of the best solution to its own png in this directory,
+    /// e.g., for a shop tablet
+    #[structopt(long)]
+    pub images: Option<String>,
+
+    /// In headless mode, also write the offcuts of the best solution worth keeping (see
+    /// --min-offcut) to this yaml file as a `scrap:` list, which may be pasted into a future input
+    #[structopt(long)]
+    pub offcut_inventory: Option<String>,
+
+    /// The smallest offcut --offcut-inventory keeps, as length by width
+    #[structopt(long, default_value = "12x2", parse(try_from_str = parse_size))]
+    pub min_offcut: (f32, f32),
+
+    /// In headless mode, also record the solve and its best solution in this sqlite database,
+    /// creating it if need be, to query past solves of your projects
+    #[structopt(long)]
+    pub db: Option<String>,
+
+    /// In headless mode, also write the best solution to this json file, e.g., for --warm-start
+    #[structopt(long)]
+    pub save_solution: Option<String>,
+
+    /// Start from the solution in this json file (see --save-solution), keeping unchanged cuts where
+    /// they were and only placing the rest
+    #[structopt(long)]
+    pub warm_start: Option<String>,
+
+    /// In the visualizer, F favorites the current solution; favorites are saved to this json file
+    #[structopt(long)]
+    pub favorites: Option<String>,
+
+    /// Search with each of this many seeds, keeping the overall best solutions
+    #[structopt(long)]
+    pub seed_sweep: Option<u64>,
+
+    /// Reuse solutions cached in this directory for identical input and options, caching new ones there.
+    /// Not used with --seed-sweep
+    #[structopt(long)]
+    pub cache_dir: Option<String>,
+
+    /// Print a random, solvable input document with this many kinds of cut, e.g., for a demo, and exit
+    #[structopt(long)]
+    pub generate_input: Option<usize>,
+
+    /// Suppress progress output
+    #[structopt(short, long)]
+    pub quiet: bool,
+}
+
+// parses a size in form of: 12x2
+fn parse_size(size: &str) -> Result<(f32, f32), String> {
+    let parse = || -> Option<(f32, f32)> {
+        let (length, width) = size.split_once("x")?;
+        Some((length.trim().parse().ok()?, width.trim().parse().ok()?))
+    };
+    match parse() {
+        Some((length, width)) if length >= 0f32 && width >= 0f32 => Ok((length, width)),
+        _ => Err(format!("Invalid size \"{}\", expected e.g., 12x2", size)),
+    }
 }
 
 fn window_conf() -> Conf {
@@ -34,19 +261,458 @@ fn window_conf() -> Conf {
     }
 }
 
-#[macroquad::main(window_conf)]
-async fn main() -> Result<(), Box<dyn Error>> {
+/// Creates a progress bar tracking solver attempts, hidden if `quiet`
+fn progress_bar(quiet: bool) -> ProgressBar {
+    if quiet {
+        return ProgressBar::hidden();
+    }
+    let progress_bar = ProgressBar::new(0);
+    progress_bar.set_style(
+        ProgressStyle::with_template("{bar:40} {pos}/{len} attempts ({percent}%) ETA {eta}")
+            .unwrap(),
+    );
+    progress_bar
+}
+
+fn compute_with_progress_bar(
+    doc: &model::Input,
+    options: &solver::Options,
+    quiet: bool,
+) -> anyhow::Result<Vec<Vec<solver::Board>>> {
+    let progress_bar = progress_bar(quiet);
+    let (solutions, convergence) =
+        solver::compute_with_convergence(doc, options, |completed, total| {
+            progress_bar.set_length(total as u64);
+            progress_bar.set_position(completed as u64);
+            if completed == total {
+                progress_bar.finish_and_clear();
+            }
+        })?;
+    // the search may have stopped early on timeout
+    progress_bar.finish_and_clear();
+    info!("{}", convergence);
+    Ok(solutions)
+}
+
+// rounds a position for display if snapping
+fn position(value: f32, snap: Option<f32>) -> f32 {
+    snap.map_or(value, |increment| solver::snap(value, increment))
+}
+
+fn print_solution(solution: &[solver::Board], snap: Option<f32>) {
+    for board in solution {
+        println!(
+            "{} ({} by {})",
+            board.id, board.nominal_length, board.nominal_width
+        );
+        let mut offset = 0f32;
+        for (i, stack) in board.stacks.iter().enumerate() {
+            offset += stack.length();
+            let crosscut = if board.requires_crosscut_at(offset) {
+                format!("crosscut at {}", position(offset, snap))
+            } else {
+                "rip only".to_owned()
+            };
+            let cuts: Vec<String> = stack
+                .cuts
+                .iter()
+                .map(|cut| {
+                    let mut notes = vec![format!("{} by {}", cut.length, cut.width)];
+                    notes.extend(cut.shape.clone());
+                    if cut.double_sided {
+                        notes.push("double-sided, mark the face up first".to_owned());
+                    }
+                    format!("{} ({})", cut.id, notes.join(", "))
+                })
+                .collect();
+            println!("    Stack {} ({}): {}", i + 1, crosscut, cuts.join(", "));
+        }
+    }
+}
+
+fn print_sheets(sheets: &[solver::SheetPacker]) {
+    for sheet in sheets {
+        println!("{} ({} by {})", sheet.id, sheet.length, sheet.width);
+        for placement in &sheet.placements {
+            println!(
+                "    {} ({} by {}) at ({}, {}){}",
+                placement.cut.id,
+                placement.cut.length,
+                placement.cut.width,
+                placement.x,
+                placement.y,
+                if placement.rotated { ", rotated" } else { "" }
+            );
+        }
+    }
+}
+
+fn print_flat_table(solution: &[solver::Board], snap: Option<f32>) {
+    println!("Board\tCut\tX\tY\tLength\tWidth\tRotated\tDouble-sided");
+    for row in solver::flat_cut_table(solution) {
+        println!(
+            "{} {}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            row.board + 1,
+            row.board_id,
+            row.cut,
+            position(row.x, snap),
+            position(row.y, snap),
+            row.length,
+            row.width,
+            if row.rotated { "yes" } else { "no" },
+            if row.double_sided { "yes" } else { "no" }
+        );
+    }
+}
+
+fn print_explanations(solution: &[solver::Board]) {
+    let mut placements: Vec<(usize, &solver::Board, &solver::Cut)> = solution
+        .iter()
+        .enumerate()
+        .flat_map(|(i, board)| board.cuts().into_iter().map(move |cut| (i, board, cut)))
+        .collect();
+    placements.sort_by_key(|(_, _, cut)| cut.placement);
+
+    println!("Placements:");
+    for (i, board, cut) in placements {
+        let reason = cut
+            .outcome
+            .as_ref()
+            .map(|outcome| outcome.reason())
+            .unwrap_or_default();
+        println!(
+            "    {}: {} on board {} {}: {}",
+            cut.placement + 1,
+            cut.id,
+            i + 1,
+            board.id,
+            reason
+        );
+    }
+}
+
+fn print_operations(solution: &[solver::Board]) {
+    println!("Operations:");
+    for (i, step) in solver::optimize_operation_order(solution)
+        .iter()
+        .enumerate()
+    {
+        let board = &solution[step.board];
+        let cut = step
+            .cut
+            .as_ref()
+            .map(|cut| format!(" -> {}", cut))
+            .unwrap_or_default();
+        println!(
+            "    {}: {:?} at {} (board {} {}, stack {}){}",
+            i + 1,
+            step.operation,
+            step.setting,
+            step.board + 1,
+            board.id,
+            step.stack + 1,
+            cut
+        );
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    // diagnostics go to stderr, at info and above unless RUST_LOG says otherwise, e.g., debug to
+    // follow each placement, or warn for quiet operation
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info"))
+        .format_timestamp(None)
+        .format_target(false)
+        .init();
     let opt = Options::from_args();
 
-    let input_str = fs::read_to_string(opt.input)?;
-    let input_yaml = YamlLoader::load_from_str(&input_str)?;
-    if let Some(doc) = input_yaml.first() {
-        let doc = model::Input::from(doc)?;
-        if let Some(solutions) = solver::compute(&doc, opt.attempts, opt.count) {
-            if !solutions.is_empty() {
-                visualizer::show(&solutions).await;
+    if let Some(cut_types) = opt.generate_input {
+        let seed = SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos() as u64;
+        print!("{}", generate::random_input(cut_types, seed));
+        return Ok(());
+    }
+
+    let input_format = opt
+        .input_format
+        .unwrap_or_else(|| model::InputFormat::from_path(&opt.input));
+    let input_str = fs::read_to_string(&opt.input)?;
+    let docs = match (opt.all_documents, opt.decimal_comma) {
+        (true, true) => model::Input::parse_all_decimal_comma(&input_str, input_format)?,
+        (true, false) => model::Input::parse_all(&input_str, input_format)?,
+        (false, true) => vec![model::Input::parse_decimal_comma(&input_str, input_format)?],
+        (false, false) => vec![model::Input::parse(&input_str, input_format)?],
+    };
+    if docs.len() > 1 && !opt.headless {
+        return Err("The visualizer shows a single project, use --headless to solve each".into());
+    }
+
+    let project_count = docs.len();
+    if project_count == 1 {
+        return solve(&opt, docs.into_iter().next().unwrap(), &input_str, None);
+    }
+    // each project is recorded under its own document's text, not the whole file's
+    let doc_texts = model::Input::document_texts(&input_str, input_format)?;
+    for (i, (doc, doc_text)) in docs.into_iter().zip(&doc_texts).enumerate() {
+        println!("Project {} of {}", i + 1, project_count);
+        solve(&opt, doc, doc_text, Some(i + 1))?;
+    }
+    Ok(())
+}
+
+// the path a file export of the project is written to: as given for a lone project, otherwise
+// numbered after it, e.g., cuts.pdf for project 2 is cuts-2.pdf
+fn project_path(path: &str, project: Option<usize>) -> String {
+    let project = match project {
+        Some(project) => project,
+        None => return path.to_owned(),
+    };
+    let path = Path::new(path);
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy())
+        .unwrap_or_default();
+    let name = match path.extension() {
+        Some(extension) => format!("{}-{}.{}", stem, project, extension.to_string_lossy()),
+        None => format!("{}-{}", stem, project),
+    };
+    path.with_file_name(name).to_string_lossy().into_owned()
+}
+
+// solves a project, reporting and exporting its solutions per the options, or showing them in the
+// visualizer; `project` numbers its exports when the input holds several
+fn solve(
+    opt: &Options,
+    mut doc: model::Input,
+    input_str: &str,
+    project: Option<usize>,
+) -> Result<(), Box<dyn Error>> {
+    let export_path = |path: &String| project_path(path, project);
+    if opt.nominal {
+        doc.convert_nominal_sizes();
+    }
+    let transposed_boards = doc.transposed_boards();
+    if opt.strict && !transposed_boards.is_empty() {
+        let specs: Vec<String> = transposed_boards.iter().map(|b| b.to_string()).collect();
+        return Err(format!(
+            "Boards wider than they are long, were their length and width transposed? {}",
+            specs.join(", ")
+        )
+        .into());
+    }
+
+    if opt.preview {
+        let preview = solver::preview(&doc);
+        println!("Total cut area: {:.2} sq in", preview.total_cut_area);
+        println!("Board-feet (at 4/4): {:.2}", preview.cut_board_feet);
+        println!("Estimated boards: at least {}", preview.estimated_boards);
+        return Ok(());
+    }
+
+    if opt.advise {
+        for combined in solver::suggest_combined_cuts(&doc) {
+            println!(
+                "Consider cutting {} and {} from a single {}x{} blank, then splitting it",
+                combined.names[0], combined.names[1], combined.length, combined.width
+            );
+        }
+    }
+
+    let warm_start = match &opt.warm_start {
+        Some(path) => Some(serde_json::from_str(&fs::read_to_string(path)?)?),
+        None => None,
+    };
+    let solver_options = solver::Options {
+        attempts: opt.attempts,
+        result_count: opt.count,
+        number_cuts: opt.number_cuts,
+        uniform_rips: opt.uniform_rips,
+        balance: opt.balance,
+        max_board_types: opt.max_board_types,
+        max_board_width: opt.max_board_width,
+        timeout: opt.timeout.map(Duration::from_secs_f64),
+        objective: opt.minimize,
+        min_crosscut_width: opt.min_crosscut_width,
+        reserve_board: opt.reserve_board,
+        stack_fit_tolerance: opt.stack_fit_tolerance,
+        preserve_order: opt.preserve_order,
+        warm_start,
+        polish: opt.polish,
+        fast: opt.fast,
+        shuffle_ties: opt.shuffle_ties,
+        uniform_orientation: opt.uniform_orientation,
+        strict_guillotine: opt.strict_guillotine,
+        ..Default::default()
+    };
+    let orphaned_cuts = solver::orphaned_cuts(&doc, &solver_options);
+    if !orphaned_cuts.is_empty() {
+        warn!(
+            "Left out cuts too short to crosscut safely: {}",
+            orphaned_cuts.join(", ")
+        );
+    }
+    let oversize_cuts = doc.oversize_cuts();
+    if !oversize_cuts.is_empty() {
+        let (length, width) = doc.max_handling.unwrap_or_default();
+        warn!(
+            "*** Parts larger than {}x{} need help to handle: {} ***",
+            length,
+            width,
+            oversize_cuts.join(", ")
+        );
+    }
+
+    for (board, count) in solver::suggest_additional_stock(&doc, &solver_options) {
+        warn!(
+            "Adding {} more {}x{}:{} board{} would place all cuts",
+            count,
+            board.length,
+            board.width,
+            board.id,
+            if count == 1 { "" } else { "s" }
+        );
+    }
+
+    if doc.boards.iter().any(|board| board.sheet) {
+        if !opt.headless {
+            return Err("The visualizer can't show sheet stock yet, use --headless".into());
+        }
+        print_sheets(&solver::pack_sheets(&doc, &solver_options)?);
+        return Ok(());
+    }
+
+    if opt.headless {
+        let solutions = match (opt.seed_sweep, &opt.cache_dir) {
+            (Some(seeds), _) => {
+                let (seed, solutions) = solver::seed_sweep(&doc, &solver_options, seeds)?;
+                info!("Best solution found with seed {}", seed);
+                solutions
+            }
+            (None, Some(cache_dir)) => {
+                cache::cached(&doc, &solver_options, Path::new(cache_dir), || {
+                    compute_with_progress_bar(&doc, &solver_options, opt.quiet)
+                })?
+            }
+            (None, None) => compute_with_progress_bar(&doc, &solver_options, opt.quiet)?,
+        };
+        if solutions.is_empty() {
+            return Err("No solutions were found".into());
+        }
+        if let Some(increment) = opt.snap {
+            let overlaps = solver::snap_overlaps(&solutions[0], increment);
+            if !overlaps.is_empty() {
+                warn!(
+                    "Snapping to {} overlaps cuts {}",
+                    increment,
+                    overlaps.join(", ")
+                );
+            }
+        }
+        if opt.table_flat {
+            print_flat_table(&solutions[0], opt.snap);
+        } else if opt.ascii {
+            println!("{}", ascii::render(&solutions[0], ascii::COLUMNS));
+        } else {
+            print_solution(&solutions[0], opt.snap);
+        }
+        for (name, count) in solver::fill_counts(&doc, &solutions[0]) {
+            println!("Made {} {} from leftover stock", count, name);
+        }
+        if let Some(weight) = solver::solution_weight(&doc, &solutions[0]) {
+            println!("Estimated weight: {:.1} lb", weight);
+        }
+        if let Some(board) = solver::longest_board(&solutions[0]) {
+            println!(
+                "Longest board: {} ({} by {}); longest crosscut section: {}",
+                board.id,
+                board.nominal_length,
+                board.nominal_width,
+                solver::longest_stack(&solutions[0])
+            );
+        }
+        let (crosscuts, rips) = solver::operation_counts(&solutions[0]);
+        println!(
+            "Estimated cutting time: {:.0} ({} crosscuts, {} rips)",
+            solver::cutting_time(&solutions[0], &doc.operation_times),
+            crosscuts,
+            rips
+        );
+        if !doc.hardware.is_empty() {
+            println!("Hardware:");
+            for item in &doc.hardware {
+                println!("    {}", item);
+            }
+        }
+        if opt.explain {
+            print_explanations(&solutions[0]);
+        }
+        if opt.operations {
+            print_operations(&solutions[0]);
+        }
+        if opt.schedule {
+            print!(
+                "{}",
+                solver::build_schedule(&solutions[0], &doc.operation_times)
+            );
+        }
+        if let Some(path) = opt.pdf.as_ref().map(export_path) {
+            export::pdf(&solutions[0], &path)?;
+        }
+        if let Some(path) = opt.bom.as_ref().map(export_path) {
+            let bill = solver::bill_of_materials(&doc, &solutions[0]);
+            fs::write(path, serde_json::to_string_pretty(&bill)?)?;
+        }
+        if let Some(path) = opt.csv.as_ref().map(export_path) {
+            table::csv(&solutions[0], &path)?;
+        }
+        if let Some(path) = opt.labels.as_ref().map(export_path) {
+            export::labels(&solutions[0], &opt.label_template, &path)?;
+        }
+        if let Some(path) = opt.svg_all.as_ref().map(export_path) {
+            fs::write(path, svg::svg_all(&solutions))?;
+        }
+        if let Some(dir) = opt.images.as_ref().map(export_path) {
+            images::write_board_images(&solutions[0], Path::new(&dir))?;
+        }
+        if let Some(path) = opt.offcut_inventory.as_ref().map(export_path) {
+            fs::write(
+                path,
+                solver::offcut_inventory(&solutions[0], opt.min_offcut),
+            )?;
+        }
+        if let Some(path) = &opt.db {
+            let mut conn = db::open(Path::new(path))?;
+            db::record_solve(&mut conn, &db::input_hash(input_str), &solutions[0])?;
+        }
+        if let Some(path) = opt.save_solution.as_ref().map(export_path) {
+            fs::write(path, serde_json::to_string_pretty(&solutions[0])?)?;
+        }
+    } else {
+        let theme = match &opt.theme {
+            Some(path) => visualizer::Theme::parse(&fs::read_to_string(path)?)?,
+            None => visualizer::Theme::default(),
+        };
+        let solutions = match (opt.seed_sweep, &opt.cache_dir) {
+            (Some(seeds), _) => solver::seed_sweep(&doc, &solver_options, seeds)?.1,
+            (None, Some(cache_dir)) => {
+                cache::cached(&doc, &solver_options, Path::new(cache_dir), || {
+                    solver::compute(&doc, &solver_options)
+                })?
             }
+            (None, None) => solver::compute(&doc, &solver_options)?,
+        };
+        if solutions.is_empty() {
+            return Err("No solutions were found".into());
         }
+        let favorites = opt.favorites.clone();
+        macroquad::Window::from_config(window_conf(), async move {
+            visualizer::show(
+                &doc,
+                &solver_options,
+                solutions,
+                &theme,
+                favorites.as_deref(),
+            )
+            .await;
+        });
     }
 
     Ok(())